@@ -1,3 +1,5 @@
 #!/bin/bash
 
-for d in day[0-2][0-9]; do (echo ">>> $d"; cd $d/rs; time cargo $*); done
+# Solve every day with the aoc runner, passing the arguments to `aoc run`,
+# as in `./cargo-all.sh --day 3-7 --report timings.csv`.
+cd "$(dirname "$0")/common/rs/cli" && cargo run --release -q -- run "$@"
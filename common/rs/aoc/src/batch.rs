@@ -0,0 +1,313 @@
+//! Solving many inputs of a day in parallel.
+//!
+//! [`solve`] solves the parts of every [`Input`] on the `rayon` pool it
+//! is called in, each on its own with [`Puzzle::solve_timed`], and
+//! [`table`] lays out the answers and the timings:
+//!
+//! ```text
+//! input  part  answer                                                           parse      solve
+//! alice  1     488                                                              183.200µs  29.023ms
+//! bob    1     invalid input: line 3, column 7: expected 3 coordinates: "2,0"
+//! carol  1     panicked: no cut of 3 wires
+//! ```
+//!
+//! A solver panicking on an input fails its row only, the other inputs
+//! are still solved.
+//!
+//! The solvers must not share state between inputs solved at the same
+//! time, as a memo in a static would: the days keep such state in the
+//! solve, for the batches to give the answers of the inputs solved one
+//! at a time.
+
+use std::any::Any;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::{ParseError, Part, Puzzle, Timed};
+
+/// Input of a batch, named after its file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+impl Input {
+    /// Inputs in the files of `dir`, sorted by name.
+    ///
+    /// # Errors
+    /// Return an error if the directory or one of its files cannot be read
+    pub fn read_dir(dir: &Path) -> io::Result<Vec<Self>> {
+        let mut inputs = fs::read_dir(dir)?
+            .map(|entry| {
+                let entry = entry?;
+                if !entry.file_type()?.is_file() {
+                    return Ok(None);
+                }
+                Ok(Some(Input {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    text: fs::read_to_string(entry.path())?,
+                }))
+            })
+            .filter_map(Result::transpose)
+            .collect::<io::Result<Vec<_>>>()?;
+        inputs.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(inputs)
+    }
+}
+
+/// Why a part of an input has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// Error found in the input.
+    Invalid(ParseError),
+    /// Message of the panic of the solver.
+    Panicked(String),
+}
+
+/// Part of an input solved, or why it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub input: String,
+    pub part: Part,
+    pub result: Result<Timed, Failure>,
+}
+
+/// Solve `parts` of every input in parallel, in the order of the inputs
+/// and then of the parts.
+pub fn solve(puzzle: &dyn Puzzle, inputs: &[Input], parts: &[Part]) -> Vec<Solved> {
+    let _span = crate::span!("batch", inputs = inputs.len());
+    inputs
+        .iter()
+        .flat_map(|input| parts.iter().map(move |&part| (input, part)))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(input, part)| {
+            let mut last = Instant::now();
+            let mut lap = || {
+                let now = Instant::now();
+                let elapsed = now - last;
+                last = now;
+                elapsed
+            };
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                puzzle.solve_timed(part, &input.text, &mut lap)
+            }));
+            Solved {
+                input: input.name.clone(),
+                part,
+                result: match result {
+                    Ok(result) => result.map_err(Failure::Invalid),
+                    Err(payload) => Err(Failure::Panicked(message(payload.as_ref()))),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Message of a panic, as the default hook prints it.
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// Table with a row for every part solved, the failure in place of the
+/// answer and of the timings for the parts that failed.
+pub fn table(solved: &[Solved]) -> String {
+    let rows = solved
+        .iter()
+        .map(|solved| {
+            let (answer, parse, solve) = match &solved.result {
+                Ok(timed) => (
+                    timed.answer.clone(),
+                    duration(timed.parse),
+                    duration(timed.solve),
+                ),
+                Err(Failure::Invalid(err)) => (
+                    format!("invalid input: {err}"),
+                    String::new(),
+                    String::new(),
+                ),
+                Err(Failure::Panicked(message)) => {
+                    (format!("panicked: {message}"), String::new(), String::new())
+                }
+            };
+            [
+                solved.input.clone(),
+                solved.part.to_string(),
+                answer,
+                parse,
+                solve,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let header = ["input", "part", "answer", "parse", "solve"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in [header].iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(table, "{}", line.trim_end());
+    }
+    table
+}
+
+fn duration(duration: Duration) -> String {
+    format!("{duration:.3?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, Solution};
+
+    struct Words;
+
+    impl Solution for Words {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Words";
+
+        type Input<'a> = Vec<&'a str>;
+        type Output1 = usize;
+        type Output2 = String;
+
+        fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            match input.find(|c: char| c.is_ascii_digit()) {
+                Some(i) => Err(crate::parse::Parser::new(input)
+                    .error(&input[i..=i], ErrorKind::Expected("a word"))),
+                None => Ok(input.split_whitespace().collect()),
+            }
+        }
+
+        fn part_1(words: &Self::Input<'_>) -> Self::Output1 {
+            words.len()
+        }
+
+        fn part_2(words: &Self::Input<'_>) -> Self::Output2 {
+            assert!(!words.contains(&"panic"), "panic at word {}", words.len());
+            words.concat()
+        }
+    }
+
+    fn input(name: &str, text: &str) -> Input {
+        Input {
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn solve_in_order() {
+        let inputs = (0..20)
+            .map(|i| input(&format!("input{i:02}"), &"a b ".repeat(i)))
+            .chain([input("invalid", "a 1")])
+            .collect::<Vec<_>>();
+
+        let solved = solve(&Words, &inputs, &Part::ALL);
+        assert_eq!(solved.len(), 42);
+        for (solved, (input, part)) in solved.iter().zip(
+            inputs
+                .iter()
+                .flat_map(|input| Part::ALL.map(|part| (input, part))),
+        ) {
+            assert_eq!((&solved.input, solved.part), (&input.name, part));
+            assert_eq!(
+                solved.result.clone().map(|timed| timed.answer),
+                Words.solve(part, &input.text).map_err(Failure::Invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn solve_after_panic() {
+        let inputs = [
+            input("a", "a"),
+            input("panic", "a panic"),
+            input("b", "b b"),
+        ];
+
+        let solved = solve(&Words, &inputs, &[Part::Two])
+            .into_iter()
+            .map(|solved| solved.result.map(|timed| timed.answer))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            solved,
+            [
+                Ok("a".to_string()),
+                Err(Failure::Panicked("panic at word 2".to_string())),
+                Ok("bb".to_string()),
+            ]
+        );
+        assert_eq!(message(&"static"), "static");
+        assert_eq!(message(&1), "Box<dyn Any>");
+    }
+
+    #[test]
+    fn table_of_solved() {
+        let solved = [
+            Solved {
+                input: "alice".to_string(),
+                part: Part::One,
+                result: Ok(Timed {
+                    answer: "488".to_string(),
+                    parse: Duration::from_micros(15),
+                    solve: Duration::from_millis(2),
+                }),
+            },
+            Solved {
+                input: "bob".to_string(),
+                part: Part::Two,
+                result: Words::try_parse("a 1")
+                    .map(|_| unreachable!())
+                    .map_err(Failure::Invalid),
+            },
+            Solved {
+                input: "carol".to_string(),
+                part: Part::One,
+                result: Err(Failure::Panicked("no cut".to_string())),
+            },
+        ];
+        assert_eq!(
+            table(&solved),
+            r#"input  part  answer                                                 parse     solve
+alice  1     488                                                    15.000µs  2.000ms
+bob    2     invalid input: line 1, column 3: expected a word: "1"
+carol  1     panicked: no cut
+"#
+        );
+    }
+
+    #[test]
+    fn read_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("subdir")).unwrap();
+        fs::write(dir.join("b"), "b b").unwrap();
+        fs::write(dir.join("a"), "a").unwrap();
+
+        let inputs = Input::read_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(inputs.unwrap(), [input("a", "a"), input("b", "b b")]);
+    }
+}
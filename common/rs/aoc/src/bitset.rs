@@ -0,0 +1,792 @@
+//! Sets of small integers stored one bit per element.
+//!
+//! [`BitSet`] grows as needed to hold its largest element,
+//! [`InlineBitSet`] has a fixed capacity and is `Copy`, so it fits
+//! search states and memoization keys:
+//!
+//! ```
+//! use aoc::bitset::{BitSet, InlineBitSet};
+//!
+//! let a = [1, 3, 200].into_iter().collect::<BitSet>();
+//! let b = [3, 4].into_iter().collect::<BitSet>();
+//! assert_eq!((&a | &b).iter().collect::<Vec<_>>(), [1, 3, 4, 200]);
+//! assert_eq!((a.rank(100), a.select(2)), (2, Some(200)));
+//!
+//! let mut visited = InlineBitSet::<1>::new();
+//! visited.insert(63);
+//! assert!(visited.contains(63) && !visited.contains(64));
+//! ```
+
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+use std::{array, iter, slice, vec};
+
+/// Storage unit of the sets.
+pub type Word = u64;
+
+/// Number of elements in a [`Word`].
+pub const WORD_BITS: usize = Word::BITS as usize;
+
+/// Growable set of `usize`.
+///
+/// Memory is proportional to the largest element, not to the number of
+/// elements.
+#[derive(Clone, Default)]
+pub struct BitSet {
+    // may end with zero words, left by removals or by the capacity
+    words: Vec<Word>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty set that holds the elements below `capacity` without
+    /// allocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Set of the bits of `words`, the element `i` is the bit
+    /// `i % WORD_BITS` of the word `i / WORD_BITS`.
+    pub fn from_words(words: Vec<Word>) -> Self {
+        Self { words }
+    }
+
+    /// The words of the set, as taken by [`BitSet::from_words`],
+    /// without the trailing zero words.
+    pub fn words(&self) -> &[Word] {
+        let len = self.words.iter().rposition(|&word| word != 0);
+        &self.words[..len.map_or(0, |index| index + 1)]
+    }
+
+    pub fn len(&self) -> usize {
+        len(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    #[inline]
+    pub fn contains(&self, value: usize) -> bool {
+        contains(&self.words, value)
+    }
+
+    /// Add `value`, returns whether it was not in the set.
+    #[inline]
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (index, bit) = position(value);
+        if index >= self.words.len() {
+            self.words.resize(index + 1, 0);
+        }
+
+        let word = &mut self.words[index];
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+
+    /// Remove `value`, returns whether it was in the set.
+    #[inline]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (index, bit) = position(value);
+        let Some(word) = self.words.get_mut(index) else {
+            return false;
+        };
+
+        let removed = *word & bit != 0;
+        *word &= !bit;
+        removed
+    }
+
+    /// Smallest element.
+    pub fn first(&self) -> Option<usize> {
+        first(&self.words)
+    }
+
+    /// Largest element.
+    pub fn last(&self) -> Option<usize> {
+        last(&self.words)
+    }
+
+    /// Elements in increasing order.
+    pub fn iter(&self) -> Iter<iter::Copied<slice::Iter<'_, Word>>> {
+        Iter::new(self.words.iter().copied())
+    }
+
+    /// Number of elements less than `value`.
+    pub fn rank(&self, value: usize) -> usize {
+        rank(&self.words, value)
+    }
+
+    /// The `n`-th smallest element, counting from 0.
+    pub fn select(&self, n: usize) -> Option<usize> {
+        select(&self.words, n)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        is_subset(&self.words, &other.words)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        is_disjoint(&self.words, &other.words)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.union_with(other);
+        union
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = self.clone();
+        intersection.intersect_with(other);
+        intersection
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        difference.difference_with(other);
+        difference
+    }
+
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        difference.symmetric_difference_with(other);
+        difference
+    }
+
+    /// Add the elements of `other`.
+    pub fn union_with(&mut self, other: &Self) {
+        if self.words.len() < other.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Keep only the elements of `other`.
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.words.truncate(other.words.len());
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    /// Remove the elements of `other`.
+    pub fn difference_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+    }
+
+    /// Keep the elements in only one of the sets.
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        if self.words.len() < other.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other;
+        }
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.words() == other.words()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.words().hash(state);
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut set = Self::new();
+        set.extend(values);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Iter<iter::Copied<slice::Iter<'a, Word>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for BitSet {
+    type Item = usize;
+    type IntoIter = Iter<vec::IntoIter<Word>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.words.into_iter())
+    }
+}
+
+macro_rules! impl_bit_set_ops {
+    ($($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $with:ident;)*) => {
+        $(
+            impl $op<&BitSet> for &BitSet {
+                type Output = BitSet;
+
+                fn $op_fn(self, other: &BitSet) -> BitSet {
+                    let mut set = self.clone();
+                    set.$with(other);
+                    set
+                }
+            }
+
+            impl $assign<&BitSet> for BitSet {
+                fn $assign_fn(&mut self, other: &BitSet) {
+                    self.$with(other);
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_set_ops! {
+    BitOr, bitor, BitOrAssign, bitor_assign, union_with;
+    BitAnd, bitand, BitAndAssign, bitand_assign, intersect_with;
+    BitXor, bitxor, BitXorAssign, bitxor_assign, symmetric_difference_with;
+    Sub, sub, SubAssign, sub_assign, difference_with;
+}
+
+/// Set of `usize` less than `WORDS * WORD_BITS`, stored inline.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InlineBitSet<const WORDS: usize> {
+    words: [Word; WORDS],
+}
+
+impl<const WORDS: usize> InlineBitSet<WORDS> {
+    /// Elements are less than `CAPACITY`.
+    pub const CAPACITY: usize = WORDS * WORD_BITS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Set of the bits of `words`, as [`BitSet::from_words`].
+    pub const fn from_words(words: [Word; WORDS]) -> Self {
+        Self { words }
+    }
+
+    pub fn words(&self) -> &[Word; WORDS] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        len(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    #[inline]
+    pub fn contains(&self, value: usize) -> bool {
+        contains(&self.words, value)
+    }
+
+    /// Add `value`, returns whether it was not in the set.
+    ///
+    /// # Panics
+    /// Panic if `value` is not less than [`InlineBitSet::CAPACITY`]
+    #[inline]
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "{value} out of the capacity {}",
+            Self::CAPACITY
+        );
+
+        let (index, bit) = position(value);
+        let word = &mut self.words[index];
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+
+    /// Remove `value`, returns whether it was in the set.
+    #[inline]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (index, bit) = position(value);
+        let Some(word) = self.words.get_mut(index) else {
+            return false;
+        };
+
+        let removed = *word & bit != 0;
+        *word &= !bit;
+        removed
+    }
+
+    /// Smallest element.
+    pub fn first(&self) -> Option<usize> {
+        first(&self.words)
+    }
+
+    /// Largest element.
+    pub fn last(&self) -> Option<usize> {
+        last(&self.words)
+    }
+
+    /// Elements in increasing order.
+    pub fn iter(&self) -> Iter<iter::Copied<slice::Iter<'_, Word>>> {
+        Iter::new(self.words.iter().copied())
+    }
+
+    /// Number of elements less than `value`.
+    pub fn rank(&self, value: usize) -> usize {
+        rank(&self.words, value)
+    }
+
+    /// The `n`-th smallest element, counting from 0.
+    pub fn select(&self, n: usize) -> Option<usize> {
+        select(&self.words, n)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        is_subset(&self.words, &other.words)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        is_disjoint(&self.words, &other.words)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_words(array::from_fn(|i| self.words[i] | other.words[i]))
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::from_words(array::from_fn(|i| self.words[i] & other.words[i]))
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self::from_words(array::from_fn(|i| self.words[i] & !other.words[i]))
+    }
+
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Self::from_words(array::from_fn(|i| self.words[i] ^ other.words[i]))
+    }
+}
+
+impl<const WORDS: usize> Default for InlineBitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Debug for InlineBitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for InlineBitSet<WORDS> {
+    /// # Panics
+    /// Panic if a value is not less than [`InlineBitSet::CAPACITY`]
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut set = Self::new();
+        set.extend(values);
+        set
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for InlineBitSet<WORDS> {
+    /// # Panics
+    /// Panic if a value is not less than [`InlineBitSet::CAPACITY`]
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a InlineBitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Iter<iter::Copied<slice::Iter<'a, Word>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const WORDS: usize> IntoIterator for InlineBitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Iter<array::IntoIter<Word, WORDS>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.words.into_iter())
+    }
+}
+
+macro_rules! impl_inline_bit_set_ops {
+    ($($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $method:ident;)*) => {
+        $(
+            impl<const WORDS: usize> $op for InlineBitSet<WORDS> {
+                type Output = Self;
+
+                fn $op_fn(self, other: Self) -> Self {
+                    self.$method(&other)
+                }
+            }
+
+            impl<const WORDS: usize> $assign for InlineBitSet<WORDS> {
+                fn $assign_fn(&mut self, other: Self) {
+                    *self = self.$method(&other);
+                }
+            }
+        )*
+    };
+}
+
+impl_inline_bit_set_ops! {
+    BitOr, bitor, BitOrAssign, bitor_assign, union;
+    BitAnd, bitand, BitAndAssign, bitand_assign, intersection;
+    BitXor, bitxor, BitXorAssign, bitxor_assign, symmetric_difference;
+    Sub, sub, SubAssign, sub_assign, difference;
+}
+
+/// Elements of a set in increasing order, from its words `W`.
+#[derive(Debug, Clone)]
+pub struct Iter<W> {
+    words: W,
+    // element of the lowest bit of `word`
+    base: usize,
+    word: Word,
+}
+
+impl<W: Iterator<Item = Word>> Iter<W> {
+    fn new(mut words: W) -> Self {
+        let word = words.next().unwrap_or(0);
+        Self {
+            words,
+            base: 0,
+            word,
+        }
+    }
+}
+
+impl<W: Iterator<Item = Word>> Iterator for Iter<W> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.word = self.words.next()?;
+            self.base += WORD_BITS;
+        }
+
+        let value = self.base + self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(value)
+    }
+}
+
+#[inline]
+fn position(value: usize) -> (usize, Word) {
+    (value / WORD_BITS, 1 << (value % WORD_BITS))
+}
+
+fn len(words: &[Word]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+#[inline]
+fn contains(words: &[Word], value: usize) -> bool {
+    let (index, bit) = position(value);
+    words.get(index).is_some_and(|word| word & bit != 0)
+}
+
+fn first(words: &[Word]) -> Option<usize> {
+    let index = words.iter().position(|&word| word != 0)?;
+    Some(index * WORD_BITS + words[index].trailing_zeros() as usize)
+}
+
+fn last(words: &[Word]) -> Option<usize> {
+    let index = words.iter().rposition(|&word| word != 0)?;
+    Some((index + 1) * WORD_BITS - 1 - words[index].leading_zeros() as usize)
+}
+
+fn rank(words: &[Word], value: usize) -> usize {
+    let (index, bit) = position(value);
+    let below = words
+        .get(index)
+        .map_or(0, |word| (word & (bit - 1)).count_ones());
+    len(&words[..index.min(words.len())]) + below as usize
+}
+
+fn select(words: &[Word], mut n: usize) -> Option<usize> {
+    for (index, &word) in words.iter().enumerate() {
+        let ones = word.count_ones() as usize;
+        if n < ones {
+            let mut word = word;
+            for _ in 0..n {
+                word &= word - 1;
+            }
+            return Some(index * WORD_BITS + word.trailing_zeros() as usize);
+        }
+        n -= ones;
+    }
+
+    None
+}
+
+fn is_subset(words: &[Word], other: &[Word]) -> bool {
+    let other = other.iter().copied().chain(iter::repeat(0));
+    words
+        .iter()
+        .zip(other)
+        .all(|(word, other)| word & !other == 0)
+}
+
+fn is_disjoint(words: &[Word], other: &[Word]) -> bool {
+    words
+        .iter()
+        .zip(other)
+        .all(|(word, other)| word & other == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    /// Pseudo random numbers, to check the sets against [`HashSet`] on
+    /// many generated cases.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, bound: usize) -> usize {
+            // xorshift64
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            usize::try_from(self.0 % bound as u64).unwrap()
+        }
+
+        fn values(&mut self, bound: usize) -> Vec<usize> {
+            let len = self.next(bound);
+            (0..len).map(|_| self.next(bound)).collect()
+        }
+    }
+
+    const CASES: u64 = 500;
+
+    fn sorted(values: &HashSet<usize>) -> Vec<usize> {
+        let mut values = values.iter().copied().collect::<Vec<_>>();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn iter_next() {
+        let mut set = BitSet::new();
+        set.insert(WORD_BITS + 1);
+
+        let mut i = set.iter();
+        assert_eq!(i.next(), Some(WORD_BITS + 1));
+        assert_eq!(i.next(), None);
+
+        let mut i = set.into_iter();
+        assert_eq!(i.next(), Some(WORD_BITS + 1));
+        assert_eq!(i.next(), None);
+
+        let set = (0..200).collect::<BitSet>();
+        assert!(set.iter().eq(0..200));
+    }
+
+    #[test]
+    fn same_as_hash_set() {
+        let mut random = Random(0x2023_1225);
+        for _ in 0..CASES {
+            let values = random.values(300);
+            let set = values.iter().copied().collect::<BitSet>();
+            let expected = values.iter().copied().collect::<HashSet<_>>();
+
+            assert_eq!(set.len(), expected.len());
+            assert_eq!(set.iter().collect::<Vec<_>>(), sorted(&expected));
+            assert_eq!(set.first(), expected.iter().min().copied());
+            assert_eq!(set.last(), expected.iter().max().copied());
+            for value in 0..310 {
+                assert_eq!(set.contains(value), expected.contains(&value));
+            }
+
+            let mut removed = set.clone();
+            let mut expected_removed = expected.clone();
+            for value in random.values(300) {
+                assert_eq!(removed.remove(value), expected_removed.remove(&value));
+                assert_eq!(removed.insert(value), expected_removed.insert(value));
+                assert_eq!(removed.remove(value), expected_removed.remove(&value));
+            }
+            assert_eq!(
+                removed.iter().collect::<Vec<_>>(),
+                sorted(&expected_removed)
+            );
+            assert_eq!(removed, expected_removed.into_iter().collect());
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut random = Random(0x2023_1201);
+        for _ in 0..CASES {
+            let (a, b) = (random.values(200), random.values(200));
+            let (set_a, set_b) = (
+                a.iter().copied().collect::<BitSet>(),
+                b.iter().copied().collect::<BitSet>(),
+            );
+            let (hash_a, hash_b) = (
+                a.into_iter().collect::<HashSet<_>>(),
+                b.into_iter().collect::<HashSet<_>>(),
+            );
+
+            let check = |set: BitSet, expected: HashSet<usize>| {
+                assert_eq!(set.iter().collect::<Vec<_>>(), sorted(&expected));
+                // equality does not depend on how the set was built
+                assert_eq!(set, expected.into_iter().collect());
+            };
+            check(&set_a | &set_b, &hash_a | &hash_b);
+            check(&set_a & &set_b, &hash_a & &hash_b);
+            check(&set_a ^ &set_b, &hash_a ^ &hash_b);
+            check(&set_a - &set_b, &hash_a - &hash_b);
+
+            assert_eq!(set_a.is_subset(&set_b), hash_a.is_subset(&hash_b));
+            assert_eq!(set_a.is_superset(&set_b), hash_a.is_superset(&hash_b));
+            assert_eq!(set_a.is_disjoint(&set_b), hash_a.is_disjoint(&hash_b));
+            assert!((&set_a & &set_b).is_subset(&set_a));
+            assert!(set_a.is_subset(&(&set_a | &set_b)));
+        }
+    }
+
+    #[test]
+    fn rank_select() {
+        let mut random = Random(0x2023_1231);
+        for _ in 0..CASES {
+            let values = random.values(300);
+            let set = values.iter().copied().collect::<BitSet>();
+            let expected = sorted(&values.into_iter().collect());
+
+            for (n, &value) in expected.iter().enumerate() {
+                assert_eq!(set.select(n), Some(value));
+                assert_eq!(set.rank(value), n);
+            }
+            assert_eq!(set.select(expected.len()), None);
+            for value in 0..310 {
+                let below = expected.iter().filter(|&&other| other < value).count();
+                assert_eq!(set.rank(value), below);
+            }
+        }
+    }
+
+    #[test]
+    fn words() {
+        let set = [0, 64, 129].into_iter().collect::<BitSet>();
+        assert_eq!(set.words(), [1, 1, 2]);
+        assert_eq!(BitSet::from_words(vec![5, 0, 0]).words(), [5]);
+        assert_eq!(BitSet::from_words(vec![0, 0]), BitSet::new());
+        assert_eq!(BitSet::with_capacity(200), BitSet::new());
+        assert!(BitSet::from_words(vec![0]).is_empty());
+    }
+
+    #[test]
+    fn inline_same_as_bit_set() {
+        let mut random = Random(0x2023_1224);
+        for _ in 0..CASES {
+            let (a, b) = (random.values(192), random.values(192));
+            let (set_a, set_b) = (
+                a.iter().copied().collect::<BitSet>(),
+                b.iter().copied().collect::<BitSet>(),
+            );
+            let (inline_a, inline_b) = (
+                a.into_iter().collect::<InlineBitSet<3>>(),
+                b.into_iter().collect::<InlineBitSet<3>>(),
+            );
+
+            let check = |inline: InlineBitSet<3>, set: BitSet| {
+                assert!(inline.iter().eq(set.iter()));
+                assert_eq!(inline.len(), set.len());
+                assert_eq!(inline.is_empty(), set.is_empty());
+                assert_eq!((inline.first(), inline.last()), (set.first(), set.last()));
+                assert_eq!(BitSet::from_words(inline.words().to_vec()), set);
+            };
+            check(inline_a, set_a.clone());
+            check(inline_a | inline_b, &set_a | &set_b);
+            check(inline_a & inline_b, &set_a & &set_b);
+            check(inline_a ^ inline_b, &set_a ^ &set_b);
+            check(inline_a - inline_b, &set_a - &set_b);
+
+            assert_eq!(inline_a.is_subset(&inline_b), set_a.is_subset(&set_b));
+            assert_eq!(inline_a.is_disjoint(&inline_b), set_a.is_disjoint(&set_b));
+            for n in 0..=set_a.len() {
+                assert_eq!(inline_a.select(n), set_a.select(n));
+            }
+            for value in 0..200 {
+                assert_eq!(inline_a.rank(value), set_a.rank(value));
+                assert_eq!(inline_a.contains(value), set_a.contains(value));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "64 out of the capacity 64")]
+    fn inline_capacity() {
+        let mut set = InlineBitSet::<1>::new();
+        assert!(set.insert(63));
+        assert!(!set.remove(64));
+        set.insert(64);
+    }
+}
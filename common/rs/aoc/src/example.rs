@@ -0,0 +1,124 @@
+//! Examples of the puzzles with their expected answers.
+//!
+//! Every day lists in [`Solution::EXAMPLES`](crate::Solution::EXAMPLES)
+//! the examples its parts solve as they are, embedded with
+//! [`example!`](crate::example), so that the web UIs can check them
+//! without reading any file:
+//!
+//! ```
+//! use aoc::Example;
+//! use aoc::{Part, Timed};
+//!
+//! let example = Example {
+//!     name: "example1",
+//!     input: "1\n2",
+//!     part: Part::One,
+//!     answer: "3",
+//! };
+//! let timed = Timed {
+//!     answer: "3".to_string(),
+//!     parse: Default::default(),
+//!     solve: Default::default(),
+//! };
+//! assert!(example.outcome(Ok(timed)).passed());
+//! ```
+
+use std::time::Duration;
+
+use crate::{Part, Puzzle, Timed};
+
+/// Example input of a part with its expected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example, as the `example2` file it comes from.
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part: Part,
+    pub answer: &'static str,
+}
+
+/// Outcome of solving an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Solved with the expected answer.
+    Passed(Timed),
+    /// Solved with another answer.
+    Failed(Timed),
+    /// Rejected as invalid input, with the error.
+    Invalid(String),
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        matches!(self, Outcome::Passed(_))
+    }
+}
+
+impl Example {
+    /// Outcome of the example given what solving it returned.
+    pub fn outcome(&self, solved: Result<Timed, String>) -> Outcome {
+        match solved {
+            Ok(timed) if timed.answer == self.answer => Outcome::Passed(timed),
+            Ok(timed) => Outcome::Failed(timed),
+            Err(err) => Outcome::Invalid(err),
+        }
+    }
+
+    /// Solve the example with `puzzle`, timing it with `lap` as
+    /// [`Puzzle::solve_timed`] does.
+    pub fn check(&self, puzzle: &dyn Puzzle, lap: &mut dyn FnMut() -> Duration) -> Outcome {
+        self.outcome(
+            puzzle
+                .solve_timed(self.part, self.input, lap)
+                .map_err(|err| err.to_string()),
+        )
+    }
+}
+
+/// Example of the calling day crate with its expected answer.
+///
+/// Expand to an [`Example`] embedding the `name` file next to the crate,
+/// as `aoc::example!("example2", Part::One, 6)`.
+#[macro_export]
+macro_rules! example {
+    ($name:literal, $part:expr, $answer:literal) => {
+        $crate::Example {
+            name: $name,
+            input: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../", $name)),
+            part: $part,
+            answer: stringify!($answer),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timed(answer: &str) -> Timed {
+        Timed {
+            answer: answer.to_string(),
+            parse: Duration::from_micros(1),
+            solve: Duration::from_micros(2),
+        }
+    }
+
+    #[test]
+    fn outcome() {
+        let example = Example {
+            name: "example1",
+            input: "a\nb",
+            part: Part::Two,
+            answer: "ab",
+        };
+        assert_eq!(
+            example.outcome(Ok(timed("ab"))),
+            Outcome::Passed(timed("ab"))
+        );
+        assert_eq!(
+            example.outcome(Ok(timed("ba"))),
+            Outcome::Failed(timed("ba"))
+        );
+        assert!(!example.outcome(Err("invalid".to_string())).passed());
+    }
+}
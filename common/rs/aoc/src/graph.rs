@@ -0,0 +1,511 @@
+//! Search algorithms over graphs given by their neighbours.
+//!
+//! A [`Graph`] yields the neighbours of a node with the cost of the
+//! edge to reach them; any `Fn(&N) -> impl IntoIterator<Item = (N, C)>`
+//! is a graph, so a day only has to describe its moves:
+//!
+//! ```
+//! use aoc::graph::dijkstra;
+//!
+//! let graph = |&node: &u32| [(node + 1, 1), (node * 2, 3)];
+//! let (cost, path) = dijkstra(&graph, 1, |&node| node == 8).unwrap();
+//! assert_eq!((cost, path), (6, vec![1, 2, 3, 4, 8]));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::mem;
+use std::ops::Add;
+
+use crate::bitset::BitSet;
+
+/// Directed graph with nodes of type `N`.
+pub trait Graph<N> {
+    type Cost;
+
+    /// Nodes reachable from `node` by one edge, with the cost of the edge.
+    fn neighbors(&self, node: &N) -> impl IntoIterator<Item = (N, Self::Cost)>;
+}
+
+impl<N, C, I, F> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    type Cost = C;
+
+    fn neighbors(&self, node: &N) -> impl IntoIterator<Item = (N, C)> {
+        self(node)
+    }
+}
+
+/// Shortest paths, in number of edges, from a start node to every
+/// reachable node, see [`bfs`].
+#[derive(Debug, Clone)]
+pub struct Paths<N> {
+    parents: HashMap<N, (Option<N>, usize)>,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    /// Number of edges from the start to `node`, `None` if unreachable.
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.parents.get(node).map(|&(_, distance)| distance)
+    }
+
+    /// Nodes from the start to `node`, both included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.parents.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some((Some(parent), _)) = self.parents.get(&path[path.len() - 1]) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every reachable node with its distance, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, usize)> {
+        self.parents
+            .iter()
+            .map(|(node, &(_, distance))| (node, distance))
+    }
+
+    /// Number of reachable nodes, the start included.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+}
+
+/// Breadth first search of every node reachable from `start`, edge
+/// costs are ignored.
+pub fn bfs<N, G>(graph: &G, start: N) -> Paths<N>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let _span = crate::span!("bfs");
+    let mut parents = HashMap::new();
+    parents.insert(start.clone(), (None, 0));
+
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for (neighbor, _) in graph.neighbors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(neighbor) {
+                queue.push_back((entry.key().clone(), distance + 1));
+                entry.insert((Some(node.clone()), distance + 1));
+            }
+        }
+    }
+
+    Paths { parents }
+}
+
+/// Distances, in number of edges, from `start` to every node numbered
+/// below `len` by `index`, `None` if unreachable.
+///
+/// The breadth first search of [`bfs`], keeping the distances in a
+/// vector rather than a map, much faster on graphs where most nodes are
+/// reachable, as the tiles of a map.
+pub fn bfs_distances<N, G>(
+    graph: &G,
+    start: N,
+    len: usize,
+    index: impl Fn(&N) -> usize,
+) -> Vec<Option<usize>>
+where
+    G: Graph<N>,
+{
+    let _span = crate::span!("bfs");
+    let mut distances = vec![None; len];
+    distances[index(&start)] = Some(0);
+
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for (neighbor, _) in graph.neighbors(&node) {
+            let known = &mut distances[index(&neighbor)];
+            if known.is_none() {
+                *known = Some(distance + 1);
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Cheapest path from `start` to the first node accepted by `is_goal`,
+/// with its cost.
+pub fn dijkstra<N, G>(
+    graph: &G,
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    G::Cost: Copy + Ord + Add<Output = G::Cost> + Default,
+{
+    astar(graph, start, |_| G::Cost::default(), is_goal)
+}
+
+/// Cheapest path from `start` to the first node accepted by `is_goal`,
+/// with its cost, exploring first the nodes closer to a goal.
+///
+/// `heuristic` must never overestimate the cost to reach a goal, or
+/// the path found may not be the cheapest.
+pub fn astar<N, G>(
+    graph: &G,
+    start: N,
+    mut heuristic: impl FnMut(&N) -> G::Cost,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    G::Cost: Copy + Ord + Add<Output = G::Cost> + Default,
+{
+    let _span = crate::span!("astar");
+    let zero = G::Cost::default();
+
+    // every node found, with the index of its parent and its best cost
+    let mut nodes = vec![(start.clone(), usize::MAX, zero)];
+    let mut indices = HashMap::from([(start, 0)]);
+
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0].0), zero, 0))]);
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > nodes[index].2 {
+            continue;
+        }
+
+        let node = nodes[index].0.clone();
+        if is_goal(&node) {
+            let mut path = vec![node];
+            let mut parent = nodes[index].1;
+            while parent != usize::MAX {
+                path.push(nodes[parent].0.clone());
+                parent = nodes[parent].1;
+            }
+            path.reverse();
+
+            crate::event!(nodes = nodes.len(), "goal reached");
+            return Some((cost, path));
+        }
+
+        for (neighbor, weight) in graph.neighbors(&node) {
+            let neighbor_cost = cost + weight;
+            let neighbor_index = match indices.get(&neighbor) {
+                Some(&i) if nodes[i].2 <= neighbor_cost => continue,
+                Some(&i) => {
+                    nodes[i].1 = index;
+                    nodes[i].2 = neighbor_cost;
+                    i
+                }
+                None => {
+                    indices.insert(neighbor.clone(), nodes.len());
+                    nodes.push((neighbor, index, neighbor_cost));
+                    nodes.len() - 1
+                }
+            };
+            let estimate = neighbor_cost + heuristic(&nodes[neighbor_index].0);
+            queue.push(Reverse((estimate, neighbor_cost, neighbor_index)));
+        }
+    }
+
+    crate::event!(nodes = nodes.len(), "goal not reachable");
+    None
+}
+
+/// Most expensive path from `start` to a node accepted by `is_goal`
+/// never visiting a node twice, with its cost.
+///
+/// Paths end at the first goal they reach. The search tries every
+/// path, so it is only viable on small graphs.
+pub fn longest_path<N, G>(
+    graph: &G,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    G::Cost: Copy + Ord + Add<Output = G::Cost> + Default,
+{
+    let _span = crate::span!("longest_path");
+    let zero = G::Cost::default();
+    if is_goal(&start) {
+        return Some((zero, vec![start]));
+    }
+
+    // number the reachable nodes to search with plain vectors
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start, 0)]);
+    let mut edges = vec![];
+    while edges.len() < nodes.len() {
+        let node = nodes[edges.len()].clone();
+        let node_edges = graph
+            .neighbors(&node)
+            .into_iter()
+            .map(|(neighbor, weight)| {
+                let index = *indices.entry(neighbor.clone()).or_insert_with(|| {
+                    nodes.push(neighbor);
+                    nodes.len() - 1
+                });
+                (index, weight)
+            })
+            .collect::<Vec<_>>();
+        edges.push(node_edges);
+    }
+    let goals = nodes.iter().map(&mut is_goal).collect::<Vec<_>>();
+    crate::event!(
+        nodes = nodes.len(),
+        edges = edges.iter().map(Vec::len).sum::<usize>(),
+        "graph numbered"
+    );
+
+    let mut longest: Option<(G::Cost, Vec<usize>)> = None;
+
+    // depth first, every entry a node, its next edge and its cost
+    let mut visited = vec![false; nodes.len()];
+    visited[0] = true;
+    let mut stack = vec![(0, 0, zero)];
+    let _span = crate::span!("depth_first_search");
+    while let Some((node, next, cost)) = stack.last_mut() {
+        let (node, cost) = (*node, *cost);
+        let Some(&(neighbor, weight)) = edges[node].get(*next) else {
+            visited[node] = false;
+            stack.pop();
+            continue;
+        };
+        *next += 1;
+
+        if visited[neighbor] {
+            continue;
+        }
+
+        let cost = cost + weight;
+        if goals[neighbor] {
+            if longest
+                .as_ref()
+                .map_or(true, |&(longest, _)| longest < cost)
+            {
+                let path = stack.iter().map(|&(node, _, _)| node);
+                longest = Some((cost, path.chain([neighbor]).collect()));
+            }
+        } else {
+            visited[neighbor] = true;
+            stack.push((neighbor, 0, cost));
+        }
+    }
+
+    longest.map(|(cost, path)| {
+        let path = path.into_iter().map(|index| nodes[index].clone()).collect();
+        (cost, path)
+    })
+}
+
+/// Minimum cut of the undirected graph of `nodes`, with the nodes on
+/// one side of the cut, using the Stoer-Wagner algorithm.
+///
+/// Every edge must be yielded by both its nodes with the same cost,
+/// edges to nodes not in `nodes` are ignored.
+/// Returns `None` if there are less than 2 nodes.
+pub fn min_cut<N, G>(graph: &G, nodes: impl IntoIterator<Item = N>) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    G::Cost: Copy + Ord + Add<Output = G::Cost> + Default,
+{
+    let _span = crate::span!("min_cut");
+    let zero = G::Cost::default();
+
+    let nodes = nodes.into_iter().collect::<Vec<_>>();
+    let indices = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.clone(), index))
+        .collect::<HashMap<_, _>>();
+
+    // adjacency lists of the nodes merged so far, by index
+    let mut edges = nodes
+        .iter()
+        .map(|node| {
+            let mut node_edges: Vec<(usize, G::Cost)> = vec![];
+            for (neighbor, weight) in graph.neighbors(node) {
+                if let Some(&neighbor) = indices.get(&neighbor) {
+                    add_weight(&mut node_edges, neighbor, weight);
+                }
+            }
+            node_edges
+        })
+        .collect::<Vec<_>>();
+    let mut merged = (0..nodes.len())
+        .map(|index| BitSet::from_iter([index]))
+        .collect::<Vec<_>>();
+    let mut active = (0..nodes.len()).collect::<BitSet>();
+
+    let mut min_cut: Option<(G::Cost, BitSet)> = None;
+    while let Some(first) = active.first().filter(|_| active.len() > 1) {
+        let _span = crate::span!("phase");
+        crate::progress::report("min cut", nodes.len() - active.len(), nodes.len() - 1);
+
+        // add the most tightly connected node, until all are added
+        let mut weights = vec![zero; nodes.len()];
+        let mut added = BitSet::with_capacity(nodes.len());
+        let mut queue = BinaryHeap::from([(zero, first)]);
+        let (mut s, mut t, mut cut) = (usize::MAX, usize::MAX, zero);
+        let mut count = 0;
+        while let Some((weight, node)) = queue.pop() {
+            if added.contains(node) || weight < weights[node] {
+                continue;
+            }
+
+            added.insert(node);
+            count += 1;
+            (s, t, cut) = (t, node, weight);
+
+            for &(neighbor, weight) in &edges[node] {
+                if !added.contains(neighbor) {
+                    weights[neighbor] = weights[neighbor] + weight;
+                    queue.push((weights[neighbor], neighbor));
+                }
+            }
+        }
+
+        if count < active.len() {
+            // not connected
+            let side = added.iter().flat_map(|node| merged[node].iter());
+            min_cut = Some((zero, side.collect()));
+            break;
+        }
+
+        if min_cut.as_ref().map_or(true, |&(min_cut, _)| cut < min_cut) {
+            min_cut = Some((cut, merged[t].clone()));
+        }
+
+        // merge t into s
+        let t_merged = mem::take(&mut merged[t]);
+        merged[s].union_with(&t_merged);
+        for (neighbor, weight) in mem::take(&mut edges[t]) {
+            let neighbor_edges = &mut edges[neighbor];
+            neighbor_edges.retain(|&(node, _)| node != t);
+            if neighbor != s {
+                add_weight(neighbor_edges, s, weight);
+                add_weight(&mut edges[s], neighbor, weight);
+            }
+        }
+        active.remove(t);
+    }
+    crate::event!(
+        nodes = nodes.len(),
+        merged = nodes.len() - active.len(),
+        "graph merged"
+    );
+
+    min_cut.map(|(cut, side)| {
+        let side = side.into_iter().map(|index| nodes[index].clone()).collect();
+        (cut, side)
+    })
+}
+
+fn add_weight<C: Copy + Add<Output = C>>(edges: &mut Vec<(usize, C)>, node: usize, weight: C) {
+    if let Some((_, total)) = edges.iter_mut().find(|(n, _)| *n == node) {
+        *total = *total + weight;
+    } else {
+        edges.push((node, weight));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2
+    // |   |   |
+    // 3 - 4 - 5, with the edges from 1 costing 5
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn ladder(&node: &usize) -> Vec<(usize, u32)> {
+        let mut neighbors = vec![];
+        if node % 3 > 0 {
+            neighbors.push(node - 1);
+        }
+        if node % 3 < 2 {
+            neighbors.push(node + 1);
+        }
+        neighbors.push((node + 3) % 6);
+        neighbors
+            .into_iter()
+            .map(|neighbor| (neighbor, if node == 1 || neighbor == 1 { 5 } else { 1 }))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let paths = bfs(&ladder, 0);
+        assert_eq!(paths.len(), 6);
+        assert_eq!(paths.distance(&5), Some(3));
+        assert_eq!(paths.distance(&6), None);
+        assert_eq!(paths.path(&2), Some(vec![0, 1, 2]));
+        assert_eq!(paths.iter().map(|(_, distance)| distance).max(), Some(3));
+
+        let distances = bfs_distances(&ladder, 0, 7, |&node| node);
+        assert_eq!(distances.len(), 7);
+        for (node, distance) in distances.iter().enumerate() {
+            assert_eq!(*distance, paths.distance(&node));
+        }
+    }
+
+    #[test]
+    fn shortest_path() {
+        assert_eq!(
+            dijkstra(&ladder, 0, |&node| node == 2),
+            Some((4, vec![0, 3, 4, 5, 2]))
+        );
+        assert_eq!(
+            astar(&ladder, 0, |&node| u32::from(node != 2), |&node| node == 2),
+            Some((4, vec![0, 3, 4, 5, 2]))
+        );
+        assert_eq!(dijkstra(&ladder, 0, |&node| node == 6), None);
+    }
+
+    #[test]
+    fn longest() {
+        assert_eq!(
+            longest_path(&ladder, 0, |&node| node == 2),
+            Some((12, vec![0, 1, 4, 5, 2]))
+        );
+        assert_eq!(
+            longest_path(&ladder, 0, |&node| node == 0),
+            Some((0, vec![0]))
+        );
+    }
+
+    #[test]
+    fn stoer_wagner() {
+        // two triangles joined by the edge 2 - 3
+        let graph = |&node: &u8| match node {
+            0 => vec![(1, 2), (2, 2)],
+            1 => vec![(0, 2), (2, 2)],
+            2 => vec![(0, 2), (1, 2), (3, 1)],
+            3 => vec![(2, 1), (4, 2), (5, 2)],
+            4 => vec![(3, 2), (5, 2)],
+            _ => vec![(3, 2), (4, 2)],
+        };
+        let (cut, mut side) = min_cut(&graph, 0..6).unwrap();
+        side.sort_unstable();
+        assert_eq!(cut, 1);
+        assert!(side == [0, 1, 2] || side == [3, 4, 5], "{side:?}");
+
+        let (cut, side) = min_cut(&|_: &u8| Vec::<(u8, u32)>::new(), 0..2).unwrap();
+        assert_eq!((cut, side.len()), (0, 1));
+
+        assert_eq!(min_cut(&graph, 0..1), None);
+    }
+}
@@ -0,0 +1,369 @@
+//! Rectangular maps of tiles.
+//!
+//! A [`Grid`] views a map of the puzzle input in place, rows still
+//! separated by their newlines, or owns its tiles after a
+//! [`Grid::transpose`] or a rotation. Positions are `(x, y)`, `x` the
+//! column and `y` the row.
+
+use std::fmt::{self, Display};
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::Parser;
+use crate::{ErrorKind, ParseError};
+
+/// Steps to the 4 neighbours: up, right, down, left.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Steps to the 8 neighbours, clockwise from up.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Map of `ncols` × `nrows` tiles, row `y` starting at `y * stride`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    tiles: T,
+    ncols: usize,
+    nrows: usize,
+    stride: usize,
+}
+
+impl<'a> Grid<&'a [u8]> {
+    /// Grid of the map `text`, a slice of the input of `parser`,
+    /// with only tiles accepted by `is_tile`, ignoring the trailing
+    /// blank lines.
+    ///
+    /// # Errors
+    /// Return the first invalid row, as [`Parser::rows`], or the first
+    /// row not ending as the first one, with `\n` or `\r\n`
+    pub fn parse(
+        parser: Parser,
+        text: &'a str,
+        is_tile: impl Fn(u8) -> bool,
+    ) -> Result<Self, ParseError> {
+        let offset = |row: &[u8]| row.as_ptr() as usize - text.as_ptr() as usize;
+
+        let mut rows = text.trim_end().lines().map(str::as_bytes);
+        let Some(first) = rows.next() else {
+            return Err(parser.error_end(ErrorKind::UnexpectedEnd));
+        };
+        let (ncols, start) = (first.len(), offset(first));
+        let (mut nrows, mut stride) = (0, ncols + 1);
+
+        for row in iter::once(first).chain(rows) {
+            if row.len() != ncols {
+                return Err(parser.error_bytes(row, ErrorKind::InvalidRowLength));
+            }
+            if let Some(i) = row.iter().position(|&tile| !is_tile(tile)) {
+                return Err(parser.error_bytes(&row[i..=i], ErrorKind::InvalidTile));
+            }
+            // the rows must be evenly spaced to be indexed by the stride
+            if nrows == 1 {
+                stride = offset(row) - start;
+            } else if nrows > 1 && offset(row) != start + nrows * stride {
+                return Err(parser.error_bytes(
+                    row,
+                    ErrorKind::Invalid("line ending, not the same on every row"),
+                ));
+            }
+            nrows += 1;
+        }
+
+        let end = start + (nrows - 1) * stride + ncols;
+
+        Ok(Self {
+            tiles: &text.as_bytes()[start..end],
+            ncols,
+            nrows,
+            stride,
+        })
+    }
+}
+
+impl Grid<Vec<u8>> {
+    /// Grid owning `tiles`, one row after the other.
+    ///
+    /// # Panics
+    /// Panic if there are not `ncols * nrows` tiles
+    pub fn new(tiles: Vec<u8>, ncols: usize, nrows: usize) -> Self {
+        assert_eq!(tiles.len(), ncols * nrows, "invalid grid size");
+
+        Self {
+            tiles,
+            ncols,
+            nrows,
+            stride: ncols,
+        }
+    }
+
+    fn from_fn(ncols: usize, nrows: usize, f: impl Fn(usize, usize) -> u8) -> Self {
+        Self::new(
+            (0..nrows)
+                .flat_map(|y| (0..ncols).map(move |x| (x, y)))
+                .map(|(x, y)| f(x, y))
+                .collect(),
+            ncols,
+            nrows,
+        )
+    }
+}
+
+impl<T: AsRef<[u8]>> Grid<T> {
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Distance between the starts of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Tiles with the row separators, if any.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.tiles.as_ref()
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.ncols && y < self.nrows
+    }
+
+    /// Tile at `position`, `None` outside the grid.
+    pub fn get(&self, position: (usize, usize)) -> Option<u8> {
+        if self.contains(position) {
+            Some(self.tiles.as_ref()[self.offset(position)])
+        } else {
+            None
+        }
+    }
+
+    /// Position one `step` from `position`, `None` outside the grid.
+    #[inline]
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        // wrapping below 0 lands far outside the grid
+        let position = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        if self.contains(position) {
+            Some(position)
+        } else {
+            None
+        }
+    }
+
+    /// Up, right, down and left neighbours of `position` inside the grid.
+    pub fn neighbors_4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_4
+            .iter()
+            .filter_map(move |&delta| self.step(position, delta))
+    }
+
+    /// Neighbours of `position` inside the grid, diagonals included.
+    pub fn neighbors_8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_8
+            .iter()
+            .filter_map(move |&delta| self.step(position, delta))
+    }
+
+    /// Tiles of row `y`.
+    ///
+    /// # Panics
+    /// Panic if `y` is outside the grid
+    pub fn row(&self, y: usize) -> &[u8] {
+        assert!(y < self.nrows, "row {y} outside the grid");
+
+        let start = y * self.stride;
+        &self.tiles.as_ref()[start..start + self.ncols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        (0..self.nrows).map(|y| self.row(y))
+    }
+
+    /// Tiles of column `x`, top to bottom.
+    ///
+    /// # Panics
+    /// Panic if `x` is outside the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item = u8> + '_ {
+        assert!(x < self.ncols, "column {x} outside the grid");
+
+        self.tiles.as_ref()[x..]
+            .iter()
+            .step_by(self.stride)
+            .take(self.nrows)
+            .copied()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = u8> + '_> + '_ {
+        (0..self.ncols).map(|x| self.column(x))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let ncols = self.ncols;
+        (0..self.nrows).flat_map(move |y| (0..ncols).map(move |x| (x, y)))
+    }
+
+    /// First position of `tile`, row by row.
+    pub fn find(&self, tile: u8) -> Option<(usize, usize)> {
+        self.positions().find(|&position| self[position] == tile)
+    }
+
+    /// Grid with rows and columns swapped.
+    pub fn transpose(&self) -> Grid<Vec<u8>> {
+        Grid::from_fn(self.nrows, self.ncols, |x, y| self[(y, x)])
+    }
+
+    /// Grid rotated by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<Vec<u8>> {
+        Grid::from_fn(self.nrows, self.ncols, |x, y| self[(y, self.nrows - 1 - x)])
+    }
+
+    /// Grid rotated by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Grid<Vec<u8>> {
+        Grid::from_fn(self.nrows, self.ncols, |x, y| self[(self.ncols - 1 - y, x)])
+    }
+
+    /// Grid owning a copy of the tiles.
+    pub fn to_vec(&self) -> Grid<Vec<u8>> {
+        Grid {
+            tiles: self.tiles.as_ref().to_vec(),
+            ncols: self.ncols,
+            nrows: self.nrows,
+            stride: self.stride,
+        }
+    }
+
+    #[inline]
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        x + y * self.stride
+    }
+}
+
+impl<T: AsRef<[u8]>> Index<(usize, usize)> for Grid<T> {
+    type Output = u8;
+
+    #[inline]
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        debug_assert!(self.contains(position), "{position:?} outside the grid");
+
+        &self.tiles.as_ref()[self.offset(position)]
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        debug_assert!(self.contains(position), "{position:?} outside the grid");
+
+        let index = self.offset(position);
+        &mut self.tiles.as_mut()[index]
+    }
+}
+
+impl<T: AsRef<[u8]>> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.S#\n";
+
+    fn grid(map: &str) -> Grid<&[u8]> {
+        Grid::parse(Parser::new(map), map, |tile| b"#.S".contains(&tile)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = grid(MAP);
+        assert_eq!((grid.ncols(), grid.nrows(), grid.stride()), (3, 2, 4));
+        assert_eq!(grid.as_bytes(), b"#..\n.S#");
+        assert_eq!(grid.get((2, 1)), Some(b'#'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.find(b'S'), Some((1, 1)));
+
+        let grid = self::grid("#..\r\n.S#\r\n");
+        assert_eq!((grid.stride(), grid[(2, 1)]), (5, b'#'));
+
+        let grid = self::grid("#..\n.S#\n\n\n");
+        assert_eq!((grid.nrows(), grid.as_bytes()), (2, &b"#..\n.S#"[..]));
+
+        let input = "#..\r\n.S#\n#.#\r\n...";
+        let error =
+            Grid::parse(Parser::new(input), input, |tile| b"#.S".contains(&tile)).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("line ending, not the same on every row")
+        );
+
+        let input = "#..\n.x#";
+        let error =
+            Grid::parse(Parser::new(input), input, |tile| b"#.S".contains(&tile)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.kind, ErrorKind::InvalidTile);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid(MAP);
+        assert_eq!(
+            grid.neighbors_4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors_8((1, 0)).count(), 5);
+        assert_eq!(grid.step((0, 1), (-1, 0)), None);
+        assert_eq!(grid.step((0, 1), (2, -1)), Some((2, 0)));
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid(MAP);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"#..", b".S#"]);
+        assert_eq!(grid.column(2).collect::<Vec<_>>(), b".#");
+        assert_eq!(grid.columns().count(), 3);
+
+        let transposed = grid.transpose();
+        assert_eq!(transposed.to_string(), "#.\n.S\n.#\n");
+        assert_eq!(transposed.transpose().to_string(), MAP);
+    }
+
+    #[test]
+    fn rotate() {
+        let grid = grid(MAP);
+        assert_eq!(grid.rotate_clockwise().to_string(), ".#\nS.\n#.\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), ".#\n.S\n#.\n");
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_counterclockwise()
+                .to_string(),
+            MAP
+        );
+
+        let mut owned = grid.rotate_clockwise().rotate_clockwise();
+        owned[(0, 0)] = b'O';
+        assert_eq!(owned.to_string(), "OS.\n..#\n");
+    }
+}
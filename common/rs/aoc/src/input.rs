@@ -0,0 +1,255 @@
+//! Puzzle input loading.
+//!
+//! The input of a day is read at runtime, from the file named by the
+//! [`ENV`] environment variable or else from the `input` file of the
+//! day. A path of `-` reads the standard input.
+//!
+//! Day crates built with their `embedded-input` feature embed the
+//! `input` file in the binary instead, as needed by the web UIs.
+//!
+//! Inputs too big to be kept in memory are read a line at a time with
+//! [`for_each_line`].
+
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+use crate::ParseError;
+
+/// Environment variable with the path of the input file.
+pub const ENV: &str = "AOC_INPUT";
+
+/// Read the input file at `path`, or the standard input if `path` is `-`.
+///
+/// # Errors
+/// Return an error if the input cannot be read
+pub fn read(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Read the input file named by [`ENV`], or `default` if not set.
+///
+/// # Errors
+/// Return an error if the input cannot be read
+pub fn load(default: impl AsRef<Path>) -> io::Result<String> {
+    match env::var_os(ENV) {
+        Some(path) => read(path),
+        None => read(default),
+    }
+}
+
+/// Like [`load`], keeping the input for the rest of the program.
+///
+/// # Panics
+/// Panic if the input cannot be read
+pub fn load_static(default: impl AsRef<Path>) -> &'static str {
+    let default = default.as_ref();
+    match load(default) {
+        Ok(input) => input.leak(),
+        Err(err) => match env::var_os(ENV) {
+            Some(path) => panic!("cannot read input {}: {err}", path.to_string_lossy()),
+            None => panic!("cannot read input {}: {err}", default.display()),
+        },
+    }
+}
+
+/// Stable hash of an input, the 64 bits FNV-1a of its bytes.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Size of an input, as shown by the web UIs before solving.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub lines: usize,
+    /// Characters of the longest line.
+    pub columns: usize,
+    pub bytes: usize,
+}
+
+impl Stats {
+    pub fn of(input: &str) -> Self {
+        Self {
+            lines: input.lines().count(),
+            columns: input
+                .lines()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0),
+            bytes: input.len(),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} lines, {} columns, {} bytes",
+            self.lines, self.columns, self.bytes
+        )
+    }
+}
+
+/// Error reading an input a line at a time.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "cannot read input: {err}"),
+            ReadError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(err) => Some(err),
+            ReadError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        ReadError::Parse(err)
+    }
+}
+
+/// Call `f` on every line of `reader`, without its line terminator.
+///
+/// Only a line at a time is kept in memory. `f` parses the line as a
+/// whole input, the line of its errors is moved to the line in `reader`.
+///
+/// # Errors
+/// Return the first error reading `reader` or returned by `f`
+pub fn for_each_line<R: BufRead>(
+    mut reader: R,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<(), ReadError> {
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+
+        let text = line.strip_suffix('\n').unwrap_or(&line);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        f(text).map_err(|mut err| {
+            err.line += number - 1;
+            err
+        })?;
+    }
+}
+
+/// Input of the calling day crate.
+///
+/// Expand to the `input` file next to the crate, embedded with the
+/// `embedded-input` feature and otherwise loaded with
+/// [`load_static`].
+#[macro_export]
+macro_rules! input {
+    () => {{
+        #[cfg(feature = "embedded-input")]
+        let input: &'static str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../input"));
+        #[cfg(not(feature = "embedded-input"))]
+        let input: &'static str =
+            $crate::input::load_static(concat!(env!("CARGO_MANIFEST_DIR"), "/../input"));
+        input
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_file() {
+        let input = read(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+
+        assert!(input.contains(r#"name = "aoc""#));
+    }
+
+    #[test]
+    fn read_missing_file() {
+        assert!(read(concat!(env!("CARGO_MANIFEST_DIR"), "/missing")).is_err());
+    }
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash("foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(
+            Stats::of("#.#\n..\r\n#.é.\n"),
+            Stats {
+                lines: 3,
+                columns: 4,
+                bytes: 14,
+            }
+        );
+        assert_eq!(Stats::of(""), Stats::default());
+        assert_eq!(
+            Stats::of("1\n22").to_string(),
+            "2 lines, 2 columns, 4 bytes"
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let mut lines = vec![];
+        for_each_line("a\nb\r\n\nc".as_bytes(), |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(lines, ["a", "b", "", "c"]);
+    }
+
+    #[test]
+    fn lines_error() {
+        let error = for_each_line("1\n2\nx\n4\n".as_bytes(), |line| {
+            crate::parse::Parser::new(line)
+                .number::<u32>(line)
+                .map(|_| ())
+        })
+        .unwrap_err();
+
+        let ReadError::Parse(error) = error else {
+            panic!("unexpected {error}");
+        };
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, "x"));
+    }
+}
@@ -0,0 +1,491 @@
+//! Intervals, their products in `N` dimensions and sets of them.
+//!
+//! An [`IntervalSet`] keeps disjoint [`Cuboid`]s, so that its volume is
+//! the sum of their volumes:
+//!
+//! ```
+//! use aoc::interval::{Cuboid, Interval, IntervalSet};
+//!
+//! let square = |x: u64, y: u64, side| Cuboid::new([
+//!     Interval::new(x, x + side),
+//!     Interval::new(y, y + side),
+//! ]);
+//!
+//! let mut set = IntervalSet::from(square(0, 0, 4));
+//! set.insert(square(2, 2, 4));
+//! assert_eq!(set.volume(), 28);
+//! set.remove(square(1, 1, 2));
+//! assert_eq!(set.volume(), 24);
+//! ```
+
+use std::mem;
+use std::ops::{Add, Mul, Range, Sub};
+
+/// Values from `start` included to `end` excluded, empty if `end` is not
+/// after `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether the intervals have a value in common.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// Values in both intervals, `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        if intersection.is_empty() {
+            None
+        } else {
+            Some(intersection)
+        }
+    }
+
+    /// Values not in `other`, as the non-empty intervals below and above it.
+    pub fn difference(&self, other: &Self) -> impl Iterator<Item = Self> {
+        let below = Self::new(self.start, self.end.min(other.start));
+        let above = Self::new(self.start.max(other.end), self.end);
+        [below, above]
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+    }
+
+    /// The values less than `value` and the others.
+    pub fn split_at(&self, value: T) -> (Self, Self) {
+        let value = value.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, value), Self::new(value, self.end))
+    }
+
+    /// The smallest interval containing both, if there are no values
+    /// between them.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        if self.start.max(other.start) <= self.end.min(other.end) {
+            Some(Self::new(
+                self.start.min(other.start),
+                self.end.max(other.end),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + From<u8>> Interval<T> {
+    /// Number of values.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::from(0)
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(Range { start, end }: Range<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(Interval { start, end }: Interval<T>) -> Self {
+        start..end
+    }
+}
+
+/// Product of `N` intervals, one for every dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cuboid<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: Copy + Ord, const N: usize> Cuboid<T, N> {
+    pub fn new(intervals: [Interval<T>; N]) -> Self {
+        Self(intervals)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0
+            .iter()
+            .zip(point)
+            .all(|(interval, &value)| interval.contains(value))
+    }
+
+    /// Whether the cuboids have a point in common.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a.intersects(b))
+    }
+
+    /// Points in both cuboids, `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut intersection = *self;
+        for (a, b) in intersection.0.iter_mut().zip(&other.0) {
+            *a = a.intersection(b)?;
+        }
+        Some(intersection)
+    }
+
+    /// Points not in `other`, as at most `2 * N` disjoint non-empty
+    /// cuboids.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(intersection) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        // cut away the slices outside `other` one dimension at a time
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for dimension in 0..N {
+            for interval in rest.0[dimension].difference(&intersection.0[dimension]) {
+                let mut piece = rest;
+                piece.0[dimension] = interval;
+                pieces.push(piece);
+            }
+            rest.0[dimension] = intersection.0[dimension];
+        }
+
+        pieces
+    }
+
+    /// The union of the cuboids, if it is a cuboid because they only
+    /// differ along one dimension.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        let mut different = (0..N).filter(|&dimension| self.0[dimension] != other.0[dimension]);
+        let Some(dimension) = different.next() else {
+            return Some(*self);
+        };
+        if different.next().is_some() {
+            return None;
+        }
+
+        let mut merged = *self;
+        merged.0[dimension] = self.0[dimension].merge(&other.0[dimension])?;
+        Some(merged)
+    }
+}
+
+impl<T, const N: usize> Cuboid<T, N>
+where
+    T: Copy + Ord + Sub<Output = T> + Mul<Output = T> + From<u8>,
+{
+    /// Number of points, the product of the lengths of the intervals.
+    pub fn volume(&self) -> T {
+        self.0
+            .iter()
+            .fold(T::from(1), |volume, interval| volume * interval.len())
+    }
+}
+
+impl<T> From<Interval<T>> for Cuboid<T, 1> {
+    fn from(interval: Interval<T>) -> Self {
+        Self([interval])
+    }
+}
+
+/// Set of points in `N` dimensions, as disjoint cuboids.
+#[derive(Debug, Clone)]
+pub struct IntervalSet<T, const N: usize = 1> {
+    // disjoint and not empty
+    cuboids: Vec<Cuboid<T, N>>,
+}
+
+impl<T: Copy + Ord, const N: usize> IntervalSet<T, N> {
+    pub fn new() -> Self {
+        Self { cuboids: vec![] }
+    }
+
+    /// The disjoint cuboids of the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Cuboid<T, N>> {
+        self.cuboids.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(point))
+    }
+
+    /// Add the points of `cuboid`.
+    pub fn insert(&mut self, cuboid: Cuboid<T, N>) {
+        let mut pieces = if cuboid.is_empty() {
+            vec![]
+        } else {
+            vec![cuboid]
+        };
+        for current in &self.cuboids {
+            if pieces.iter().any(|piece| piece.intersects(current)) {
+                pieces = pieces
+                    .iter()
+                    .flat_map(|piece| piece.difference(current))
+                    .collect();
+            }
+        }
+
+        self.cuboids.append(&mut pieces);
+    }
+
+    /// Remove the points of `cuboid`.
+    pub fn remove(&mut self, cuboid: Cuboid<T, N>) {
+        if self
+            .cuboids
+            .iter()
+            .any(|current| current.intersects(&cuboid))
+        {
+            self.cuboids = self
+                .cuboids
+                .iter()
+                .flat_map(|current| current.difference(&cuboid))
+                .collect();
+        }
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.cuboids.iter().copied());
+        union
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|a| other.cuboids.iter().filter_map(move |b| a.intersection(b)));
+        Self {
+            cuboids: cuboids.collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for &cuboid in &other.cuboids {
+            difference.remove(cuboid);
+        }
+        difference
+    }
+
+    /// Merge the cuboids whose union is a cuboid, until there are none,
+    /// then sort them.
+    pub fn coalesce(&mut self) {
+        self.cuboids.sort_unstable();
+
+        let mut merged = true;
+        while merged {
+            merged = false;
+
+            for cuboid in mem::take(&mut self.cuboids) {
+                let union = self
+                    .cuboids
+                    .iter_mut()
+                    .find_map(|other| Some((other.merge(&cuboid)?, other)));
+                if let Some((union, other)) = union {
+                    *other = union;
+                    merged = true;
+                } else {
+                    self.cuboids.push(cuboid);
+                }
+            }
+        }
+
+        // merges replace a cuboid with a larger one, out of order
+        self.cuboids.sort_unstable();
+    }
+}
+
+impl<T, const N: usize> IntervalSet<T, N>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<u8>,
+{
+    /// Number of points.
+    pub fn volume(&self) -> T {
+        self.cuboids
+            .iter()
+            .fold(T::from(0), |volume, cuboid| volume + cuboid.volume())
+    }
+}
+
+impl<T: Copy + Ord, const N: usize> Default for IntervalSet<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord, const N: usize> From<Cuboid<T, N>> for IntervalSet<T, N> {
+    fn from(cuboid: Cuboid<T, N>) -> Self {
+        let mut set = Self::new();
+        set.insert(cuboid);
+        set
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from(Cuboid::from(interval))
+    }
+}
+
+impl<T: Copy + Ord, const N: usize> FromIterator<Cuboid<T, N>> for IntervalSet<T, N> {
+    fn from_iter<I: IntoIterator<Item = Cuboid<T, N>>>(cuboids: I) -> Self {
+        let mut set = Self::new();
+        set.extend(cuboids);
+        set
+    }
+}
+
+impl<T: Copy + Ord, const N: usize> Extend<Cuboid<T, N>> for IntervalSet<T, N> {
+    fn extend<I: IntoIterator<Item = Cuboid<T, N>>>(&mut self, cuboids: I) {
+        for cuboid in cuboids {
+            self.insert(cuboid);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(x: Range<i32>, y: Range<i32>) -> Cuboid<i32, 2> {
+        Cuboid::new([x.into(), y.into()])
+    }
+
+    fn points(set: &IntervalSet<i32, 2>) -> Vec<[i32; 2]> {
+        (-1..12)
+            .flat_map(|x| (-1..12).map(move |y| [x, y]))
+            .filter(|point| set.contains(point))
+            .collect()
+    }
+
+    #[test]
+    fn intervals() {
+        let interval = Interval::from(2..6);
+        assert_eq!(interval.len(), 4);
+        assert_eq!(Interval::new(6, 2).len(), 0);
+        assert_eq!(interval.intersection(&(4..9).into()), Some((4..6).into()));
+        assert_eq!(interval.intersection(&(6..9).into()), None);
+        assert_eq!(
+            interval.difference(&(3..4).into()).collect::<Vec<_>>(),
+            [(2..3).into(), (4..6).into()]
+        );
+        assert_eq!(interval.difference(&(0..9).into()).count(), 0);
+        assert_eq!(interval.split_at(4), ((2..4).into(), (4..6).into()));
+        assert_eq!(interval.split_at(9), ((2..6).into(), (6..6).into()));
+        assert_eq!(interval.split_at(0), ((2..2).into(), (2..6).into()));
+    }
+
+    #[test]
+    fn every_interval_difference() {
+        let intervals =
+            (0..5).flat_map(|start| (start..6).map(move |end| Interval::new(start, end)));
+        for a in intervals.clone() {
+            for b in intervals.clone() {
+                let difference = a.difference(&b).collect::<Vec<_>>();
+                for value in -1..7 {
+                    let expected = a.contains(value) && !b.contains(value);
+                    assert_eq!(difference.iter().any(|i| i.contains(value)), expected);
+                }
+                assert_eq!(
+                    a.intersection(&b).map_or(0, |i| i.len()),
+                    a.len() - difference.iter().map(Interval::len).sum::<i32>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cuboid_difference() {
+        let cuboid = Cuboid::new([(1..4).into(); 4]);
+        let hole = Cuboid::new([(2..3).into(); 4]);
+
+        let pieces = cuboid.difference(&hole);
+        assert_eq!(pieces.len(), 8);
+        assert_eq!(
+            pieces.iter().map(Cuboid::volume).sum::<i32>(),
+            3 * 3 * 3 * 3 - 1
+        );
+        assert!(pieces.iter().all(|piece| !piece.intersects(&hole)));
+        assert_eq!(cuboid.difference(&cuboid), []);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_iter([rectangle(0..6, 0..4), rectangle(2..4, 2..10)]);
+        let b = IntervalSet::from_iter([rectangle(3..11, 3..5), rectangle(8..9, 0..11)]);
+        assert_eq!(a.volume(), 24 + 12);
+        assert_eq!(b.volume(), 16 + 9);
+
+        let expected = |f: fn(bool, bool) -> bool| {
+            (-1..12)
+                .flat_map(|x| (-1..12).map(move |y| [x, y]))
+                .filter(|point| f(a.contains(point), b.contains(point)))
+                .collect::<Vec<_>>()
+        };
+
+        let union = a.union(&b);
+        assert_eq!(points(&union), expected(|a, b| a || b));
+        assert_eq!(union.volume(), i32::try_from(points(&union).len()).unwrap());
+
+        let intersection = a.intersection(&b);
+        assert_eq!(points(&intersection), expected(|a, b| a && b));
+        assert_eq!(intersection.volume(), 4);
+
+        let difference = a.difference(&b);
+        assert_eq!(points(&difference), expected(|a, b| a && !b));
+        assert_eq!(difference.volume(), a.volume() - 4);
+    }
+
+    #[test]
+    fn coalesce() {
+        let mut set = IntervalSet::from_iter([
+            Cuboid::from(Interval::from(5..7)),
+            Cuboid::from(Interval::from(0..2)),
+            Cuboid::from(Interval::from(2..4)),
+            Cuboid::from(Interval::from(7..8)),
+        ]);
+        set.coalesce();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [0..4, 5..8].map(|range| Cuboid::from(Interval::from(range)))
+        );
+
+        let mut set = IntervalSet::from(rectangle(0..4, 0..4));
+        set.remove(rectangle(1..2, 1..2));
+        set.insert(rectangle(1..2, 1..2));
+        set.coalesce();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [&rectangle(0..4, 0..4)]);
+
+        // the merged rectangle sorts after the one left alone
+        let mut set = IntervalSet::from_iter([
+            rectangle(1..3, 4..7),
+            rectangle(1..4, 1..4),
+            rectangle(3..5, 4..7),
+        ]);
+        set.coalesce();
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [&rectangle(1..4, 1..4), &rectangle(1..5, 4..7)]
+        );
+    }
+}
@@ -0,0 +1,555 @@
+//! Common definitions shared by every day of the Advent of Code 2023.
+//!
+//! Each `dayNN` crate implements [`Solution`] on a unit struct, so
+//! that tools can drive any day through the same interface, either
+//! statically with the trait or dynamically with [`Puzzle`].
+
+#![deny(clippy::pedantic)]
+#![allow(clippy::must_use_candidate)]
+
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(feature = "batch")]
+pub mod batch;
+pub mod bitset;
+pub mod example;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod memory;
+pub mod overlay;
+pub mod parse;
+pub mod progress;
+pub mod report;
+pub mod trace;
+
+pub use example::Example;
+pub use overlay::Overlay;
+pub use parse::{ErrorKind, ParseError};
+
+/// Puzzle part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = &'static str;
+
+    fn try_from(part: u8) -> Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err("invalid part, must be 1 or 2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(part: &str) -> Result<Self, Self::Err> {
+        part.parse::<u8>()
+            .map_err(|_| "invalid part, must be 1 or 2")
+            .and_then(Part::try_from)
+    }
+}
+
+/// Solution of one day.
+///
+/// `parse` turns the raw puzzle input into the model shared by both
+/// parts, then `part_1` and `part_2` compute the answers from it.
+///
+/// The input is parsed with its trailing whitespace trimmed, so that
+/// blank lines at its end are not taken for an empty row or record.
+/// The days whose parts cannot solve every input that parses, as an
+/// unreachable end, check it in `try_part_1` and `try_part_2`: every
+/// `try_` method then returns an error instead of panicking.
+pub trait Solution {
+    /// Day of the puzzle, from 1 to 25.
+    const DAY: u8;
+
+    /// Title of the puzzle.
+    const TITLE: &'static str;
+
+    /// Enabled cargo features of the day choosing other solvers.
+    const FEATURES: &'static [&'static str] = &[];
+
+    /// Examples of the puzzle solved as they are, with their answers.
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Parsed input.
+    type Input<'a>;
+
+    /// Answer of part 1.
+    type Output1: Display + Debug;
+
+    /// Answer of part 2.
+    type Output2: Display + Debug;
+
+    /// Parse the puzzle input.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    /// Parse the puzzle input.
+    ///
+    /// # Panics
+    /// Panic if invalid input
+    fn parse(input: &str) -> Self::Input<'_> {
+        Self::try_parse(input.trim_end()).unwrap_or_else(|err| panic!("invalid input: {err}"))
+    }
+
+    /// Solve part 1 on the parsed input.
+    ///
+    /// # Panics
+    /// Panic if invalid input
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1;
+
+    /// Solve part 2 on the parsed input.
+    ///
+    /// # Panics
+    /// Panic if invalid input
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2;
+
+    /// Solve part 1 on the parsed `input`, checking it can be solved.
+    /// `text` is the input parsed, to locate the errors.
+    ///
+    /// # Errors
+    /// Return why the input cannot be solved, never by default
+    fn try_part_1(input: &Self::Input<'_>, text: &str) -> Result<Self::Output1, ParseError> {
+        let _ = text;
+        Ok(Self::part_1(input))
+    }
+
+    /// Solve part 2 on the parsed `input`, checking it can be solved.
+    /// `text` is the input parsed, to locate the errors.
+    ///
+    /// # Errors
+    /// Return why the input cannot be solved, never by default
+    fn try_part_2(input: &Self::Input<'_>, text: &str) -> Result<Self::Output2, ParseError> {
+        let _ = text;
+        Ok(Self::part_2(input))
+    }
+
+    /// Solve `part` on the parsed `input` with the drawing of its answer
+    /// over the map, for the days solving on a map, both from the same
+    /// solve. `text` is the input parsed, to locate the errors.
+    ///
+    /// # Errors
+    /// Return why the input cannot be solved, as `try_part_1` and
+    /// `try_part_2`
+    fn try_part_overlay(
+        part: Part,
+        input: &Self::Input<'_>,
+        text: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let answer = match part {
+            Part::One => Self::try_part_1(input, text)?.to_string(),
+            Part::Two => Self::try_part_2(input, text)?.to_string(),
+        };
+        Ok((answer, None))
+    }
+
+    /// Parse and solve part 1.
+    ///
+    /// # Panics
+    /// Panic if invalid input
+    fn solve_1(input: &str) -> Self::Output1 {
+        traced::<Self, _>(Part::One, input, Self::try_part_1)
+            .unwrap_or_else(|err| panic!("invalid input: {err}"))
+    }
+
+    /// Parse and solve part 2.
+    ///
+    /// # Panics
+    /// Panic if invalid input
+    fn solve_2(input: &str) -> Self::Output2 {
+        traced::<Self, _>(Part::Two, input, Self::try_part_2)
+            .unwrap_or_else(|err| panic!("invalid input: {err}"))
+    }
+
+    /// Parse and solve part 1, without panicking on invalid input.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn try_solve_1(input: &str) -> Result<Self::Output1, ParseError> {
+        traced::<Self, _>(Part::One, input, Self::try_part_1)
+    }
+
+    /// Parse and solve part 2, without panicking on invalid input.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn try_solve_2(input: &str) -> Result<Self::Output2, ParseError> {
+        traced::<Self, _>(Part::Two, input, Self::try_part_2)
+    }
+
+    /// Parse and solve `part`, timing both with `lap`, that returns the
+    /// time elapsed since it was last called.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn try_solve_timed(
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<Timed, ParseError> {
+        let (timed, ()) = timed::<Self, _>(part, input, lap, |input, text| {
+            let answer = match part {
+                Part::One => Self::try_part_1(input, text)?.to_string(),
+                Part::Two => Self::try_part_2(input, text)?.to_string(),
+            };
+            Ok((answer, ()))
+        })?;
+        Ok(timed)
+    }
+
+    /// Parse and solve `part` with [`Solution::try_part_overlay`], timing
+    /// both as [`Solution::try_solve_timed`] does, the drawing included in
+    /// the solve.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn try_solve_overlay(
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError> {
+        timed::<Self, _>(part, input, lap, |input, text| {
+            Self::try_part_overlay(part, input, text)
+        })
+    }
+}
+
+/// Parse `input` trimmed and solve `part` of `S` with `solve`, timing
+/// both with `lap` in the spans of [`traced`].
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn timed<S: Solution + ?Sized, T>(
+    part: Part,
+    input: &str,
+    lap: &mut dyn FnMut() -> Duration,
+    solve: impl FnOnce(&S::Input<'_>, &str) -> Result<(String, T), ParseError>,
+) -> Result<(Timed, T), ParseError> {
+    let _span = span!("puzzle", day = S::DAY, part = part.number());
+    lap();
+    let text = input.trim_end();
+    let input = {
+        let _span = span!("parse");
+        S::try_parse(text)?
+    };
+    let parse = lap();
+    let _span = span!("solve");
+    let (answer, extra) = solve(&input, text)?;
+    let solve = lap();
+
+    Ok((
+        Timed {
+            answer,
+            parse,
+            solve,
+        },
+        extra,
+    ))
+}
+
+/// Parse `input` trimmed and solve `part` of `S` with `solve`, in the
+/// `parse` and `solve` spans of a `puzzle` span.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn traced<S: Solution + ?Sized, T>(
+    part: Part,
+    input: &str,
+    solve: impl FnOnce(&S::Input<'_>, &str) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let _span = span!("puzzle", day = S::DAY, part = part.number());
+    let text = input.trim_end();
+    let input = {
+        let _span = span!("parse");
+        S::try_parse(text)?
+    };
+    let _span = span!("solve");
+    solve(&input, text)
+}
+
+/// Answer with the time spent parsing and solving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timed {
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Object safe view of a [`Solution`].
+///
+/// Implemented for every [`Solution`], so that days with different
+/// output types can be kept in the same collection.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn features(&self) -> &'static [&'static str];
+
+    fn examples(&self) -> &'static [Example];
+
+    /// Parse and solve `part`, returning the answer as text.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn solve(&self, part: Part, input: &str) -> Result<String, ParseError>;
+
+    /// Like [`Puzzle::solve`], timing parsing and solving with `lap` as
+    /// [`Solution::try_solve_timed`] does.
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn solve_timed(
+        &self,
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<Timed, ParseError>;
+
+    /// Like [`Puzzle::solve_timed`], with the drawing of the answer over
+    /// the map from the same solve, as [`Solution::try_solve_overlay`].
+    ///
+    /// # Errors
+    /// Return the first error found in the input
+    fn solve_overlay(
+        &self,
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn features(&self) -> &'static [&'static str] {
+        S::FEATURES
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<String, ParseError> {
+        match part {
+            Part::One => S::try_solve_1(input).map(|answer| answer.to_string()),
+            Part::Two => S::try_solve_2(input).map(|answer| answer.to_string()),
+        }
+    }
+
+    fn solve_timed(
+        &self,
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<Timed, ParseError> {
+        S::try_solve_timed(part, input, lap)
+    }
+
+    fn solve_overlay(
+        &self,
+        part: Part,
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError> {
+        S::try_solve_overlay(part, input, lap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Lines";
+        const EXAMPLES: &'static [Example] = &[Example {
+            name: "example1",
+            input: "a\nb",
+            part: Part::Two,
+            answer: "ab",
+        }];
+
+        type Input<'a> = Vec<&'a str>;
+        type Output1 = usize;
+        type Output2 = String;
+
+        fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            let lines = input.lines().collect::<Vec<_>>();
+            if let Some(line) = lines.iter().find(|line| line.is_empty()) {
+                return Err(parse::Parser::new(input).error(line, ErrorKind::Expected("a line")));
+            }
+            Ok(lines)
+        }
+
+        fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+            input.len()
+        }
+
+        fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
+            input.concat()
+        }
+
+        fn try_part_2(input: &Self::Input<'_>, text: &str) -> Result<Self::Output2, ParseError> {
+            if input.len() > 3 {
+                return Err(
+                    parse::Parser::new(text).error_end(ErrorKind::Invalid("lines, more than 3"))
+                );
+            }
+            Ok(Self::part_2(input))
+        }
+    }
+
+    #[test]
+    fn solve_with_trait() {
+        assert_eq!(Lines::solve_1("a\nb\nc"), 3);
+        assert_eq!(Lines::solve_2("a\nb\nc"), "abc");
+    }
+
+    #[test]
+    fn try_solve_with_trait() {
+        assert_eq!(Lines::try_solve_1("a\nb"), Ok(2));
+
+        let error = Lines::try_solve_2("a\n\nb").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.kind, ErrorKind::Expected("a line"));
+    }
+
+    #[test]
+    fn try_solve_trailing_blank_lines() {
+        assert_eq!(Lines::try_solve_1("a\nb\n\n"), Ok(2));
+        assert_eq!(Lines::try_solve_2("a\nb \r\n\n").as_deref(), Ok("ab"));
+    }
+
+    #[test]
+    fn try_solve_unsolvable() {
+        let error = Lines::try_solve_2("a\nb\nc\nd").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 2));
+        assert_eq!(error.kind, ErrorKind::Invalid("lines, more than 3"));
+
+        let puzzle: &dyn Puzzle = &Lines;
+        assert!(puzzle
+            .solve_timed(Part::Two, "a\nb\nc\nd", &mut Duration::default)
+            .is_err());
+        assert_eq!(puzzle.solve(Part::One, "a\nb\nc\nd").unwrap(), "4");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid input: line 2, column 1: expected a line")]
+    fn solve_invalid_input() {
+        Lines::solve_1("a\n\nb");
+    }
+
+    #[test]
+    fn solve_with_puzzle() {
+        let puzzle: &dyn Puzzle = &Lines;
+
+        assert_eq!(puzzle.day(), 0);
+        assert_eq!(puzzle.title(), "Lines");
+        assert_eq!(puzzle.solve(Part::One, "a\nb").unwrap(), "2");
+        assert_eq!(puzzle.solve(Part::Two, "a\nb").unwrap(), "ab");
+        assert!(puzzle.solve(Part::One, "a\n\nb").is_err());
+        assert_eq!(puzzle.examples().len(), 1);
+    }
+
+    #[test]
+    fn solve_timed() {
+        let puzzle: &dyn Puzzle = &Lines;
+
+        let mut laps = 0;
+        let mut lap = || {
+            laps += 1;
+            Duration::from_millis(laps)
+        };
+        let timed = puzzle.solve_timed(Part::Two, "a\nb", &mut lap).unwrap();
+        assert_eq!(timed.answer, "ab");
+        assert_eq!(timed.parse, Duration::from_millis(2));
+        assert_eq!(timed.solve, Duration::from_millis(3));
+        assert_eq!(timed.elapsed(), Duration::from_millis(5));
+        assert!(puzzle.features().is_empty());
+    }
+
+    #[test]
+    fn solve_overlay() {
+        let puzzle: &dyn Puzzle = &Lines;
+
+        let mut laps = 0;
+        let mut lap = || {
+            laps += 1;
+            Duration::from_millis(laps)
+        };
+        let (timed, overlay) = puzzle.solve_overlay(Part::One, "a\nb", &mut lap).unwrap();
+        assert_eq!(timed.answer, "2");
+        assert_eq!(timed.elapsed(), Duration::from_millis(5));
+        assert_eq!(overlay, None);
+
+        let error = puzzle
+            .solve_overlay(Part::Two, "a\nb\nc\nd", &mut Duration::default)
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Invalid("lines, more than 3"));
+    }
+
+    #[test]
+    fn check_examples() {
+        let puzzle: &dyn Puzzle = &Lines;
+
+        for example in puzzle.examples() {
+            assert!(example.check(puzzle, &mut Duration::default).passed());
+        }
+    }
+
+    #[test]
+    fn part_try_from() {
+        assert_eq!(Part::try_from(1), Ok(Part::One));
+        assert_eq!(Part::try_from(2), Ok(Part::Two));
+        assert!(Part::try_from(3).is_err());
+    }
+
+    #[test]
+    fn part_from_str() {
+        assert_eq!("1".parse(), Ok(Part::One));
+        assert_eq!("2".parse(), Ok(Part::Two));
+        assert!("one".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Heap usage of the solvers, counted by the global allocator.
+//!
+//! With the `count-alloc` feature [`Counting`] is the global allocator
+//! of every program using this crate, and [`measure`] tells how many
+//! allocations a closure made, how many bytes they asked for and how
+//! much the heap grew at its peak:
+//!
+//! ```
+//! let (sum, usage) = aoc::memory::measure(|| (0..1000).collect::<Vec<u64>>().iter().sum::<u64>());
+//!
+//! assert_eq!(sum, 499_500);
+//! if let Some(usage) = usage {
+//!     assert!(usage.bytes >= 8000);
+//! }
+//! ```
+//!
+//! The counters are shared by every thread, the usage of a closure
+//! includes the allocations made meanwhile by the other threads, as the
+//! `rayon` workers of a solver.
+//!
+//! The day crates forward their `count-alloc` feature to this crate, so
+//! that the `aoc` runner and the benches report the usage of every part.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Global allocator with the `count-alloc` feature.
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting::new();
+
+/// Heap usage of a closure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Allocations and reallocations made.
+    pub allocations: usize,
+    /// Bytes asked for by the allocations and the reallocations.
+    pub bytes: usize,
+    /// Growth of the heap at its peak.
+    pub peak: usize,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} bytes, peak {} bytes",
+            self.allocations, self.bytes, self.peak
+        )
+    }
+}
+
+/// Allocator of the system, counting the allocations.
+#[derive(Debug, Default)]
+pub struct Counting {
+    allocations: AtomicUsize,
+    bytes: AtomicUsize,
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Counting {
+    pub const fn new() -> Self {
+        Self {
+            allocations: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Call `f`, returning its value and the heap usage of the call.
+    ///
+    /// The calls must not overlap, as the peak is restarted by each.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, Usage) {
+        let allocations = self.allocations.load(Ordering::Relaxed);
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        let current = self.current.load(Ordering::Relaxed);
+        self.peak.store(current, Ordering::Relaxed);
+
+        let value = f();
+
+        let usage = Usage {
+            allocations: self.allocations.load(Ordering::Relaxed) - allocations,
+            bytes: self.bytes.load(Ordering::Relaxed) - bytes,
+            peak: self.peak.load(Ordering::Relaxed).saturating_sub(current),
+        };
+        (value, usage)
+    }
+
+    fn grow(&self, size: usize, by: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        let current = self.current.fetch_add(by, Ordering::Relaxed) + by;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                self.grow(new_size, new_size - layout.size());
+            } else {
+                self.grow(new_size, 0);
+                self.current
+                    .fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Call `f`, returning its value and, with the `count-alloc` feature,
+/// the heap usage of the call.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    #[cfg(feature = "count-alloc")]
+    {
+        let (value, usage) = ALLOCATOR.measure(f);
+        (value, Some(usage))
+    }
+
+    #[cfg(not(feature = "count-alloc"))]
+    {
+        (f(), None)
+    }
+}
+
+/// Print the heap usage of a call of `f` as `name`, with the
+/// `count-alloc` feature, for the benches. Without it `f` is not called.
+///
+/// The usage includes the lazy statics first used by the call: the
+/// benches initialize the puzzle input before, so that it is not
+/// counted in part 1.
+#[cfg_attr(not(feature = "count-alloc"), allow(unused_variables))]
+pub fn report<T>(name: &str, f: impl FnOnce() -> T) {
+    #[cfg(feature = "count-alloc")]
+    {
+        let (_, usage) = ALLOCATOR.measure(f);
+        println!("{name}: {usage}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let counting = Counting::new();
+        let layout = Layout::array::<u64>(16).unwrap();
+
+        let ((), usage) = counting.measure(|| unsafe {
+            let ptr = counting.alloc(layout);
+            let other = counting.alloc_zeroed(layout);
+            counting.dealloc(other, layout);
+            let ptr = counting.realloc(ptr, layout, 256);
+            counting.dealloc(ptr, Layout::array::<u64>(32).unwrap());
+        });
+        assert_eq!(
+            usage,
+            Usage {
+                allocations: 3,
+                bytes: 128 + 128 + 256,
+                peak: 256,
+            }
+        );
+        assert_eq!(counting.current.load(Ordering::Relaxed), 0);
+
+        let ((), usage) = counting.measure(|| ());
+        assert_eq!(usage, Usage::default());
+    }
+
+    #[test]
+    fn display() {
+        let usage = Usage {
+            allocations: 3,
+            bytes: 512,
+            peak: 256,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "3 allocations, 512 bytes, peak 256 bytes"
+        );
+    }
+}
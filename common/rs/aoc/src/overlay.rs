@@ -0,0 +1,179 @@
+//! Drawings of the answers over the map of the input, for the web UIs.
+//!
+//! The days solving on a map return an [`Overlay`] from
+//! [`Solution::overlay`](crate::Solution::overlay): the tiles to fill,
+//! as the tiles energised by the beam of day 16, and the paths to draw
+//! through the centres of their tiles, as the path of the crucible of
+//! day 17. Positions are `(x, y)`, as in [`grid`](crate::grid).
+//!
+//! An overlay is sent as text by the web workers, a line for every layer:
+//!
+//! ```
+//! use aoc::overlay::{Color, Overlay};
+//!
+//! let overlay = Overlay::default()
+//!     .cells(Color::YELLOW, [(0, 0), (1, 0)])
+//!     .path(Color::RED, [(0, 0), (0, 2)]);
+//! assert_eq!(overlay.to_string(), "cells #ffd700 0,0 1,0\npath #ff0000 0,0 0,2\n");
+//! assert_eq!(overlay.to_string().parse(), Ok(overlay));
+//! ```
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Colour of a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const RED: Color = Color(0xff, 0x00, 0x00);
+    pub const GREEN: Color = Color(0x2e, 0x8b, 0x57);
+    pub const BLUE: Color = Color(0x1e, 0x90, 0xff);
+    pub const YELLOW: Color = Color(0xff, 0xd7, 0x00);
+    pub const ORANGE: Color = Color(0xff, 0x8c, 0x00);
+}
+
+/// CSS hex colour, as `#ff8c00`.
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Color {
+    type Err = &'static str;
+
+    fn from_str(color: &str) -> Result<Self, Self::Err> {
+        let invalid = "invalid color, must be #rrggbb";
+        let hex = color
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or(invalid)?;
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid);
+        Ok(Color(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Positions drawn in the same colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    pub color: Color,
+    pub positions: Vec<(usize, usize)>,
+}
+
+/// Tiles filled and paths drawn over the map of the input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlay {
+    /// Tiles filled, the later layers over the earlier ones.
+    pub cells: Vec<Layer>,
+    /// Paths through the centres of their tiles, drawn over the cells.
+    pub paths: Vec<Layer>,
+}
+
+impl Overlay {
+    /// Fill the tiles at `positions` with `color`.
+    #[must_use]
+    pub fn cells(
+        mut self,
+        color: Color,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        self.cells.push(Layer {
+            color,
+            positions: positions.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Draw a path through `positions` with `color`.
+    #[must_use]
+    pub fn path(
+        mut self,
+        color: Color,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        self.paths.push(Layer {
+            color,
+            positions: positions.into_iter().collect(),
+        });
+        self
+    }
+}
+
+impl Display for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let layers = self
+            .cells
+            .iter()
+            .map(|layer| ("cells", layer))
+            .chain(self.paths.iter().map(|layer| ("path", layer)));
+        for (kind, layer) in layers {
+            write!(f, "{kind} {}", layer.color)?;
+            for (x, y) in &layer.positions {
+                write!(f, " {x},{y}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Overlay {
+    type Err = &'static str;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = "invalid overlay, must be lines of cells or path, a color and x,y positions";
+        text.lines().try_fold(Overlay::default(), |overlay, line| {
+            let mut words = line.split_whitespace();
+            let (Some(kind), Some(color)) = (words.next(), words.next()) else {
+                return Err(invalid);
+            };
+            let color = color.parse()?;
+            let positions = words
+                .map(|position| {
+                    let (x, y) = position.split_once(',')?;
+                    Some((x.parse().ok()?, y.parse().ok()?))
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or(invalid)?;
+            match kind {
+                "cells" => Ok(overlay.cells(color, positions)),
+                "path" => Ok(overlay.path(color, positions)),
+                _ => Err(invalid),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color() {
+        assert_eq!(Color::ORANGE.to_string(), "#ff8c00");
+        assert_eq!("#1E90ff".parse(), Ok(Color::BLUE));
+        for color in ["", "1e90ff", "#1e90f", "#1e90fg", "#1e90fff", "#é1e90"] {
+            assert!(color.parse::<Color>().is_err(), "{color}");
+        }
+    }
+
+    #[test]
+    fn text() {
+        let overlay = Overlay::default()
+            .cells(Color::GREEN, [])
+            .cells(Color::BLUE, [(3, 4)])
+            .path(Color(1, 2, 3), [(10, 0), (10, 20), (0, 20)]);
+        assert_eq!(overlay.to_string().parse(), Ok(overlay));
+        assert_eq!("".parse(), Ok(Overlay::default()));
+        for text in [
+            "cells",
+            "cells red 1,2",
+            "line #ff0000",
+            "path #ff0000 1",
+            "path #ff0000 1,x",
+        ] {
+            assert!(text.parse::<Overlay>().is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Input parsing errors.
+//!
+//! Parsers report a [`ParseError`] locating the offending text in the
+//! puzzle input. [`Parser`] computes the location from the slices of
+//! the input the parsers already work with.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// What is wrong with the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input ends too early.
+    UnexpectedEnd,
+    /// The input is not the expected text.
+    Expected(&'static str),
+    /// Not a number, or a number out of range.
+    InvalidNumber,
+    /// Character not allowed in a map.
+    InvalidTile,
+    /// Map row of a different length than the first one.
+    InvalidRowLength,
+    /// The input lacks a mandatory element.
+    Missing(&'static str),
+    /// Reference to something never defined.
+    Undefined(&'static str),
+    /// The input is well formed but cannot be solved.
+    Invalid(&'static str),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ErrorKind::Expected(what) => write!(f, "expected {what}"),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ErrorKind::InvalidTile => write!(f, "invalid tile"),
+            ErrorKind::InvalidRowLength => write!(f, "invalid row length"),
+            ErrorKind::Missing(what) => write!(f, "missing {what}"),
+            ErrorKind::Undefined(what) => write!(f, "undefined {what}"),
+            ErrorKind::Invalid(what) => write!(f, "invalid {what}"),
+        }
+    }
+}
+
+/// Error in the puzzle input.
+///
+/// `line` and `column` start from 1, `column` counts characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        if !self.text.is_empty() {
+            write!(f, ": {:?}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Builder of [`ParseError`]s for one puzzle input.
+///
+/// Every `at` argument must be a slice of the input, that is where
+/// the error is located.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a>(&'a str);
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self(input)
+    }
+
+    /// Error of `kind` on the text `at`.
+    pub fn error(self, at: &str, kind: ErrorKind) -> ParseError {
+        self.error_at(self.offset(at.as_ptr()), at, kind)
+    }
+
+    /// Error of `kind` on the bytes `at`, made of whole characters.
+    pub fn error_bytes(self, at: &[u8], kind: ErrorKind) -> ParseError {
+        self.error_at(self.offset(at.as_ptr()), &String::from_utf8_lossy(at), kind)
+    }
+
+    /// Error of `kind` at the end of the input.
+    pub fn error_end(self, kind: ErrorKind) -> ParseError {
+        self.error_at(self.0.len(), "", kind)
+    }
+
+    /// Parse `text` as a number.
+    ///
+    /// # Errors
+    /// [`ErrorKind::InvalidNumber`] if `text` is not a number
+    pub fn number<T: FromStr>(self, text: &str) -> Result<T, ParseError> {
+        text.parse()
+            .map_err(|_| self.error(text, ErrorKind::InvalidNumber))
+    }
+
+    /// Split `text` around the first `separator`.
+    ///
+    /// # Errors
+    /// [`ErrorKind::Expected`] if `text` has no `separator`
+    pub fn split_once<'b>(
+        self,
+        text: &'b str,
+        separator: &'static str,
+    ) -> Result<(&'b str, &'b str), ParseError> {
+        text.split_once(separator)
+            .ok_or_else(|| self.error(text, ErrorKind::Expected(separator)))
+    }
+
+    /// Remove `prefix` from `text`.
+    ///
+    /// # Errors
+    /// [`ErrorKind::Expected`] if `text` does not start with `prefix`
+    pub fn strip_prefix<'b>(
+        self,
+        text: &'b str,
+        prefix: &'static str,
+    ) -> Result<&'b str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, ErrorKind::Expected(prefix)))
+    }
+
+    /// Rows of a map, checking they have the same length and only
+    /// tiles accepted by `is_tile`, ignoring the trailing blank lines.
+    ///
+    /// # Errors
+    /// [`ErrorKind::InvalidRowLength`] or [`ErrorKind::InvalidTile`]
+    /// on the first invalid row, [`ErrorKind::UnexpectedEnd`] if
+    /// there are no rows
+    pub fn rows(self, text: &str, is_tile: impl Fn(u8) -> bool) -> Result<Vec<&[u8]>, ParseError> {
+        let rows = text
+            .trim_end()
+            .lines()
+            .map(str::as_bytes)
+            .collect::<Vec<_>>();
+        let Some(first) = rows.first() else {
+            return Err(self.error_end(ErrorKind::UnexpectedEnd));
+        };
+
+        for row in &rows {
+            if row.len() != first.len() {
+                return Err(self.error_bytes(row, ErrorKind::InvalidRowLength));
+            }
+            if let Some(i) = row.iter().position(|&tile| !is_tile(tile)) {
+                return Err(self.error_bytes(&row[i..=i], ErrorKind::InvalidTile));
+            }
+        }
+
+        Ok(rows)
+    }
+
+    fn offset(self, at: *const u8) -> usize {
+        (at as usize)
+            .checked_sub(self.0.as_ptr() as usize)
+            .filter(|&offset| offset <= self.0.len())
+            .unwrap_or(self.0.len())
+    }
+
+    fn error_at(self, offset: usize, text: &str, kind: ErrorKind) -> ParseError {
+        let before = self.0.get(..offset).unwrap_or(self.0);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "a: 1\nb: x\ncc";
+
+    #[test]
+    fn error_location() {
+        let parser = Parser::new(INPUT);
+
+        let error = parser.error(&INPUT[8..9], ErrorKind::InvalidNumber);
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.text, "x");
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 4: invalid number: "x""#
+        );
+
+        let error = parser.error_end(ErrorKind::UnexpectedEnd);
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: unexpected end of input"
+        );
+    }
+
+    #[test]
+    fn parse_lines() {
+        let parser = Parser::new(INPUT);
+
+        let mut lines = INPUT.lines();
+        let (name, value) = parser.split_once(lines.next().unwrap(), ": ").unwrap();
+        assert_eq!((name, parser.number::<u32>(value)), ("a", Ok(1)));
+
+        let (_, value) = parser.split_once(lines.next().unwrap(), ": ").unwrap();
+        assert_eq!(
+            parser.number::<u32>(value).unwrap_err().kind,
+            ErrorKind::InvalidNumber
+        );
+
+        let error = parser.split_once(lines.next().unwrap(), ": ").unwrap_err();
+        assert_eq!((error.line, error.kind), (3, ErrorKind::Expected(": ")));
+    }
+
+    #[test]
+    fn parse_rows() {
+        let input = "#.#\n..#\n#.";
+        let error = Parser::new(input)
+            .rows(input, |tile| matches!(tile, b'#' | b'.'))
+            .unwrap_err();
+        assert_eq!((error.line, error.kind), (3, ErrorKind::InvalidRowLength));
+
+        let input = "#.#\n.O#";
+        let error = Parser::new(input)
+            .rows(input, |tile| matches!(tile, b'#' | b'.'))
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.kind, ErrorKind::InvalidTile);
+
+        let input = "#.#\n..#";
+        assert_eq!(
+            Parser::new(input)
+                .rows(input, |tile| matches!(tile, b'#' | b'.'))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let input = "#.#\n..#\n\n";
+        assert_eq!(
+            Parser::new(input)
+                .rows(input, |tile| matches!(tile, b'#' | b'.'))
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
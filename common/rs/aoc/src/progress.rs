@@ -0,0 +1,154 @@
+//! Progress of the long solves, for the web UIs to show.
+//!
+//! The solvers tell how far they are with [`report`], naming the phase
+//! and counting the work done out of the total. [`watch`] calls back
+//! with the phase and the percentage done while running a solve, only
+//! when they change:
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! let seen = Rc::new(RefCell::new(vec![]));
+//! let watcher = seen.clone();
+//! aoc::progress::watch(
+//!     move |progress| watcher.borrow_mut().push(progress.percent),
+//!     || {
+//!         for done in 0..8 {
+//!             aoc::progress::report("phase", done, 4);
+//!         }
+//!     },
+//! );
+//! assert_eq!(*seen.borrow(), [0, 25, 50, 75, 100]);
+//! ```
+//!
+//! Without a watcher [`report`] only reads a thread local. The reports
+//! are seen from the thread running the solve, not from the `rayon`
+//! workers of the solvers.
+
+use std::cell::RefCell;
+
+/// Phase of a solve and how much of it is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress<'a> {
+    pub phase: &'a str,
+    /// Percentage done, from 0 to 100.
+    pub percent: u8,
+}
+
+struct Watcher {
+    callback: Box<dyn Fn(Progress)>,
+    last: Option<(String, u8)>,
+}
+
+thread_local! {
+    static WATCHER: RefCell<Option<Watcher>> = const { RefCell::new(None) };
+}
+
+/// Watcher in place before [`watch`], put back even on panic.
+struct Restore(Option<Watcher>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        WATCHER.set(self.0.take());
+    }
+}
+
+/// Call `f`, calling `callback` with the progress reported meanwhile.
+///
+/// `callback` must not report progress itself.
+pub fn watch<T>(callback: impl Fn(Progress) + 'static, f: impl FnOnce() -> T) -> T {
+    let _restore = Restore(WATCHER.replace(Some(Watcher {
+        callback: Box::new(callback),
+        last: None,
+    })));
+    f()
+}
+
+/// Report that `done` out of `total` of `phase` is done.
+pub fn report(phase: &str, done: usize, total: usize) {
+    WATCHER.with_borrow_mut(|watcher| {
+        let Some(watcher) = watcher else {
+            return;
+        };
+
+        let percent = (done.min(total) * 100)
+            .checked_div(total)
+            .map_or(100, |percent| u8::try_from(percent).unwrap_or(100));
+        if watcher
+            .last
+            .as_ref()
+            .is_some_and(|(last, last_percent)| last == phase && *last_percent == percent)
+        {
+            return;
+        }
+
+        watcher.last = Some((phase.to_string(), percent));
+        (watcher.callback)(Progress { phase, percent });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    fn watched(f: impl FnOnce()) -> Vec<(String, u8)> {
+        let seen = Rc::new(RefCell::new(vec![]));
+        let watcher = seen.clone();
+        watch(
+            move |progress| {
+                watcher
+                    .borrow_mut()
+                    .push((progress.phase.to_string(), progress.percent));
+            },
+            f,
+        );
+        seen.take()
+    }
+
+    #[test]
+    fn phases() {
+        let seen = watched(|| {
+            for done in 0..=3 {
+                report("fall", done, 3);
+            }
+            report("chain", 1, 3);
+            report("chain", 1, 3);
+            report("empty", 0, 0);
+        });
+        assert_eq!(
+            seen,
+            [
+                ("fall".to_string(), 0),
+                ("fall".to_string(), 33),
+                ("fall".to_string(), 66),
+                ("fall".to_string(), 100),
+                ("chain".to_string(), 33),
+                ("empty".to_string(), 100),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let mut inner = vec![];
+        let outer = watched(|| {
+            report("outer", 0, 2);
+            inner = watched(|| report("inner", 1, 2));
+            report("outer", 2, 2);
+        });
+        assert_eq!(inner, [("inner".to_string(), 50)]);
+        assert_eq!(
+            outer,
+            [("outer".to_string(), 0), ("outer".to_string(), 100)]
+        );
+    }
+
+    #[test]
+    fn unwatched() {
+        report("alone", 1, 2);
+        assert!(WATCHER.with_borrow(Option::is_none));
+    }
+}
@@ -0,0 +1,257 @@
+//! Timing reports of runs, in JSON or CSV.
+//!
+//! A [`Report`] has a [`Record`] for every part solved, with what is
+//! needed to chart the performance across commits: the variant of the
+//! solvers, the hash of the input and the time spent parsing and solving.
+
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{input, Part, Puzzle, Timed};
+
+/// Part solved in a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    /// Enabled features of the day, joined by `+`.
+    pub variant: String,
+    pub input_hash: u64,
+    pub parse: Duration,
+    pub solve: Duration,
+    pub answer: String,
+}
+
+impl Record {
+    /// Record of `part` of `puzzle` solved on `input`.
+    pub fn new(puzzle: &dyn Puzzle, part: Part, input: &str, timed: Timed) -> Self {
+        Self {
+            day: puzzle.day(),
+            part,
+            variant: puzzle.features().join("+"),
+            input_hash: input::hash(input),
+            parse: timed.parse,
+            solve: timed.solve,
+            answer: timed.answer,
+        }
+    }
+}
+
+/// Records of a run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    /// JSON array with an object for every record, times in nanoseconds.
+    pub fn to_json(&self) -> String {
+        let objects = self
+            .records
+            .iter()
+            .map(|record| {
+                format!(
+                    "\n  {{\"day\": {}, \"part\": {}, \"variant\": {}, \"input_hash\": \"{:016x}\", \"parse_ns\": {}, \"solve_ns\": {}, \"answer\": {}}}",
+                    record.day,
+                    record.part,
+                    JsonString(&record.variant),
+                    record.input_hash,
+                    record.parse.as_nanos(),
+                    record.solve.as_nanos(),
+                    JsonString(&record.answer),
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("[{}\n]\n", objects.join(","))
+    }
+
+    /// CSV with a header and a row for every record, times in nanoseconds.
+    pub fn to_csv(&self) -> String {
+        let rows = self.records.iter().map(|record| {
+            format!(
+                "{},{},{},{:016x},{},{},{}\n",
+                record.day,
+                record.part,
+                CsvField(&record.variant),
+                record.input_hash,
+                record.parse.as_nanos(),
+                record.solve.as_nanos(),
+                CsvField(&record.answer),
+            )
+        });
+        let mut csv = String::from("day,part,variant,input_hash,parse_ns,solve_ns,answer\n");
+        csv.extend(rows);
+        csv
+    }
+}
+
+/// Format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub const ALL: [Format; 2] = [Format::Json, Format::Csv];
+
+    /// Extension of the files in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv",
+        }
+    }
+
+    pub fn render(self, report: &Report) -> String {
+        match self {
+            Format::Json => report.to_json(),
+            Format::Csv => report.to_csv(),
+        }
+    }
+
+    /// `data:` URL of `report` in this format, for the web UIs to download.
+    pub fn data_url(self, report: &Report) -> String {
+        let data = self
+            .render(report)
+            .bytes()
+            .map(|byte| {
+                if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                    char::from(byte).to_string()
+                } else {
+                    format!("%{byte:02X}")
+                }
+            })
+            .collect::<String>();
+        format!("data:{};charset=utf-8,{data}", self.mime_type())
+    }
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        Format::ALL
+            .into_iter()
+            .find(|f| f.extension().eq_ignore_ascii_case(format))
+            .ok_or("invalid format, must be json or csv")
+    }
+}
+
+/// String quoted and escaped for JSON.
+struct JsonString<'a>(&'a str);
+
+impl Display for JsonString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// CSV field, quoted when needed.
+struct CsvField<'a>(&'a str);
+
+impl Display for CsvField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.contains([',', '"', '\n', '\r']) {
+            write!(f, "\"{}\"", self.0.replace('"', "\"\""))
+        } else {
+            f.write_str(self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        Report {
+            records: vec![
+                Record {
+                    day: 12,
+                    part: Part::One,
+                    variant: "rayon+recursion".to_string(),
+                    input_hash: 0xff,
+                    parse: Duration::from_micros(15),
+                    solve: Duration::from_millis(2),
+                    answer: "7169".to_string(),
+                },
+                Record {
+                    day: 24,
+                    part: Part::Two,
+                    variant: String::new(),
+                    input_hash: 0x0123_4567_89ab_cdef,
+                    parse: Duration::from_nanos(1),
+                    solve: Duration::from_nanos(2),
+                    answer: "print(\"a, b\")\n".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            report().to_json(),
+            r#"[
+  {"day": 12, "part": 1, "variant": "rayon+recursion", "input_hash": "00000000000000ff", "parse_ns": 15000, "solve_ns": 2000000, "answer": "7169"},
+  {"day": 24, "part": 2, "variant": "", "input_hash": "0123456789abcdef", "parse_ns": 1, "solve_ns": 2, "answer": "print(\"a, b\")\n"}
+]
+"#
+        );
+        assert_eq!(Report::default().to_json(), "[\n]\n");
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            report().to_csv(),
+            r#"day,part,variant,input_hash,parse_ns,solve_ns,answer
+12,1,rayon+recursion,00000000000000ff,15000,2000000,7169
+24,2,,0123456789abcdef,1,2,"print(""a, b"")
+"
+"#
+        );
+    }
+
+    #[test]
+    fn json_control_characters() {
+        assert_eq!(JsonString("a\u{1}b").to_string(), r#""a\u0001b""#);
+    }
+
+    #[test]
+    fn data_url() {
+        assert_eq!(
+            Format::Csv.data_url(&Report::default()),
+            "data:text/csv;charset=utf-8,day%2Cpart%2Cvariant%2Cinput_hash%2Cparse_ns%2Csolve_ns%2Canswer%0A"
+        );
+    }
+
+    #[test]
+    fn format_from_str() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("CSV".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
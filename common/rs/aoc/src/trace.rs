@@ -0,0 +1,73 @@
+//! Optional `tracing` instrumentation of the solvers.
+//!
+//! With the `tracing` feature [`span!`](crate::span) enters an info
+//! level span until the end of the scope and [`event!`](crate::event)
+//! emits an info level event. Without it they expand to nothing, their
+//! arguments are not even evaluated, so that the days are instrumented
+//! without a `cfg` at every call:
+//!
+//! ```
+//! let nodes = 42;
+//!
+//! let _span = aoc::span!("compress", nodes);
+//! aoc::event!(edges = nodes * 2, "compressed");
+//! ```
+//!
+//! The day crates forward their `tracing` feature to this crate, the
+//! `aoc` runner collects the spans with `run --trace`.
+
+#[cfg(feature = "tracing")]
+pub use tracing;
+
+/// Span entered by [`span!`](crate::span) without the `tracing`
+/// feature, doing nothing.
+#[cfg(not(feature = "tracing"))]
+#[derive(Debug, Default)]
+pub struct Entered;
+
+#[cfg(not(feature = "tracing"))]
+impl Entered {
+    pub fn new() -> Self {
+        Entered
+    }
+}
+
+/// Enter an info level span, named and with fields as
+/// [`tracing::info_span!`](https://docs.rs/tracing/latest/tracing/macro.info_span.html),
+/// until the returned guard is dropped.
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! span {
+    ($($arg:tt)+) => {
+        $crate::trace::tracing::info_span!($($arg)+).entered()
+    };
+}
+
+/// Enter an info level span, named and with fields as
+/// [`tracing::info_span!`](https://docs.rs/tracing/latest/tracing/macro.info_span.html),
+/// until the returned guard is dropped.
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! span {
+    ($($arg:tt)+) => {
+        $crate::trace::Entered::new()
+    };
+}
+
+/// Emit an info level event, with fields and message as
+/// [`tracing::info!`](https://docs.rs/tracing/latest/tracing/macro.info.html).
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! event {
+    ($($arg:tt)+) => {
+        $crate::trace::tracing::info!($($arg)+)
+    };
+}
+
+/// Emit an info level event, with fields and message as
+/// [`tracing::info!`](https://docs.rs/tracing/latest/tracing/macro.info.html).
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! event {
+    ($($arg:tt)+) => {{}};
+}
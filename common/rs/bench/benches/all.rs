@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use aoc::Part;
+use bench::DAYS;
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let dir = bench::inputs_dir();
+    for day in &DAYS {
+        let inputs = day.inputs(dir.as_deref()).expect("cannot read inputs");
+        let variant = day.variant();
+
+        let mut group = c.benchmark_group(format!("day{:02}", day.puzzle.day()));
+        group.sample_size(10);
+        for input in &inputs {
+            for part in Part::ALL {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("part {part}/{variant}"), &input.name),
+                    &input.text,
+                    |b, input| b.iter(|| day.puzzle.solve(part, input)),
+                );
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
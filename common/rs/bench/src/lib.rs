@@ -0,0 +1,361 @@
+//! Benchmarks of every day, compared across saved baselines.
+//!
+//! The `all` bench times both parts of every day through the [`Puzzle`]
+//! interface, parsing included, on the puzzle input of the day and on
+//! the files in the `dayNN` subdirectory of the directory named by
+//! [`INPUTS_ENV`], as written by `inputgen`. The benchmarks are named
+//! `dayNN/part N/<variant>/<input>`, the variant being the enabled
+//! features of the day, so that the baselines of different variants do
+//! not mix.
+//!
+//! The `compare` binary reads the estimates saved by criterion for two
+//! baselines and prints the change of every benchmark, failing when one
+//! got slower than a threshold.
+
+#![deny(clippy::pedantic)]
+#![allow(clippy::must_use_candidate)]
+
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use aoc::batch::Input;
+use aoc::Puzzle;
+
+/// Environment variable with the directory of more inputs, in a `dayNN`
+/// subdirectory for every day.
+pub const INPUTS_ENV: &str = "AOC_BENCH_INPUTS";
+
+/// Name of the puzzle input of a day among the inputs.
+pub const PUZZLE_INPUT: &str = "puzzle";
+
+/// Day benchmarked.
+pub struct Day {
+    pub puzzle: &'static dyn Puzzle,
+    pub input: fn() -> &'static str,
+}
+
+impl Day {
+    /// Variant of the solvers, the enabled features of the day joined by
+    /// `+`, or `base` if none.
+    pub fn variant(&self) -> String {
+        match self.puzzle.features() {
+            [] => "base".to_string(),
+            features => features.join("+"),
+        }
+    }
+
+    /// Puzzle input of the day, then the files in the `dayNN`
+    /// subdirectory of `dir`, if any.
+    ///
+    /// # Errors
+    /// Return an error if the subdirectory or one of its files cannot be
+    /// read
+    pub fn inputs(&self, dir: Option<&Path>) -> io::Result<Vec<Input>> {
+        let mut inputs = vec![Input {
+            name: PUZZLE_INPUT.to_string(),
+            text: (self.input)().to_string(),
+        }];
+        if let Some(dir) = dir {
+            let dir = dir.join(format!("day{:02}", self.puzzle.day()));
+            if dir.is_dir() {
+                inputs.extend(Input::read_dir(&dir)?);
+            }
+        }
+        Ok(inputs)
+    }
+}
+
+macro_rules! days {
+    ($($krate:ident :: $solution:ident),* $(,)?) => {
+        /// Every day, indexed by day number minus one.
+        pub static DAYS: [Day; 25] = [
+            $(Day {
+                puzzle: &$krate::$solution,
+                input: || &$krate::INPUT,
+            }),*
+        ];
+    };
+}
+
+days!(
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+);
+
+/// Directory of more inputs named by [`INPUTS_ENV`], if set.
+pub fn inputs_dir() -> Option<PathBuf> {
+    env::var_os(INPUTS_ENV).map(PathBuf::from)
+}
+
+#[derive(Deserialize)]
+struct Benchmark {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// Mean time in nanoseconds of every benchmark saved by criterion in
+/// `dir` as `baseline`, by benchmark name.
+///
+/// # Errors
+/// Return an error if `dir` or the saved estimates cannot be read
+pub fn load(dir: &Path, baseline: &str) -> Result<BTreeMap<String, f64>, Box<dyn Error>> {
+    fn visit(
+        dir: &Path,
+        baseline: &str,
+        means: &mut BTreeMap<String, f64>,
+    ) -> Result<(), Box<dyn Error>> {
+        let saved = dir.join(baseline);
+        if saved.join("benchmark.json").is_file() {
+            let read = |name: &str| {
+                let path = saved.join(name);
+                fs::read_to_string(&path)
+                    .map_err(|err| format!("cannot read {}: {err}", path.display()))
+            };
+            let benchmark = serde_json::from_str::<Benchmark>(&read("benchmark.json")?)?;
+            let estimates = serde_json::from_str::<Estimates>(&read("estimates.json")?)?;
+            means.insert(benchmark.full_id, estimates.mean.point_estimate);
+        }
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() && entry.file_name() != baseline {
+                visit(&entry.path(), baseline, means)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut means = BTreeMap::new();
+    visit(dir, baseline, &mut means)
+        .map_err(|err| format!("cannot load baseline {baseline}: {err}"))?;
+    Ok(means)
+}
+
+/// Mean times of a benchmark in two baselines, in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub name: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    /// Change of the mean time, in percent of the time before.
+    pub fn percent(&self) -> f64 {
+        (self.after / self.before - 1.0) * 100.0
+    }
+
+    /// Whether the benchmark got slower by more than `threshold` percent.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Changes of the benchmarks in both `before` and `after`, by name.
+pub fn compare(before: &BTreeMap<String, f64>, after: &BTreeMap<String, f64>) -> Vec<Change> {
+    before
+        .iter()
+        .filter_map(|(name, &before)| {
+            after.get(name).map(|&after| Change {
+                name: name.clone(),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+/// Table with a row for every change, the regressions above `threshold`
+/// percent marked.
+pub fn table(changes: &[Change], threshold: f64) -> String {
+    let width = changes
+        .iter()
+        .map(|change| change.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("benchmark".len());
+
+    let mut table = format!(
+        "{:<width$}  {:>12}  {:>12}  {:>8}\n",
+        "benchmark", "before", "after", "change"
+    );
+    for change in changes {
+        let _ = writeln!(
+            table,
+            "{:<width$}  {:>12}  {:>12}  {:>+7.1}%{}",
+            change.name,
+            nanos(change.before),
+            nanos(change.after),
+            change.percent(),
+            if change.regressed(threshold) {
+                "  regressed"
+            } else {
+                ""
+            }
+        );
+    }
+    table
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos(nanos: f64) -> String {
+    format!(
+        "{:.3?}",
+        std::time::Duration::from_nanos(nanos.round() as u64)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(day.puzzle.day()), i + 1);
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-bench-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn inputs() {
+        let dir = temp_dir("inputs");
+        fs::create_dir_all(dir.join("day06")).unwrap();
+        fs::write(dir.join("day06").join("large"), "Time: 1\nDistance: 0\n").unwrap();
+
+        let inputs = DAYS[5].inputs(Some(&dir));
+        let others = DAYS[6].inputs(Some(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names = |inputs: Vec<Input>| {
+            inputs
+                .into_iter()
+                .map(|input| input.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(inputs.unwrap()), ["puzzle", "large"]);
+        assert_eq!(names(others.unwrap()), ["puzzle"]);
+    }
+
+    fn save(dir: &Path, baseline: &str, id: &str, mean: f64) {
+        let saved = dir.join(id.replace('/', "_")).join(baseline);
+        fs::create_dir_all(&saved).unwrap();
+        fs::write(
+            saved.join("benchmark.json"),
+            format!(r#"{{"group_id":"day01","function_id":null,"value_str":null,"throughput":null,"full_id":"{id}","directory_name":"{id}","title":"{id}"}}"#),
+        )
+        .unwrap();
+        fs::write(
+            saved.join("estimates.json"),
+            format!(r#"{{"mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":1.0,"upper_bound":2.0}},"point_estimate":{mean},"standard_error":0.1}}}}"#),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn load_and_compare() {
+        let dir = temp_dir("load");
+        save(&dir, "main", "day01/part 1/base/puzzle", 1000.0);
+        save(&dir, "main", "day01/part 2/base/puzzle", 2000.0);
+        save(&dir, "main", "day02/part 1/base/puzzle", 500.0);
+        save(&dir, "new", "day01/part 1/base/puzzle", 1200.0);
+        save(&dir, "new", "day01/part 2/base/puzzle", 1000.0);
+
+        let before = load(&dir, "main");
+        let after = load(&dir, "new");
+        let missing = load(&dir.join("missing"), "main");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let before = before.unwrap();
+        assert_eq!(before.len(), 3);
+        assert!(missing.is_err());
+
+        let changes = compare(&before, &after.unwrap());
+        assert_eq!(
+            changes,
+            [
+                Change {
+                    name: "day01/part 1/base/puzzle".to_string(),
+                    before: 1000.0,
+                    after: 1200.0,
+                },
+                Change {
+                    name: "day01/part 2/base/puzzle".to_string(),
+                    before: 2000.0,
+                    after: 1000.0,
+                },
+            ]
+        );
+        assert!(changes[0].regressed(5.0));
+        assert!(!changes[0].regressed(25.0));
+        assert!(!changes[1].regressed(5.0));
+    }
+
+    #[test]
+    fn table_of_changes() {
+        let changes = [
+            Change {
+                name: "day01/part 1/base/puzzle".to_string(),
+                before: 1000.0,
+                after: 1200.0,
+            },
+            Change {
+                name: "day25/part 1/base/puzzle".to_string(),
+                before: 2_000_000.0,
+                after: 1_000_000.0,
+            },
+        ];
+        assert_eq!(
+            table(&changes, 5.0),
+            "\
+benchmark                       before         after    change
+day01/part 1/base/puzzle       1.000µs       1.200µs    +20.0%  regressed
+day25/part 1/base/puzzle       2.000ms       1.000ms    -50.0%
+"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Comparison of two baselines of the `all` bench.
+//!
+//! ```text
+//! cargo bench -- --save-baseline main
+//! cargo bench
+//! compare --baseline main
+//! compare --baseline main --against rayon --threshold 10
+//! ```
+//!
+//! Prints the change of the mean time of every benchmark saved in both
+//! baselines, `new` being the last run of the bench, and fails if any
+//! got slower than the threshold.
+
+#![deny(clippy::pedantic)]
+
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+/// Target directory of this crate, unless told by `CARGO_TARGET_DIR`.
+const TARGET_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target");
+
+#[derive(Parser)]
+#[command(
+    name = "compare",
+    about = "Compare the benchmarks of every day across two baselines"
+)]
+struct Cli {
+    /// Baseline to compare against, saved with `--save-baseline`
+    #[arg(short, long)]
+    baseline: String,
+
+    /// Baseline compared, the last run if missing
+    #[arg(short, long, default_value = "new")]
+    against: String,
+
+    /// Slowdown in percent above which a benchmark regressed
+    #[arg(short, long, default_value_t = 5.0)]
+    threshold: f64,
+
+    /// Directory of the criterion results, `criterion` in the target
+    /// directory if missing
+    #[arg(long)]
+    criterion_dir: Option<PathBuf>,
+}
+
+/// Compare the baselines, returning how many benchmarks regressed.
+fn compare(cli: &Cli) -> Result<usize, Box<dyn Error>> {
+    let dir = cli.criterion_dir.clone().unwrap_or_else(|| {
+        env::var_os("CARGO_TARGET_DIR")
+            .map_or_else(|| PathBuf::from(TARGET_DIR), PathBuf::from)
+            .join("criterion")
+    });
+
+    let before = bench::load(&dir, &cli.baseline)?;
+    let after = bench::load(&dir, &cli.against)?;
+    let changes = bench::compare(&before, &after);
+    if changes.is_empty() {
+        return Err(format!(
+            "no benchmarks saved in both {} and {}",
+            cli.baseline, cli.against
+        )
+        .into());
+    }
+
+    print!("{}", bench::table(&changes, cli.threshold));
+    Ok(changes
+        .iter()
+        .filter(|change| change.regressed(cli.threshold))
+        .count())
+}
+
+fn main() -> ExitCode {
+    match compare(&Cli::parse()) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(regressions) => {
+            eprintln!("error: {regressions} benchmarks regressed");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use aoc::input::ReadError;
+use aoc::{ParseError, Part, Puzzle, Solution};
+
+/// Day known by the runner.
+pub struct Day {
+    pub puzzle: &'static dyn Puzzle,
+    pub input: fn() -> &'static str,
+}
+
+macro_rules! days {
+    ($($krate:ident :: $solution:ident),* $(,)?) => {
+        /// Every day, indexed by day number minus one.
+        pub static DAYS: [Day; 25] = [
+            $(Day {
+                puzzle: &$krate::$solution,
+                input: || &$krate::INPUT,
+            }),*
+        ];
+    };
+}
+
+days!(
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+);
+
+/// Solver of a part reading the input a line at a time.
+pub type Stream = fn(Part, &mut dyn BufRead) -> Result<String, ReadError>;
+
+macro_rules! streams {
+    ($($day:literal => $krate:ident),* $(,)?) => {
+        /// Days with a solver reading the input a line at a time. Days 1, 2,
+        /// 4, 9, 12 and 18 keep a line at a time, day 7 a count for every
+        /// hand and bid, day 22 the bricks seen from above, its input sorted
+        /// by their bottom, and day 24 the first three hailstones for part 2
+        /// but every hailstone for part 1.
+        pub const STREAMED: &[u8] = &[$($day),*];
+
+        /// Solver of `day` reading the input a line at a time, if any.
+        pub fn stream(day: u8) -> Option<Stream> {
+            match day {
+                $($day => Some(|part, reader| match part {
+                    Part::One => $krate::solve_1_reader(reader).map(|answer| answer.to_string()),
+                    Part::Two => $krate::solve_2_reader(reader).map(|answer| answer.to_string()),
+                }),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+streams!(
+    1 => day01,
+    2 => day02,
+    4 => day04,
+    7 => day07,
+    9 => day09,
+    12 => day12,
+    18 => day18,
+    22 => day22,
+    24 => day24,
+);
+
+/// Parser of a day dumping the parsed input as JSON.
+pub type Dump = fn(&str) -> Result<String, ParseError>;
+
+macro_rules! models {
+    ($($day:literal => $krate:ident :: $solution:ident),* $(,)?) => {
+        /// Days with a parsed input that can be dumped.
+        pub const MODELED: &[u8] = &[$($day),*];
+
+        /// Parser of `day` dumping the parsed input as JSON, if any.
+        pub fn dump(day: u8) -> Option<Dump> {
+            match day {
+                $($day => Some(|input| Ok(to_json(&$krate::$solution::try_parse(input)?))),)*
+                _ => None,
+            }
+        }
+
+        #[cfg(test)]
+        fn round_trips(day: u8, input: &str) -> bool {
+            match day {
+                $($day => round_trip::<$krate::$solution>(input),)*
+                _ => unreachable!(),
+            }
+        }
+    };
+}
+
+models!(
+    5 => day05::Day05,
+    19 => day19::Day19,
+    20 => day20::Day20,
+    22 => day22::Day22,
+    24 => day24::Day24,
+    25 => day25::Day25,
+);
+
+fn to_json(model: &impl Serialize) -> String {
+    serde_json::to_string_pretty(model).expect("model cannot be serialized")
+}
+
+/// JSON of `model` with the elements of the arrays sorted, for the sets
+/// serialized as arrays in the order of their hashes.
+#[cfg(test)]
+fn canonical(model: &impl Serialize) -> serde_json::Value {
+    fn sort(value: serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+
+        match value {
+            Value::Array(values) => {
+                let mut values = values.into_iter().map(sort).collect::<Vec<_>>();
+                values.sort_unstable_by_key(Value::to_string);
+                Value::Array(values)
+            }
+            Value::Object(map) => map.into_iter().map(|(k, v)| (k, sort(v))).collect(),
+            value => value,
+        }
+    }
+
+    sort(serde_json::to_value(model).expect("model cannot be serialized"))
+}
+
+/// Whether the parsed `input` read back from JSON has the same answers
+/// and the same JSON, but for the order of the maps and sets.
+#[cfg(test)]
+fn round_trip<S: Solution>(input: &str) -> bool
+where
+    for<'a> S::Input<'a>: Serialize + serde::Deserialize<'a>,
+{
+    let model = S::parse(input);
+    let json = to_json(&model);
+    let read = serde_json::from_str::<S::Input<'_>>(&json).unwrap();
+
+    canonical(&read) == canonical(&model)
+        && S::part_1(&read).to_string() == S::part_1(&model).to_string()
+        && S::part_2(&read).to_string() == S::part_2(&model).to_string()
+}
+
+/// Selection of days: a single day, a range like `3-7` or `all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(RangeInclusive<u8>);
+
+impl Days {
+    pub fn is_single(&self) -> bool {
+        self.0.start() == self.0.end()
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static Day> {
+        self.0.clone().map(|day| &DAYS[usize::from(day) - 1])
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(days: &str) -> Result<Self, Self::Err> {
+        fn day(day: &str) -> Result<u8, String> {
+            match day.trim().parse() {
+                Ok(day @ 1..=25) => Ok(day),
+                _ => Err(format!("invalid day {day:?}, must be between 1 and 25")),
+            }
+        }
+
+        if days == "all" {
+            return Ok(Days(1..=25));
+        }
+
+        let (first, last) = if let Some((first, last)) = days.split_once('-') {
+            (day(first)?, day(last)?)
+        } else {
+            let day = day(days)?;
+            (day, day)
+        };
+
+        if first > last {
+            return Err(format!("invalid range {days:?}, {first} is after {last}"));
+        }
+
+        Ok(Days(first..=last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn days_are_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(day.puzzle.day()), i + 1);
+        }
+    }
+
+    #[test]
+    fn examples_pass() {
+        for day in &DAYS {
+            for example in day.puzzle.examples() {
+                assert!(
+                    example.check(day.puzzle, &mut Duration::default).passed(),
+                    "day {} part {} {}",
+                    day.puzzle.day(),
+                    example.part,
+                    example.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn streams_match_puzzles() {
+        let position = |error: &ParseError| (error.line, error.column, error.kind);
+        for &day in STREAMED {
+            let mut input = (DAYS[usize::from(day) - 1].input)().to_string();
+            if day == 22 {
+                // streamed in the order of the bottom of the bricks
+                let mut lines = input.lines().collect::<Vec<_>>();
+                lines.sort_by_key(|line| {
+                    let (start, _) = line.split_once('~').unwrap();
+                    start.rsplit(',').next().unwrap().parse::<usize>().unwrap()
+                });
+                input = lines.join("\n") + "\n";
+            }
+            let invalid = format!("{input}x\n");
+            for input in [&input, &invalid] {
+                for part in Part::ALL {
+                    let stream = stream(day).unwrap();
+                    let streamed = stream(part, &mut input.as_bytes()).map_err(|err| match err {
+                        ReadError::Parse(error) => position(&error),
+                        ReadError::Io(err) => panic!("day {day} part {part}: {err}"),
+                    });
+                    let solved = DAYS[usize::from(day) - 1].puzzle.solve(part, input);
+                    assert_eq!(
+                        streamed,
+                        solved.map_err(|error| position(&error)),
+                        "day {day} part {part}"
+                    );
+                }
+            }
+        }
+        assert!(stream(3).is_none());
+    }
+
+    #[test]
+    fn models_round_trip() {
+        for &day in MODELED {
+            let input = (DAYS[usize::from(day) - 1].input)();
+            assert!(round_trips(day, input), "day {day}");
+        }
+        assert!(dump(1).is_none());
+        assert!(dump(5).unwrap()("seeds: 1\n\nmap:\n1 2 x").is_err());
+    }
+
+    #[test]
+    fn parse_days() {
+        assert_eq!("17".parse(), Ok(Days(17..=17)));
+        assert_eq!("3-7".parse(), Ok(Days(3..=7)));
+        assert_eq!("all".parse(), Ok(Days(1..=25)));
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("7-3".parse::<Days>().is_err());
+        assert!("x".parse::<Days>().is_err());
+    }
+}
@@ -0,0 +1,434 @@
+//! Command line runner of the Advent of Code 2023 solutions.
+//!
+//! ```text
+//! aoc run --day 17 --part 2 --input path
+//! aoc run --day 3-7
+//! aoc run
+//! aoc run --day 12 --input path --record
+//! aoc run --report timings.csv
+//! aoc verify
+//! zcat input.gz | aoc stream --day 7 --part 1
+//! aoc batch --day 22 --inputs path --jobs 4
+//! aoc parse --day 19 > workflows.json
+//! aoc run --day 23 --trace
+//! ```
+//!
+//! `--trace` prints a flame-style summary of the `tracing` spans of the
+//! solvers, with the `tracing` feature. With the `count-alloc` feature
+//! `run` prints the heap usage of every part too.
+
+#![deny(clippy::pedantic)]
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use clap::{Args, Parser, Subcommand};
+
+use aoc::batch;
+use aoc::report::{Format, Record, Report};
+use aoc::Part;
+
+mod days;
+mod registry;
+#[cfg(feature = "tracing")]
+mod trace;
+
+use days::{Days, DAYS};
+use registry::{Entry, Registry, Source};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, a range of days or all of them
+    Run(RunArgs),
+    /// Solve again the inputs in the registry, checking their answers
+    Verify(VerifyArgs),
+    /// Solve a part reading the input a line at a time, for inputs too big to be kept in memory
+    Stream(StreamArgs),
+    /// Solve every input in a directory, in parallel
+    Batch(BatchArgs),
+    /// Print the parsed input of a day as JSON
+    Parse(ParseArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve: a number, a range like `3-7` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Days,
+
+    /// Part to solve, both if missing
+    #[arg(short, long)]
+    part: Option<Part>,
+
+    /// Input file, `-` for the standard input, the puzzle input of the day if missing
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Record the answers in the registry, as known-good
+    #[arg(long)]
+    record: bool,
+
+    /// Registry of known-good answers
+    #[arg(long, default_value = registry::DEFAULT_PATH)]
+    registry: PathBuf,
+
+    /// Write a timing report, in JSON or CSV as told by the file extension
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Print a flame-style summary of where the time went
+    #[cfg(feature = "tracing")]
+    #[arg(long)]
+    trace: bool,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to verify: a number, a range like `3-7` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Days,
+
+    /// Registry of known-good answers
+    #[arg(long, default_value = registry::DEFAULT_PATH)]
+    registry: PathBuf,
+}
+
+#[derive(Args)]
+struct StreamArgs {
+    /// Day to solve
+    #[arg(short, long)]
+    day: u8,
+
+    /// Part to solve
+    #[arg(short, long)]
+    part: Part,
+
+    /// Input file, the standard input if missing
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Day to solve
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part to solve, both if missing
+    #[arg(short, long)]
+    part: Option<Part>,
+
+    /// Directory of the inputs, a file each
+    #[arg(short, long)]
+    inputs: PathBuf,
+
+    /// Number of threads, as many as the CPUs if missing
+    #[arg(short, long)]
+    jobs: Option<usize>,
+}
+
+#[derive(Args)]
+struct ParseArgs {
+    /// Day to parse
+    #[arg(short, long)]
+    day: u8,
+
+    /// Input file, `-` for the standard input, the puzzle input of the day if missing
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    if args.input.is_some() && !args.day.is_single() {
+        return Err("--input needs a single day".into());
+    }
+
+    let file = match &args.input {
+        Some(path) => Some(
+            aoc::input::read(path)
+                .map_err(|err| format!("cannot read input {}: {err}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let source = match &args.input {
+        _ if !args.record => None,
+        Some(path) if path.as_os_str() == "-" => {
+            return Err("cannot record the answers of the standard input".into());
+        }
+        Some(path) => Some(Source::File(fs::canonicalize(path)?)),
+        None => Some(Source::Puzzle),
+    };
+    let mut registry = match source {
+        Some(_) => Registry::load(&args.registry)?,
+        None => Registry::default(),
+    };
+
+    let format = match &args.report {
+        Some(path) => Some(
+            path.extension()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .parse::<Format>()
+                .map_err(|err| format!("invalid report {}: {err}", path.display()))?,
+        ),
+        None => None,
+    };
+    let mut report = Report::default();
+
+    #[cfg(feature = "tracing")]
+    let summary = args.trace.then(trace::Summary::install);
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let mut total = Duration::ZERO;
+    for day in args.day.iter() {
+        let input = file.as_deref().unwrap_or_else(|| (day.input)());
+        let hash = aoc::input::hash(input);
+
+        println!("day {:02}: {}", day.puzzle.day(), day.puzzle.title());
+        for &part in &parts {
+            let mut last = Instant::now();
+            let mut lap = || {
+                let now = Instant::now();
+                let elapsed = now - last;
+                last = now;
+                elapsed
+            };
+            let (timed, usage) =
+                aoc::memory::measure(|| day.puzzle.solve_timed(part, input, &mut lap));
+            let timed = timed
+                .map_err(|err| format!("invalid input for day {}: {err}", day.puzzle.day()))?;
+            let elapsed = timed.elapsed();
+
+            println!("  part {part}: {} ({elapsed:?})", timed.answer);
+            if let Some(usage) = usage {
+                println!("  part {part}: {usage}");
+            }
+
+            total += elapsed;
+
+            if let Some(source) = &source {
+                let entry = Entry {
+                    source: source.clone(),
+                    answer: timed.answer.clone(),
+                };
+                match registry.record(day.puzzle.day(), hash, part, entry) {
+                    Some(old) if old.answer != timed.answer => {
+                        println!("  part {part}: replaced {}", registry::escape(&old.answer));
+                    }
+                    _ => {}
+                }
+            }
+
+            report
+                .records
+                .push(Record::new(day.puzzle, part, input, timed));
+        }
+    }
+
+    println!("elapsed: {}ms ({}ns)", total.as_millis(), total.as_nanos());
+
+    #[cfg(feature = "tracing")]
+    if let Some(summary) = summary {
+        print!("{summary}");
+    }
+
+    if source.is_some() {
+        registry
+            .save(&args.registry)
+            .map_err(|err| format!("cannot write registry {}: {err}", args.registry.display()))?;
+        println!("recorded in {}", args.registry.display());
+    }
+
+    if let (Some(path), Some(format)) = (&args.report, format) {
+        fs::write(path, format.render(&report))
+            .map_err(|err| format!("cannot write report {}: {err}", path.display()))?;
+        println!("report in {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), Box<dyn Error>> {
+    let registry = Registry::load(&args.registry)?;
+
+    let (mut verified, mut failed) = (0, 0);
+    for (day, hash, part, entry) in registry.iter().filter(|&(day, ..)| args.day.contains(day)) {
+        let solver = &DAYS[usize::from(day) - 1];
+        print!("day {day:02} part {part} ({}): ", entry.source);
+        verified += 1;
+
+        let input = match &entry.source {
+            Source::Puzzle => Cow::Borrowed((solver.input)()),
+            Source::File(path) => match aoc::input::read(path) {
+                Ok(input) => Cow::Owned(input),
+                Err(err) => {
+                    println!("FAILED, cannot read input: {err}");
+                    failed += 1;
+                    continue;
+                }
+            },
+        };
+        if aoc::input::hash(&input) != hash {
+            println!("FAILED, the input changed");
+            failed += 1;
+            continue;
+        }
+
+        match solver.puzzle.solve(part, &input) {
+            Ok(answer) if answer == entry.answer => println!("ok"),
+            Ok(answer) => {
+                println!(
+                    "FAILED, expected {} but got {}",
+                    registry::escape(&entry.answer),
+                    registry::escape(&answer)
+                );
+                failed += 1;
+            }
+            Err(err) => {
+                println!("FAILED, invalid input: {err}");
+                failed += 1;
+            }
+        }
+    }
+
+    if verified == 0 {
+        return Err(format!("no answers to verify in {}", args.registry.display()).into());
+    }
+    if failed > 0 {
+        return Err(format!("{failed} of {verified} answers failed verification").into());
+    }
+    println!("verified {verified} answers");
+
+    Ok(())
+}
+
+fn stream(args: &StreamArgs) -> Result<(), Box<dyn Error>> {
+    let Some(solve) = days::stream(args.day) else {
+        let days = days::STREAMED.iter().map(u8::to_string).collect::<Vec<_>>();
+        return Err(format!(
+            "day {} cannot be streamed, only days {}",
+            args.day,
+            days.join(", ")
+        )
+        .into());
+    };
+    let day = &DAYS[usize::from(args.day) - 1];
+
+    let now = Instant::now();
+    let answer = match &args.input {
+        Some(path) => {
+            let file = fs::File::open(path)
+                .map_err(|err| format!("cannot read input {}: {err}", path.display()))?;
+            solve(args.part, &mut BufReader::new(file))
+        }
+        None => solve(args.part, &mut io::stdin().lock()),
+    }
+    .map_err(|err| format!("invalid input for day {}: {err}", args.day))?;
+    let elapsed = now.elapsed();
+
+    println!("day {:02}: {}", day.puzzle.day(), day.puzzle.title());
+    println!("  part {}: {answer} ({elapsed:?})", args.part);
+
+    Ok(())
+}
+
+fn batch(args: &BatchArgs) -> Result<(), Box<dyn Error>> {
+    let day = &DAYS[usize::from(args.day) - 1];
+
+    let inputs = batch::Input::read_dir(&args.inputs)
+        .map_err(|err| format!("cannot read inputs {}: {err}", args.inputs.display()))?;
+    if inputs.is_empty() {
+        return Err(format!("no inputs in {}", args.inputs.display()).into());
+    }
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or_default())
+        .build()?;
+    let now = Instant::now();
+    let solved = pool.install(|| batch::solve(day.puzzle, &inputs, &parts));
+    let elapsed = now.elapsed();
+
+    println!("day {:02}: {}", day.puzzle.day(), day.puzzle.title());
+    print!("{}", batch::table(&solved));
+    println!(
+        "elapsed: {}ms ({}ns), {} threads",
+        elapsed.as_millis(),
+        elapsed.as_nanos(),
+        pool.current_num_threads()
+    );
+
+    let failed = solved
+        .iter()
+        .filter(|solved| solved.result.is_err())
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} parts failed", solved.len()).into());
+    }
+
+    Ok(())
+}
+
+fn parse(args: &ParseArgs) -> Result<(), Box<dyn Error>> {
+    let Some(dump) = days::dump(args.day) else {
+        let days = days::MODELED.iter().map(u8::to_string).collect::<Vec<_>>();
+        return Err(format!(
+            "day {} cannot be parsed, only days {}",
+            args.day,
+            days.join(", ")
+        )
+        .into());
+    };
+    let day = &DAYS[usize::from(args.day) - 1];
+
+    let input = match &args.input {
+        Some(path) => Cow::Owned(
+            aoc::input::read(path)
+                .map_err(|err| format!("cannot read input {}: {err}", path.display()))?,
+        ),
+        None => Cow::Borrowed((day.input)()),
+    };
+    let json = dump(&input).map_err(|err| format!("invalid input for day {}: {err}", args.day))?;
+    println!("{json}");
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run(args) => run(&args),
+        Command::Verify(args) => verify(&args),
+        Command::Stream(args) => stream(&args),
+        Command::Batch(args) => batch(&args),
+        Command::Parse(args) => parse(&args),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
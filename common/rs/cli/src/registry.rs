@@ -0,0 +1,258 @@
+//! Registry of known-good answers, keyed by day and input hash.
+//!
+//! The registry is a text file with an answer on every line, made of
+//! tab separated fields: the day, the part, the [`aoc::input::hash`] of
+//! the input, where to find the input again and the answer. The input is
+//! either `puzzle`, the puzzle input of the day, or the path of a file:
+//!
+//! ```text
+//! 14  1  9d3f0c6ab4e2d517  puzzle          108813
+//! 14  2  0f5a4e1c2b7d8396  /path/to/input  104533
+//! ```
+//!
+//! Tabs, newlines and backslashes in the fields are escaped with a
+//! backslash. Blank lines and lines starting with `#` are ignored.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc::Part;
+
+/// Registry used when none is given.
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
+
+/// Where the input of an answer is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Puzzle input of the day.
+    Puzzle,
+    /// Input file.
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Puzzle => write!(f, "puzzle"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Known-good answer of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub source: Source,
+    pub answer: String,
+}
+
+/// Answers by day, input hash and part.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Registry {
+    entries: BTreeMap<(u8, u64, Part), Entry>,
+}
+
+impl Registry {
+    /// Read the registry at `path`, empty if there is no file.
+    ///
+    /// # Errors
+    /// Return an error if the file cannot be read or is invalid
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|err| format!("invalid registry {}: {err}", path.display()).into()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("cannot read registry {}: {err}", path.display()).into()),
+        }
+    }
+
+    /// Write the registry at `path`.
+    ///
+    /// # Errors
+    /// Return an error if the file cannot be written
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Register the answer of `part` of `day` for the input with `hash`,
+    /// returning the answer it replaces.
+    pub fn record(&mut self, day: u8, hash: u64, part: Part, entry: Entry) -> Option<Entry> {
+        self.entries.insert((day, hash, part), entry)
+    }
+
+    /// Answers by day, input hash and part, in this order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u64, Part, &Entry)> {
+        self.entries
+            .iter()
+            .map(|(&(day, hash, part), entry)| (day, hash, part, entry))
+    }
+}
+
+impl Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day\tpart\tinput hash\tinput\tanswer")?;
+        for (day, hash, part, entry) in self.iter() {
+            writeln!(
+                f,
+                "{day}\t{part}\t{hash:016x}\t{}\t{}",
+                escape(&entry.source.to_string()),
+                escape(&entry.answer)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Registry {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut registry = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |what: &str| format!("line {}: invalid {what}", i + 1);
+            let [day, part, hash, source, answer] = line
+                .split('\t')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid("number of fields"))?;
+
+            let day = day
+                .parse()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| invalid("day"))?;
+            let part = part.parse().map_err(|_| invalid("part"))?;
+            let hash = u64::from_str_radix(hash, 16).map_err(|_| invalid("hash"))?;
+            let source = match unescape(source).ok_or_else(|| invalid("input"))? {
+                source if source == "puzzle" => Source::Puzzle,
+                path => Source::File(path.into()),
+            };
+            let answer = unescape(answer).ok_or_else(|| invalid("answer"))?;
+
+            if registry
+                .record(day, hash, part, Entry { source, answer })
+                .is_some()
+            {
+                return Err(format!("line {}: duplicated answer", i + 1));
+            }
+        }
+
+        Ok(registry)
+    }
+}
+
+/// Field with its tabs, newlines and backslashes escaped.
+pub fn escape(field: &str) -> String {
+    field.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            });
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        let field = "a\tb\nc\\d\re";
+        assert_eq!(escape(field), "a\\tb\\nc\\\\d\\re");
+        assert_eq!(unescape(&escape(field)).as_deref(), Some(field));
+        assert_eq!(unescape("a\\"), None);
+        assert_eq!(unescape("a\\x"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut registry = Registry::default();
+        registry.record(
+            14,
+            aoc::input::hash("input"),
+            Part::One,
+            Entry {
+                source: Source::Puzzle,
+                answer: "108813".to_string(),
+            },
+        );
+        registry.record(
+            24,
+            aoc::input::hash("other"),
+            Part::Two,
+            Entry {
+                source: Source::File("/some where/input".into()),
+                answer: "line 1\nline 2".to_string(),
+            },
+        );
+
+        let text = registry.to_string();
+        assert_eq!(text.lines().count(), 3);
+        assert_eq!(text.parse(), Ok(registry));
+    }
+
+    #[test]
+    fn record_replaces() {
+        let mut registry = Registry::default();
+        let entry = |answer: &str| Entry {
+            source: Source::Puzzle,
+            answer: answer.to_string(),
+        };
+        assert_eq!(registry.record(1, 0, Part::One, entry("1")), None);
+        assert_eq!(
+            registry.record(1, 0, Part::One, entry("2")),
+            Some(entry("1"))
+        );
+        assert_eq!(
+            registry.iter().collect::<Vec<_>>(),
+            [(1, 0, Part::One, &entry("2"))]
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert_eq!("# comment\n\n".parse(), Ok(Registry::default()));
+        for line in [
+            "1\t1\t0\tpuzzle",
+            "0\t1\t0\tpuzzle\t1",
+            "1\t3\t0\tpuzzle\t1",
+            "1\t1\tx\tpuzzle\t1",
+            "1\t1\t0\tpuzzle\t1\\",
+            "1\t1\t0\tpuzzle\t1\n1\t1\t0\tpuzzle\t2",
+        ] {
+            assert!(line.parse::<Registry>().is_err(), "{line:?}");
+        }
+    }
+}
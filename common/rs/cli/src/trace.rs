@@ -0,0 +1,239 @@
+//! Flame-style summary of the `tracing` spans of a run.
+//!
+//! Spans are merged by their path from the root span, as a flame graph
+//! does, keeping the time spent inside them, how many times they were
+//! entered and the events emitted in them. Every day has the `puzzle`,
+//! `parse` and `solve` spans, only days 14, 20 and 23 and the graph
+//! searches of `aoc` trace the steps of their solvers:
+//!
+//! ```text
+//!                                            40.207µs      1x puzzle day=14 part=1
+//!                                            23.719µs      1x   parse
+//!                                             8.954µs      1x   solve
+//! ████████████████████████████████████████   29.866ms      1x puzzle day=14 part=2
+//!                                            12.505µs      1x   parse
+//! ████████████████████████████████████████   29.850ms      1x   solve
+//! ████████████████████████████████████████   29.668ms      1x     cycle_search
+//!                                                          1x       · "cycle found" start=102 length=14
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Width of the bar of the spans taking all the time.
+const WIDTH: usize = 40;
+
+/// Spans with the same path, merged.
+#[derive(Debug, Default)]
+struct Node {
+    busy: Duration,
+    entered: u64,
+    events: BTreeMap<String, u64>,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn get(&mut self, path: &[String]) -> &mut Node {
+        path.iter().fold(self, |node, label| {
+            node.children.entry(label.clone()).or_default()
+        })
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn write(&self, f: &mut fmt::Formatter, depth: usize, total: Duration) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        for (event, count) in &self.events {
+            writeln!(f, "{:<WIDTH$} {:>10} {count:>6}x {indent}· {event}", "", "")?;
+        }
+        for (label, child) in &self.children {
+            let width = if total.is_zero() {
+                0
+            } else {
+                (child.busy.as_secs_f64() / total.as_secs_f64() * WIDTH as f64).round() as usize
+            };
+            writeln!(
+                f,
+                "{:<WIDTH$} {:>10.3?} {:>6}x {indent}{label}",
+                "█".repeat(width),
+                child.busy,
+                child.entered,
+            )?;
+            child.write(f, depth + 1, total)?;
+        }
+        Ok(())
+    }
+}
+
+/// Path of a span, from the root, and when it was entered.
+struct Timing {
+    path: Vec<String>,
+    entered: Option<Instant>,
+}
+
+/// Name and fields of a span or an event, as `name field=value`.
+struct Label(String);
+
+impl Visit for Label {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {:?}", format!("{value:?}"));
+        } else {
+            let _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// Layer collecting the spans, printed as a summary when displayed.
+#[derive(Debug, Clone, Default)]
+pub struct Summary(Arc<Mutex<Node>>);
+
+impl Summary {
+    /// Collect the spans of the program from now on.
+    ///
+    /// # Panics
+    /// Panic if a subscriber is already installed
+    pub fn install() -> Self {
+        let summary = Summary::default();
+        tracing::subscriber::set_global_default(
+            tracing_subscriber::registry().with(summary.clone()),
+        )
+        .expect("tracing subscriber already installed");
+        summary
+    }
+
+    fn root(&self) -> MutexGuard<'_, Node> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Summary {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut path = span
+            .parent()
+            .and_then(|parent| {
+                parent
+                    .extensions()
+                    .get::<Timing>()
+                    .map(|timing| timing.path.clone())
+            })
+            .unwrap_or_default();
+        let mut label = Label(attrs.metadata().name().to_string());
+        attrs.record(&mut label);
+        path.push(label.0);
+
+        span.extensions_mut().insert(Timing {
+            path,
+            entered: None,
+        });
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<Timing>() {
+                timing.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(timing) = extensions.get_mut::<Timing>() else {
+            return;
+        };
+
+        if let Some(entered) = timing.entered.take() {
+            let mut root = self.root();
+            let node = root.get(&timing.path);
+            node.busy += entered.elapsed();
+            node.entered += 1;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let path = ctx
+            .event_span(event)
+            .and_then(|span| {
+                span.extensions()
+                    .get::<Timing>()
+                    .map(|timing| timing.path.clone())
+            })
+            .unwrap_or_default();
+        let mut label = Label(String::new());
+        event.record(&mut label);
+
+        *self
+            .root()
+            .get(&path)
+            .events
+            .entry(label.0.trim_start().to_string())
+            .or_default() += 1;
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let root = self.root();
+        let total = root.children.values().map(|node| node.busy).sum();
+        root.write(f, 0, total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let summary = Summary::default();
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry().with(summary.clone()),
+            || {
+                let _span = tracing::info_span!("puzzle", day = 14).entered();
+                for i in 0..2 {
+                    let _span = tracing::info_span!("phase").entered();
+                    tracing::info!(length = 7, "cycle");
+                    tracing::info!(i);
+                }
+            },
+        );
+
+        let summary = summary.to_string();
+        let lines = summary
+            .lines()
+            .map(|line| {
+                let columns = line.chars().skip(WIDTH + 11).collect::<String>();
+                columns.split_whitespace().collect::<Vec<_>>().join(" ")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "1x puzzle day=14",
+                "2x phase",
+                "2x · \"cycle\" length=7",
+                "1x · i=0",
+                "1x · i=1",
+            ]
+        );
+        assert!(summary.starts_with(&"█".repeat(WIDTH)));
+    }
+}
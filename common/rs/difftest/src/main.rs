@@ -0,0 +1,489 @@
+//! Differential tests of the solver variants selected by features.
+//!
+//! ```text
+//! difftest
+//! difftest --day 12 --day 22 --count 5 --scale 0.5 --seed 42
+//! difftest --inputs path --nightly
+//! ```
+//!
+//! Every variant of a day is built with cargo and run on the same
+//! inputs: the examples and the puzzle input of the day, random inputs
+//! from `inputgen`, and the files in the `dayNN` subdirectory of
+//! `--inputs`. The variants must give the same answers, or fail the same
+//! way. The seed of the random inputs is printed, `--seed` generates the
+//! same inputs again.
+
+#![deny(clippy::pedantic)]
+
+use std::env::consts::EXE_SUFFIX;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use clap::Parser;
+use inputgen::day_rng;
+
+mod variants;
+
+use variants::{DayVariants, Variant, DAYS};
+
+/// Root of the repository.
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../..");
+
+#[derive(Parser)]
+#[command(
+    name = "difftest",
+    about = "Check that the feature variants of the solvers agree"
+)]
+struct Cli {
+    /// Day to check, can be repeated, every day with variants if missing
+    #[arg(short, long)]
+    day: Vec<u8>,
+
+    /// Directory of more inputs, in a `dayNN` subdirectory for every day
+    #[arg(short, long)]
+    inputs: Option<PathBuf>,
+
+    /// Random inputs of every day
+    #[arg(short, long, default_value_t = 2)]
+    count: usize,
+
+    /// Size of the random inputs, relative to the puzzle inputs
+    #[arg(long, default_value_t = 1.)]
+    scale: f64,
+
+    /// Seed of the random inputs, a random one if missing
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Check also the variants that need the nightly toolchain
+    #[arg(long)]
+    nightly: bool,
+
+    /// Directory of the builds, `target/variants` of this crate if missing
+    #[arg(long)]
+    target_dir: Option<PathBuf>,
+}
+
+fn day_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{day:02}"))
+}
+
+/// Build `variant` of `day`, returns the path of its binary.
+fn build(root: &Path, target_dir: &Path, day: u8, variant: &Variant) -> Result<PathBuf, String> {
+    let name = format!("day{day:02}");
+    let toolchain = if variant.nightly { "nightly" } else { "stable" };
+
+    let mut cargo = Command::new("cargo");
+    if variant.nightly {
+        cargo.arg("+nightly");
+    }
+    cargo
+        .args(["build", "--release", "--quiet", "--no-default-features"])
+        .args(["--bin", &name])
+        .arg("--manifest-path")
+        .arg(day_dir(root, day).join("rs").join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", target_dir.join(toolchain));
+    if !variant.features.is_empty() {
+        cargo.args(["--features", &variant.features.join(",")]);
+    }
+
+    let status = cargo
+        .status()
+        .map_err(|err| format!("cannot run cargo: {err}"))?;
+    if !status.success() {
+        return Err(format!("build failed, {status}"));
+    }
+
+    // the next variant of the day overwrites the binary, keep a copy
+    let built = target_dir
+        .join(toolchain)
+        .join("release")
+        .join(format!("{name}{EXE_SUFFIX}"));
+    let binary = target_dir
+        .join("bin")
+        .join(format!("{name}-{}{EXE_SUFFIX}", variant.name));
+    fs::create_dir_all(target_dir.join("bin"))
+        .and_then(|()| fs::copy(&built, &binary))
+        .map_err(|err| format!("cannot copy {}: {err}", built.display()))?;
+
+    Ok(binary)
+}
+
+/// Examples and puzzle input of `day`, then the inputs in `more`.
+fn inputs(root: &Path, day: u8, more: Option<&Path>) -> io::Result<Vec<PathBuf>> {
+    let files = |dir: &Path, filter: fn(&str) -> bool| -> io::Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if path.is_file() && filter(&name) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    };
+
+    let mut inputs = files(&day_dir(root, day), |name| {
+        name.starts_with("example") || name == "input"
+    })?;
+    if let Some(more) = more {
+        let dir = more.join(format!("day{day:02}"));
+        if dir.is_dir() {
+            inputs.extend(files(&dir, |_| true)?);
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Write `count` random inputs of `day` generated from `seed` in the
+/// `dayNN` subdirectory of `dir`, the layout and names of `inputgen`.
+fn generate(dir: &Path, day: u8, count: usize, scale: f64, seed: u64) -> io::Result<Vec<PathBuf>> {
+    let generator = &inputgen::DAYS[usize::from(day) - 1];
+    let size = generator.scaled(scale);
+    let dir = dir.join(format!("day{day:02}"));
+    fs::create_dir_all(&dir)?;
+
+    let mut rng = day_rng(seed, day);
+    let mut inputs = vec![];
+    for i in 0..count {
+        let path = dir.join(format!("random-{seed}-{i}"));
+        fs::write(&path, (generator.generate)(size, &mut rng))?;
+        inputs.push(path);
+    }
+
+    Ok(inputs)
+}
+
+/// What `binary` answers on `input`: its output without the timings,
+/// followed by the panic message if it fails.
+fn run(binary: &Path, input: &Path) -> String {
+    let output = match Command::new(binary).env(aoc::input::ENV, input).output() {
+        Ok(output) => output,
+        Err(err) => return format!("cannot run: {err}"),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut outcome = String::new();
+    for line in stdout.lines().filter(|line| !line.starts_with("elapsed:")) {
+        writeln!(outcome, "{line}").unwrap();
+    }
+
+    if !output.status.success() {
+        // the message follows the line naming the thread, that differs
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut lines = stderr.lines();
+        let message = lines
+            .by_ref()
+            .find(|line| line.contains(" panicked at "))
+            .and_then(|_| lines.next())
+            .unwrap_or(stderr.trim_end());
+        writeln!(outcome, "failed: {message}").unwrap();
+    }
+
+    outcome
+}
+
+/// Distinct outcomes of `binaries` on `input`, with the names of the
+/// binaries giving each of them, in the order of `binaries`.
+fn outcomes<'a>(binaries: &[(&'a str, PathBuf)], input: &Path) -> Vec<(String, Vec<&'a str>)> {
+    let mut outcomes: Vec<(String, Vec<&str>)> = vec![];
+    for (name, binary) in binaries {
+        let outcome = run(binary, input);
+        match outcomes.iter_mut().find(|(other, _)| *other == outcome) {
+            Some((_, names)) => names.push(name),
+            None => outcomes.push((outcome, vec![name])),
+        }
+    }
+    outcomes
+}
+
+/// Check the variants of a day, returns the number of problems found.
+fn check(
+    cli: &Cli,
+    root: &Path,
+    target_dir: &Path,
+    seed: u64,
+    day: &DayVariants,
+) -> io::Result<usize> {
+    let variants = day
+        .variants
+        .iter()
+        .filter(|variant| cli.nightly || !variant.nightly)
+        .collect::<Vec<_>>();
+
+    let names = variants.iter().map(|variant| variant.name);
+    println!(
+        "day {:02}: {}",
+        day.day,
+        names.collect::<Vec<_>>().join(", ")
+    );
+
+    let mut problems = 0;
+
+    let mut binaries = vec![];
+    for variant in variants {
+        match build(root, target_dir, day.day, variant) {
+            Ok(binary) => binaries.push((variant.name, binary)),
+            Err(err) => {
+                println!("  {}: {err}", variant.name);
+                problems += 1;
+            }
+        }
+    }
+
+    let mut inputs = inputs(root, day.day, cli.inputs.as_deref())?;
+    inputs.extend(generate(
+        &target_dir.join("inputs"),
+        day.day,
+        cli.count,
+        cli.scale,
+        seed,
+    )?);
+
+    for input in inputs {
+        let outcomes = outcomes(&binaries, &input);
+        let input = input
+            .strip_prefix(root)
+            .or_else(|_| input.strip_prefix(target_dir))
+            .unwrap_or(&input);
+        if outcomes.len() <= 1 {
+            println!("  {}: agree", input.display());
+        } else {
+            println!("  {}: DISAGREE", input.display());
+            for (outcome, names) in outcomes {
+                println!("    {}:", names.join(", "));
+                for line in outcome.lines() {
+                    println!("      {line}");
+                }
+            }
+            problems += 1;
+        }
+    }
+
+    Ok(problems)
+}
+
+fn difftest(cli: &Cli) -> Result<usize, Box<dyn Error>> {
+    let root = fs::canonicalize(ROOT)?;
+    let target_dir = cli.target_dir.clone().unwrap_or_else(|| {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("variants")
+    });
+
+    if let Some(day) = cli
+        .day
+        .iter()
+        .find(|&&day| DAYS.iter().all(|days| days.day != day))
+    {
+        return Err(format!("day {day} has no variants").into());
+    }
+    if !cli.scale.is_finite() || cli.scale <= 0. {
+        return Err(format!("invalid scale {}, must be positive", cli.scale).into());
+    }
+
+    let seed = cli.seed.unwrap_or_else(rand::random);
+    println!("seed: {seed}");
+
+    let mut problems = 0;
+    for day in DAYS
+        .iter()
+        .filter(|days| cli.day.is_empty() || cli.day.contains(&days.day))
+    {
+        problems += check(cli, &root, &target_dir, seed, day)?;
+    }
+
+    Ok(problems)
+}
+
+fn main() -> ExitCode {
+    match difftest(&Cli::parse()) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(problems) => {
+            eprintln!("error: {problems} problems found");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-difftest-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn inputs_of_day() {
+        let root = temp_dir("inputs");
+        let day = day_dir(&root, 7);
+        fs::create_dir_all(day.join("rs")).unwrap();
+        for name in ["input", "example2", "example", "README.md", "input.txt"] {
+            fs::write(day.join(name), "").unwrap();
+        }
+        let more = root.join("more");
+        fs::create_dir_all(more.join("day07")).unwrap();
+        fs::create_dir_all(more.join("day08")).unwrap();
+        fs::write(more.join("day07").join("large"), "").unwrap();
+        fs::write(more.join("day07").join("deep"), "").unwrap();
+        fs::write(more.join("day08").join("other"), "").unwrap();
+
+        let found = inputs(&root, 7, None);
+        let found_more = inputs(&root, 7, Some(&more));
+        let missing = inputs(&root, 9, None);
+        fs::remove_dir_all(&root).unwrap();
+
+        let names = |inputs: Vec<PathBuf>| {
+            inputs
+                .iter()
+                .map(|input| input.strip_prefix(&root).unwrap().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+        let paths = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+        assert_eq!(
+            names(found.unwrap()),
+            paths(&["day07/example", "day07/example2", "day07/input"])
+        );
+        assert_eq!(
+            names(found_more.unwrap()),
+            paths(&[
+                "day07/example",
+                "day07/example2",
+                "day07/input",
+                "more/day07/deep",
+                "more/day07/large",
+            ])
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let dir = temp_dir("generate");
+
+        let first = generate(&dir.join("first"), 12, 3, 0.1, 42).unwrap();
+        let again = generate(&dir.join("again"), 12, 3, 0.1, 42).unwrap();
+        let other = generate(&dir.join("other"), 12, 1, 0.1, 43).unwrap();
+        let read = |inputs: &[PathBuf]| {
+            inputs
+                .iter()
+                .map(|input| fs::read_to_string(input).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let (first_inputs, again_inputs, other_inputs) = (read(&first), read(&again), read(&other));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names = first
+            .iter()
+            .map(|input| input.strip_prefix(&dir).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["random-42-0", "random-42-1", "random-42-2"]
+                .map(|name| Path::new("first/day12").join(name))
+        );
+        assert_eq!(first_inputs, again_inputs);
+        assert_ne!(first_inputs[0], first_inputs[1]);
+        assert_ne!(first_inputs[0], other_inputs[0]);
+    }
+
+    #[test]
+    fn build_failed() {
+        let root = temp_dir("build");
+        let variant = Variant {
+            name: "missing",
+            features: &[],
+            nightly: false,
+        };
+
+        let built = build(&root, &root.join("target"), 7, &variant);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(built.unwrap_err().starts_with("build failed"));
+    }
+
+    /// Shell script named `name` in `dir`, running `script`.
+    #[cfg(unix)]
+    fn script(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_outcome() {
+        let dir = temp_dir("run");
+        let input = dir.join("input");
+        fs::write(&input, "42\n").unwrap();
+        let answer = script(
+            &dir,
+            "answer",
+            "echo \"part 1: $(cat \"$AOC_INPUT\")\"\necho 'elapsed: 1ms'\necho 'part 2: 7'\n",
+        );
+        let panic = script(
+            &dir,
+            "panic",
+            "echo 'part 1: 1'\necho \"thread 'main' panicked at src/lib.rs:1:1:\" >&2\necho 'invalid input' >&2\nexit 101\n",
+        );
+        let fail = script(&dir, "fail", "echo 'no panic' >&2\nexit 1\n");
+
+        let outcomes = [
+            run(&answer, &input),
+            run(&panic, &input),
+            run(&fail, &input),
+            run(&dir.join("missing"), &input),
+        ];
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(outcomes[0], "part 1: 42\npart 2: 7\n");
+        assert_eq!(outcomes[1], "part 1: 1\nfailed: invalid input\n");
+        assert_eq!(outcomes[2], "failed: no panic\n");
+        assert!(outcomes[3].starts_with("cannot run: "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn outcomes_agree_or_not() {
+        let dir = temp_dir("outcomes");
+        let input = dir.join("input");
+        fs::write(&input, "").unwrap();
+        let binaries = [
+            ("first", script(&dir, "first", "echo 'part 1: 1'\n")),
+            ("second", script(&dir, "second", "echo 'part 1: 2'\n")),
+            (
+                "timed",
+                script(&dir, "timed", "echo 'part 1: 1'\necho 'elapsed: 2ms'\n"),
+            ),
+        ];
+
+        let agree = outcomes(&[binaries[0].clone(), binaries[2].clone()], &input);
+        let disagree = outcomes(&binaries, &input);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(agree, [("part 1: 1\n".to_string(), vec!["first", "timed"])]);
+        assert_eq!(
+            disagree,
+            [
+                ("part 1: 1\n".to_string(), vec!["first", "timed"]),
+                ("part 1: 2\n".to_string(), vec!["second"]),
+            ]
+        );
+    }
+}
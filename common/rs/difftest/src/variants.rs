@@ -0,0 +1,94 @@
+/// Build of a day crate with a set of features.
+pub struct Variant {
+    pub name: &'static str,
+    /// Features enabled, on top of none of the default ones.
+    pub features: &'static [&'static str],
+    /// Whether the variant needs the nightly toolchain.
+    pub nightly: bool,
+}
+
+/// Days with more than one solver variant.
+pub struct DayVariants {
+    pub day: u8,
+    pub variants: &'static [Variant],
+}
+
+const fn variant(name: &'static str, features: &'static [&'static str]) -> Variant {
+    Variant {
+        name,
+        features,
+        nightly: false,
+    }
+}
+
+const fn nightly(name: &'static str, features: &'static [&'static str]) -> Variant {
+    Variant {
+        name,
+        features,
+        nightly: true,
+    }
+}
+
+pub static DAYS: &[DayVariants] = &[
+    DayVariants {
+        day: 5,
+        variants: &[variant("rayon", &["rayon"]), variant("sequential", &[])],
+    },
+    DayVariants {
+        day: 8,
+        variants: &[
+            variant("rayon", &["rayon"]),
+            variant("sequential", &[]),
+            // mutually exclusive with rayon
+            nightly("simd", &["simd"]),
+        ],
+    },
+    DayVariants {
+        day: 9,
+        variants: &[variant("rayon", &["rayon"]), variant("sequential", &[])],
+    },
+    DayVariants {
+        day: 12,
+        variants: &[
+            variant("recursion-rayon", &["recursion", "rayon"]),
+            variant("recursion", &["recursion"]),
+            variant("norecursion-rayon", &["rayon"]),
+            variant("norecursion", &[]),
+        ],
+    },
+    DayVariants {
+        day: 13,
+        variants: &[variant("sequential", &[]), variant("rayon", &["rayon"])],
+    },
+    DayVariants {
+        day: 14,
+        // simd1 only fits the examples, simd2 fits the examples and the
+        // puzzle input, the other maps spin with the simple cycle
+        variants: &[
+            variant("simple", &[]),
+            nightly("simd1", &["simd1"]),
+            nightly("simd2", &["simd2"]),
+        ],
+    },
+    DayVariants {
+        day: 16,
+        variants: &[variant("rayon", &["rayon"]), variant("sequential", &[])],
+    },
+    DayVariants {
+        day: 22,
+        variants: &[
+            variant("rayon", &["rayon"]),
+            variant("sequential", &[]),
+            variant("spinlock-rayon", &["spinlock", "rayon"]),
+            variant("spinlock", &["spinlock"]),
+        ],
+    },
+    DayVariants {
+        day: 23,
+        variants: &[variant("fast", &["fast"]), variant("simple", &[])],
+    },
+    DayVariants {
+        day: 24,
+        variants: &[variant("z3", &["z3"]), variant("simple", &[])],
+    },
+];
@@ -0,0 +1,54 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+
+use aoc::{Part, Puzzle};
+use inputgen::DAYS;
+
+static PUZZLES: [&dyn Puzzle; 25] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    for (generator, puzzle) in DAYS.iter().zip(PUZZLES) {
+        let mut group = c.benchmark_group(format!("day{:02}", puzzle.day()));
+        group.sample_size(10);
+        for size in [4, 2, 1].map(|divisor| (generator.puzzle_size / divisor).max(1)) {
+            let input = (generator.generate)(size, &mut StdRng::seed_from_u64(0));
+            for part in Part::ALL {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("part {part}"), size),
+                    &input,
+                    |b, input| b.iter(|| puzzle.solve(part, input)),
+                );
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+//! Calibration document, lines of letters with digits and digits spelled
+//! out.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub struct Calibrations {
+    pub lines: usize,
+    /// Maximum number of letters, digits and spelled digits of a line.
+    pub max_tokens: usize,
+}
+
+impl Generator for Calibrations {
+    const PUZZLE_SIZE: usize = 1000;
+
+    fn sized(lines: usize) -> Self {
+        Self {
+            lines,
+            max_tokens: 30,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        lines((0..self.lines).map(|_| {
+            let tokens = rng.gen_range(1..=self.max_tokens.max(1));
+            // part 1 needs a digit on every line
+            let digit = rng.gen_range(0..tokens);
+            (0..tokens)
+                .map(|i| match rng.gen_range(0..10) {
+                    _ if i == digit => rng.gen_range(1..=9).to_string(),
+                    0 => rng.gen_range(1..=9).to_string(),
+                    1 => WORDS.choose(rng).unwrap().to_string(),
+                    _ => char::from(rng.gen_range(b'a'..=b'z')).to_string(),
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for size in [1, 10, 100] {
+            let input = Calibrations::sized(size).generate(&mut rng);
+            solvable(&day01::Day01, &input);
+        }
+    }
+}
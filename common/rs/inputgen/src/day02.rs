@@ -0,0 +1,57 @@
+//! Games of cubes drawn from a bag.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator};
+
+pub struct Games {
+    pub games: usize,
+    pub max_sets: usize,
+    pub max_cubes: u32,
+}
+
+impl Generator for Games {
+    const PUZZLE_SIZE: usize = 100;
+
+    fn sized(games: usize) -> Self {
+        Self {
+            games,
+            max_sets: 6,
+            max_cubes: 20,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        lines((1..=self.games).map(|id| {
+            let sets = (0..rng.gen_range(1..=self.max_sets.max(1)))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    colors[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.gen_range(1..=self.max_cubes)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>();
+            format!("Game {id}: {}", sets.join("; "))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for size in [1, 10, 100] {
+            let input = Games::sized(size).generate(&mut rng);
+            solvable(&day02::Day02, &input);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Engine schematic, a map of part numbers and symbols.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator};
+
+const SYMBOLS: &[u8] = b"*#+$/@%=-&";
+
+pub struct Schematic {
+    pub side: usize,
+    /// Probability of a number starting on a tile.
+    pub numbers: f64,
+    /// Probability of a symbol on a tile.
+    pub symbols: f64,
+    /// Probability of a symbol being a gear, `*`.
+    pub gears: f64,
+}
+
+impl Generator for Schematic {
+    const PUZZLE_SIZE: usize = 140;
+
+    fn sized(side: usize) -> Self {
+        Self {
+            side,
+            numbers: 0.1,
+            symbols: 0.05,
+            gears: 0.3,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let side = self.side.max(1);
+        lines((0..side).map(|_| {
+            let mut row = Vec::with_capacity(side);
+            while row.len() < side {
+                if rng.gen_bool(self.numbers) {
+                    let digits = rng.gen_range(1..=3).min(side - row.len());
+                    row.extend(rng.gen_range(1..1000_u32).to_string().bytes().take(digits));
+                    if row.len() < side {
+                        row.push(b'.');
+                    }
+                } else if rng.gen_bool(self.symbols) {
+                    row.push(if rng.gen_bool(self.gears) {
+                        b'*'
+                    } else {
+                        *SYMBOLS.choose(rng).unwrap()
+                    });
+                } else {
+                    row.push(b'.');
+                }
+            }
+            String::from_utf8(row).unwrap()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for size in [1, 10, 100] {
+            let input = Schematic::sized(size).generate(&mut rng);
+            assert_eq!(input.lines().count(), size);
+            assert!(input.lines().all(|line| line.len() == size));
+            solvable(&day03::Day03, &input);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Scratchcards, with winning numbers and numbers you have.
+
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+use crate::{lines, Generator};
+
+pub struct Scratchcards {
+    pub cards: usize,
+    pub winning: usize,
+    pub numbers: usize,
+}
+
+impl Generator for Scratchcards {
+    const PUZZLE_SIZE: usize = 200;
+
+    fn sized(cards: usize) -> Self {
+        Self {
+            cards,
+            winning: 10,
+            numbers: 25,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let list = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        // copies of each card, kept low enough that the total fits in `u32`
+        let limit = usize::try_from(u32::MAX).unwrap() / self.cards.max(1);
+        let mut copies = vec![1; self.cards];
+
+        lines((1..=self.cards).map(|id| {
+            let winning = index::sample(rng, 99, self.winning.min(99))
+                .into_iter()
+                .map(|number| number + 1)
+                .collect::<Vec<_>>();
+            // draw some of the winning numbers, the others at random, never
+            // winning cards past the end of the table
+            let max = copies[id..]
+                .iter()
+                .take_while(|&&copy| copy + copies[id - 1] <= limit)
+                .count()
+                .min(winning.len())
+                .min(self.numbers);
+            let matches = rng.gen_range(0..=max);
+            let count = copies[id - 1];
+            for copy in &mut copies[id..id + matches] {
+                *copy += count;
+            }
+            let mut numbers = winning[..matches].to_vec();
+            let len = self.numbers.min(matches + 99 - winning.len());
+            while numbers.len() < len {
+                let number = rng.gen_range(1..=99);
+                if !winning.contains(&number) && !numbers.contains(&number) {
+                    numbers.push(number);
+                }
+            }
+            numbers.shuffle(rng);
+            format!("Card {id:>3}: {} | {}", list(&winning), list(&numbers))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for size in [1, 10, 100] {
+            let input = Scratchcards::sized(size).generate(&mut rng);
+            solvable(&day04::Day04, &input);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Almanac, seeds and the maps from seeds to locations.
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::Generator;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+pub struct Almanac {
+    /// Ranges of each map.
+    pub entries: usize,
+    /// Pairs of seed numbers, ranges of seeds for part 2.
+    pub seeds: usize,
+    /// End of the numbers, exclusive.
+    pub max: u64,
+}
+
+impl Generator for Almanac {
+    const PUZZLE_SIZE: usize = 40;
+
+    fn sized(entries: usize) -> Self {
+        Self {
+            entries,
+            seeds: 10,
+            max: 1 << 32,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let max = self.max.max(2);
+
+        let seeds = (0..self.seeds.max(1))
+            .flat_map(|_| {
+                let start = rng.gen_range(0..max - 1);
+                [start, rng.gen_range(1..=(max - start).min(max / 10).max(1))]
+            })
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+        for map in MAPS {
+            // the sources split a range, the destinations shuffle the pieces
+            let mut cuts = (0..=self.entries)
+                .map(|_| rng.gen_range(0..max))
+                .collect::<Vec<_>>();
+            cuts.sort_unstable();
+            cuts.dedup();
+            let mut pieces = cuts.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>();
+            pieces.shuffle(rng);
+            let mut destination = cuts[0];
+            let mut entries = pieces
+                .into_iter()
+                .map(|(start, end)| {
+                    let entry = (destination, start, end - start);
+                    destination += end - start;
+                    entry
+                })
+                .collect::<Vec<_>>();
+            entries.shuffle(rng);
+
+            writeln!(input, "\n{map} map:").unwrap();
+            for (destination, source, length) in entries {
+                writeln!(input, "{destination} {source} {length}").unwrap();
+            }
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for size in [1, 10, 100] {
+            let input = Almanac::sized(size).generate(&mut rng);
+            solvable(&day05::Day05, &input);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Boat races, times and record distances.
+
+use rand::Rng;
+
+use crate::Generator;
+
+pub struct Races {
+    /// Races, at most 9 so that the single race of part 2 fits in `u64`.
+    pub races: usize,
+}
+
+impl Generator for Races {
+    const PUZZLE_SIZE: usize = 4;
+
+    fn sized(races: usize) -> Self {
+        Self {
+            races: races.clamp(1, 9),
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let races = self.races.clamp(1, 9);
+        // the times of all races together have at most 9 digits
+        let digits = match races {
+            1..=3 => 3,
+            4 => 2,
+            _ => 1,
+        };
+        let record = |time: u64| time / 2 * (time - time / 2);
+
+        let times = (0..races)
+            .map(|_| rng.gen_range(10_u64.pow(digits - 1).max(5)..10_u64.pow(digits)))
+            .collect::<Vec<_>>();
+        // as in the puzzle, the distances are above the times, and all
+        // together they have fewer digits than the record of the single
+        // race, so that it can be beaten too
+        let time = times
+            .iter()
+            .map(u64::to_string)
+            .collect::<String>()
+            .parse::<u64>()
+            .unwrap();
+        let budget = (record(time).ilog10() / u32::try_from(races).unwrap()).min(18);
+        let distances = times
+            .iter()
+            .map(|&time| rng.gen_range(time..record(time).min(10_u64.pow(budget))))
+            .collect::<Vec<_>>();
+
+        let width = distances
+            .iter()
+            .chain(&times)
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap();
+        let row = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|n| format!("{n:>width$}"))
+                .collect::<Vec<_>>()
+                .join("   ")
+        };
+        format!(
+            "Time:      {}\nDistance:  {}\n",
+            row(&times),
+            row(&distances)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for size in 1..=9 {
+            let input = Races::sized(size).generate(&mut rng);
+            solvable(&day06::Day06, &input);
+        }
+    }
+}
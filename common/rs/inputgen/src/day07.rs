@@ -0,0 +1,78 @@
+//! Hands of Camel Cards and their bids.
+
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator};
+
+const CARDS: &[u8] = b"AKQJT98765432";
+
+pub struct Hands {
+    /// Hands, all distinct.
+    pub hands: usize,
+    pub max_bid: u32,
+    /// Cards the hands are dealt from, fewer cards make more pairs.
+    pub cards: &'static [u8],
+}
+
+impl Generator for Hands {
+    const PUZZLE_SIZE: usize = 1000;
+
+    fn sized(hands: usize) -> Self {
+        Self {
+            hands,
+            max_bid: 1000,
+            cards: CARDS,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let count = self.hands.min(self.cards.len().pow(5));
+
+        let mut hands = HashSet::with_capacity(count);
+        while hands.len() < count {
+            hands.insert(
+                (0..5)
+                    .map(|_| char::from(*self.cards.choose(rng).unwrap()))
+                    .collect::<String>(),
+            );
+        }
+
+        let mut hands = hands.into_iter().collect::<Vec<_>>();
+        hands.sort_unstable();
+        hands.shuffle(rng);
+        lines(
+            hands
+                .into_iter()
+                .map(|hand| format!("{hand} {}", rng.gen_range(1..=self.max_bid))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for size in [1, 10, 1000] {
+            let input = Hands::sized(size).generate(&mut rng);
+            solvable(&day07::Day07, &input);
+        }
+    }
+
+    #[test]
+    fn few_cards() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let hands = Hands {
+            cards: b"AJ2",
+            ..Hands::sized(1000)
+        };
+        assert_eq!(hands.generate(&mut rng).lines().count(), 3_usize.pow(5));
+    }
+}
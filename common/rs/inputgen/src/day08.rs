@@ -0,0 +1,114 @@
+//! Network of nodes walked left and right by ghosts.
+//!
+//! Every ghost walks a cycle of layers, where the node of each layer
+//! depends on the directions but the layer does not, so that the ghost
+//! reaches its `Z` node after a prime number of steps, and then again
+//! every as many steps. The answers are that prime for the first ghost
+//! and the product of the primes for all of them.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, names, primes, Generator, Planted};
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub struct Network {
+    /// Ghosts, at most 8 so that the answer of part 2 fits in `u64`.
+    pub ghosts: usize,
+    pub max_path: usize,
+    /// Range of the primes of the cycles.
+    pub cycles: std::ops::Range<u64>,
+}
+
+impl Network {
+    /// Random network, with the steps of both parts planted.
+    ///
+    /// # Panics
+    /// Panic if there are fewer primes in `cycles` than ghosts
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let ghosts = self.ghosts.clamp(1, 8);
+
+        let mut cycles = primes(self.cycles.start.max(2)..self.cycles.end);
+        assert!(cycles.len() >= ghosts, "not enough primes in cycles");
+        cycles.shuffle(rng);
+        cycles.truncate(ghosts);
+
+        let path = (0..rng.gen_range(1..=self.max_path.max(1)))
+            .map(|_| if rng.gen() { 'L' } else { 'R' })
+            .collect::<String>();
+
+        let layers = cycles.iter().map(|&cycle| 2 * (cycle - 1)).sum::<u64>();
+        let mut layer_names = names(
+            rng,
+            usize::try_from(layers).unwrap(),
+            3,
+            &LETTERS[1..LETTERS.len() - 1],
+            &[],
+        )
+        .into_iter();
+        let prefixes = names(rng, ghosts - 1, 2, LETTERS, &["AA", "ZZ"]);
+
+        let mut nodes = vec![];
+        for (i, &cycle) in cycles.iter().enumerate() {
+            let (start, end) = if i == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            } else {
+                (
+                    format!("{}A", prefixes[i - 1]),
+                    format!("{}Z", prefixes[i - 1]),
+                )
+            };
+            let layers = (1..cycle)
+                .map(|_| (layer_names.next().unwrap(), layer_names.next().unwrap()))
+                .collect::<Vec<_>>();
+
+            let (first, second) = layers.first().map_or((&end, &end), |(p, q)| (p, q));
+            nodes.push(format!("{start} = ({first}, {second})"));
+            nodes.push(format!("{end} = ({first}, {second})"));
+            for (j, (p, q)) in layers.iter().enumerate() {
+                let (next_p, next_q) = layers.get(j + 1).map_or((&end, &end), |(p, q)| (p, q));
+                nodes.push(format!("{p} = ({next_p}, {next_q})"));
+                nodes.push(format!("{q} = ({next_q}, {next_p})"));
+            }
+        }
+        nodes.shuffle(rng);
+
+        Planted {
+            input: format!("{path}\n\n{}", lines(nodes)),
+            part_1: Some(cycles[0]),
+            part_2: Some(cycles.iter().product()),
+        }
+    }
+}
+
+impl Generator for Network {
+    const PUZZLE_SIZE: usize = 6;
+
+    fn sized(ghosts: usize) -> Self {
+        Self {
+            ghosts: ghosts.clamp(1, 8),
+            max_path: 300,
+            cycles: 20..200,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for size in 1..=8 {
+            let planted = Network::sized(size).planted(&mut rng);
+            planted.check(&day08::Day08);
+        }
+    }
+}
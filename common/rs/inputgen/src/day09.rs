@@ -0,0 +1,78 @@
+//! Histories of values, each following a polynomial.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct Histories {
+    pub histories: usize,
+    pub values: usize,
+    pub max_degree: usize,
+    /// Largest absolute value of the differences at the start.
+    pub max_difference: i64,
+}
+
+impl Generator for Histories {
+    const PUZZLE_SIZE: usize = 200;
+
+    fn sized(histories: usize) -> Self {
+        Self {
+            histories,
+            values: 21,
+            max_degree: 20,
+            max_difference: 5,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let values = self.values.max(1);
+        lines((0..self.histories).map(|_| {
+            // first value of every row of differences, down to a constant
+            let degree = rng.gen_range(0..=self.max_degree.min(values - 1));
+            let mut differences = (0..=degree)
+                .map(|_| rng.gen_range(-self.max_difference..=self.max_difference))
+                .collect::<Vec<_>>();
+            let mut history = Vec::with_capacity(values);
+            for _ in 0..values {
+                history.push(differences[0].to_string());
+                for i in 0..degree {
+                    differences[i] += differences[i + 1];
+                }
+            }
+            history.join(" ")
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for size in [1, 10, 100] {
+            let input = Histories::sized(size).generate(&mut rng);
+            solvable(&day09::Day09, &input);
+        }
+    }
+
+    #[test]
+    fn constant() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let histories = Histories {
+            max_degree: 0,
+            ..Histories::sized(10)
+        };
+        let input = histories.generate(&mut rng);
+        let first = input
+            .lines()
+            .map(|line| line.split(' ').next().unwrap().parse::<i64>().unwrap())
+            .sum::<i64>();
+        assert_eq!(day09::try_solve_1(&input).unwrap(), first);
+        assert_eq!(day09::try_solve_2(&input).unwrap(), first);
+    }
+}
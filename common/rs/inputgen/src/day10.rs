@@ -0,0 +1,135 @@
+//! Field of pipes with a loop through the start tile `S`.
+//!
+//! The loop follows the boundary of a random [`Polyomino`], drawn at
+//! double scale so that its corners land on even tiles. The loop has
+//! twice as many tiles as the boundary has steps, and Pick's theorem
+//! gives the tiles it encloses.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::shape::{Direction, Polyomino};
+use crate::{lines, Generator, Planted};
+
+const PIPES: &[u8] = b"|-LJ7F";
+
+pub struct Pipes {
+    pub side: usize,
+    /// Part of the field inside the loop.
+    pub fill: f64,
+    /// Probability of a pipe on a tile out of the loop.
+    pub junk: f64,
+}
+
+impl Pipes {
+    /// Random loop, with its farthest point and enclosed tiles planted.
+    ///
+    /// # Panics
+    /// Panic if `junk` is not a probability
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let side = self.side.max(3);
+        let cells = (side - 1) / 2;
+        let polyomino = Polyomino::random(rng, cells, cells, self.fill);
+        let boundary = polyomino.boundary();
+
+        let mut tiles = (0..side)
+            .map(|_| {
+                (0..side)
+                    .map(|_| {
+                        if rng.gen_bool(self.junk) {
+                            *PIPES.choose(rng).unwrap()
+                        } else {
+                            b'.'
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut pipes = Vec::with_capacity(2 * boundary.len());
+        let mut from = boundary.last().unwrap().1;
+        for &((x, y), direction) in &boundary {
+            let corner = (2 * x, 2 * y);
+            pipes.push((corner, pipe(from.opposite(), direction)));
+            let (mx, my) = direction.step(corner);
+            pipes.push(((mx, my), pipe(direction.opposite(), direction)));
+            from = direction;
+        }
+        for &((x, y), pipe) in &pipes {
+            tiles[y][x] = pipe;
+        }
+
+        // only the loop connects to the start
+        let &((x, y), _) = pipes.choose(rng).unwrap();
+        tiles[y][x] = b'S';
+        for direction in [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            if (direction == Direction::Up && y == 0) || (direction == Direction::Left && x == 0) {
+                continue;
+            }
+            let (nx, ny) = direction.step((x, y));
+            if nx < side && ny < side && !pipes.iter().any(|&(tile, _)| tile == (nx, ny)) {
+                tiles[ny][nx] = b'.';
+            }
+        }
+
+        let steps = boundary.len() as u64;
+        Planted {
+            input: lines(tiles.into_iter().map(|row| String::from_utf8(row).unwrap())),
+            part_1: Some(steps),
+            part_2: Some(4 * polyomino.cells().count() as u64 - steps + 1),
+        }
+    }
+}
+
+/// Pipe connecting the sides `a` and `b` of a tile.
+fn pipe(a: Direction, b: Direction) -> u8 {
+    use Direction::{Down, Left, Right, Up};
+
+    match (a, b) {
+        (Up, Down) | (Down, Up) => b'|',
+        (Left, Right) | (Right, Left) => b'-',
+        (Up, Right) | (Right, Up) => b'L',
+        (Up, Left) | (Left, Up) => b'J',
+        (Down, Left) | (Left, Down) => b'7',
+        (Down, Right) | (Right, Down) => b'F',
+        _ => unreachable!("no pipe turns back"),
+    }
+}
+
+impl Generator for Pipes {
+    const PUZZLE_SIZE: usize = 140;
+
+    fn sized(side: usize) -> Self {
+        Self {
+            side,
+            fill: 0.4,
+            junk: 0.6,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(10);
+        for size in [3, 4, 10, 20, 50] {
+            for _ in 0..10 {
+                let planted = Pipes::sized(size).planted(&mut rng);
+                planted.check(&day10::Day10);
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Image of galaxies, with empty rows and columns to expand.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct Image {
+    pub side: usize,
+    /// Probability of a galaxy on a tile.
+    pub galaxies: f64,
+    /// Probability of a row, or column, without galaxies.
+    pub empty: f64,
+}
+
+impl Generator for Image {
+    const PUZZLE_SIZE: usize = 140;
+
+    fn sized(side: usize) -> Self {
+        Self {
+            side,
+            galaxies: 0.03,
+            empty: 0.05,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let side = self.side.max(1);
+        let empty_rows = (0..side)
+            .map(|_| rng.gen_bool(self.empty))
+            .collect::<Vec<_>>();
+        let empty_columns = (0..side)
+            .map(|_| rng.gen_bool(self.empty))
+            .collect::<Vec<_>>();
+
+        lines(empty_rows.iter().map(|&empty_row| {
+            empty_columns
+                .iter()
+                .map(|&empty_column| {
+                    if !empty_row && !empty_column && rng.gen_bool(self.galaxies) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for size in [1, 10, 100] {
+            let input = Image::sized(size).generate(&mut rng);
+            solvable(&day11::Day11, &input);
+        }
+    }
+}
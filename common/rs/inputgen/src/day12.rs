@@ -0,0 +1,85 @@
+//! Condition records of springs, with the groups of damaged springs.
+//!
+//! Each row is drawn first, so its groups are known, then some springs
+//! are hidden as unknown, so that every row has an arrangement.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct Records {
+    pub rows: usize,
+    pub max_springs: usize,
+    /// Probability of a spring being damaged.
+    pub damaged: f64,
+    /// Probability of a spring being unknown in the record.
+    pub unknown: f64,
+}
+
+impl Generator for Records {
+    const PUZZLE_SIZE: usize = 1000;
+
+    fn sized(rows: usize) -> Self {
+        Self {
+            rows,
+            max_springs: 20,
+            damaged: 0.5,
+            unknown: 0.5,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        lines((0..self.rows).map(|_| {
+            let len = rng.gen_range(1..=self.max_springs.max(1));
+            let mut springs = (0..len)
+                .map(|_| rng.gen_bool(self.damaged))
+                .collect::<Vec<_>>();
+            // the record needs a group
+            springs[rng.gen_range(0..len)] = true;
+
+            let groups = springs
+                .split(|&damaged| !damaged)
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect::<Vec<_>>();
+            let record = springs
+                .iter()
+                .map(|&damaged| match (rng.gen_bool(self.unknown), damaged) {
+                    (true, _) => '?',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                })
+                .collect::<String>();
+
+            format!("{record} {}", groups.join(","))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn arrangements() {
+        let mut rng = StdRng::seed_from_u64(12);
+        for size in [1, 10, 100] {
+            let input = Records::sized(size).generate(&mut rng);
+            assert!(day12::try_solve_1(&input).unwrap() >= size as u64);
+            assert!(day12::try_solve_2(&input).unwrap() >= size as u64);
+        }
+    }
+
+    #[test]
+    fn known() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let records = Records {
+            unknown: 0.,
+            ..Records::sized(100)
+        };
+        let input = records.generate(&mut rng);
+        assert_eq!(day12::try_solve_1(&input), Ok(100));
+    }
+}
@@ -0,0 +1,160 @@
+//! Patterns of ash and rocks, each with a line of reflection, and one
+//! more line once a smudge is cleaned.
+//!
+//! The pattern is mirrored exactly across one line, and across a line
+//! of the other orientation but for one tile, the smudge, that is far
+//! enough from the first line to leave its reflection alone. Patterns
+//! with any other line of reflection are drawn again.
+
+use rand::Rng;
+
+use crate::{Generator, Planted};
+
+pub struct Patterns {
+    pub patterns: usize,
+    /// Range of the rows and of the columns of a pattern.
+    pub sides: std::ops::RangeInclusive<usize>,
+}
+
+impl Patterns {
+    /// Random patterns, with the summaries of both parts planted.
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let (mut part_1, mut part_2) = (0, 0);
+        let patterns = (0..self.patterns)
+            .map(|_| {
+                let (pattern, mirror, smudged_mirror) = self.pattern(rng);
+                part_1 += mirror;
+                part_2 += smudged_mirror;
+                pattern
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|&rock| if rock { '#' } else { '.' })
+                            .chain(['\n'])
+                            .collect::<String>()
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Planted {
+            input: patterns.join("\n"),
+            part_1: Some(part_1),
+            part_2: Some(part_2),
+        }
+    }
+
+    /// Pattern, with the summaries of its line and of its smudged line.
+    fn pattern<R: Rng + ?Sized>(&self, rng: &mut R) -> (Vec<Vec<bool>>, u64, u64) {
+        let sides = *self.sides.start().max(&3)..=*self.sides.end().max(&3);
+        loop {
+            let (ncols, nrows) = (rng.gen_range(sides.clone()), rng.gen_range(sides.clone()));
+            let mut pattern = (0..nrows)
+                .map(|_| (0..ncols).map(|_| rng.gen::<bool>()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            // exact vertical line, off the middle to leave room for the smudge
+            let column = rng.gen_range(1..ncols);
+            let width = column.min(ncols - column);
+            if 2 * width == ncols {
+                continue;
+            }
+            for row in &mut pattern {
+                for k in 0..width {
+                    row[column + k] = row[column - 1 - k];
+                }
+            }
+
+            let row = rng.gen_range(1..nrows);
+            let height = row.min(nrows - row);
+            let (above, below) = pattern.split_at_mut(row);
+            for (mirrored, original) in below.iter_mut().zip(above.iter().rev()) {
+                mirrored.clone_from(original);
+            }
+
+            let x = if column < ncols - column {
+                rng.gen_range(2 * column..ncols)
+            } else {
+                rng.gen_range(0..ncols - 2 * width)
+            };
+            let y = rng.gen_range(row - height..row + height);
+            pattern[y][x] = !pattern[y][x];
+
+            let vertical = (1..ncols)
+                .map(|c| {
+                    (0..c.min(ncols - c))
+                        .map(|k| {
+                            pattern
+                                .iter()
+                                .filter(|line| line[c - 1 - k] != line[c + k])
+                                .count()
+                        })
+                        .sum::<usize>()
+                })
+                .collect::<Vec<_>>();
+            let horizontal = (1..nrows)
+                .map(|r| {
+                    (0..r.min(nrows - r))
+                        .map(|k| {
+                            pattern[r - 1 - k]
+                                .iter()
+                                .zip(&pattern[r + k])
+                                .filter(|(a, b)| a != b)
+                                .count()
+                        })
+                        .sum::<usize>()
+                })
+                .collect::<Vec<_>>();
+            let count = |mismatches| {
+                vertical
+                    .iter()
+                    .chain(&horizontal)
+                    .filter(|&&m| m == mismatches)
+                    .count()
+            };
+            if count(0) != 1 || count(1) != 1 {
+                continue;
+            }
+
+            let (column, row) = (column as u64, row as u64);
+            if rng.gen() {
+                break (pattern, column, 100 * row);
+            }
+            let transposed = (0..ncols)
+                .map(|c| pattern.iter().map(|line| line[c]).collect())
+                .collect();
+            break (transposed, 100 * column, row);
+        }
+    }
+}
+
+impl Generator for Patterns {
+    const PUZZLE_SIZE: usize = 100;
+
+    fn sized(patterns: usize) -> Self {
+        Self {
+            patterns,
+            sides: 5..=17,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(13);
+        for size in [1, 10, 100] {
+            let planted = Patterns::sized(size).planted(&mut rng);
+            planted.check(&day13::Day13);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Platform of rounded rocks, that roll, and cube rocks, that stay.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct Platform {
+    pub side: usize,
+    /// Probability of a rounded rock on a tile.
+    pub rounded: f64,
+    /// Probability of a cube rock on a tile.
+    pub cubes: f64,
+}
+
+impl Generator for Platform {
+    const PUZZLE_SIZE: usize = 100;
+
+    fn sized(side: usize) -> Self {
+        Self {
+            side,
+            rounded: 0.2,
+            cubes: 0.15,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let side = self.side.max(1);
+        lines((0..side).map(|_| {
+            (0..side)
+                .map(|_| {
+                    let tile = rng.gen::<f64>();
+                    if tile < self.rounded {
+                        'O'
+                    } else if tile < self.rounded + self.cubes {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(14);
+        for size in [1, 2, 10, 50] {
+            let input = Platform::sized(size).generate(&mut rng);
+            solvable(&day14::Day14, &input);
+        }
+    }
+}
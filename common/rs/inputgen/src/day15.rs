@@ -0,0 +1,65 @@
+//! Initialization sequence, steps putting lenses in and out of boxes.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{names, Generator};
+
+pub struct Sequence {
+    pub steps: usize,
+    /// Distinct labels, fewer labels change the same lenses more often.
+    pub labels: usize,
+    /// Probability of a step removing a lens.
+    pub remove: f64,
+}
+
+impl Generator for Sequence {
+    const PUZZLE_SIZE: usize = 4000;
+
+    fn sized(steps: usize) -> Self {
+        Self {
+            steps,
+            labels: steps / 8 + 1,
+            remove: 0.3,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        // labels of 2 to 6 letters
+        let labels = (2..=6_u32)
+            .flat_map(|len| {
+                let count = (self.labels / 5 + 1).min(26_usize.pow(len));
+                names(rng, count, len as usize, b"abcdefghijklmnopqrstuvwxyz", &[])
+            })
+            .collect::<Vec<_>>();
+
+        let steps = (0..self.steps.max(1))
+            .map(|_| {
+                let label = labels.choose(rng).unwrap();
+                if rng.gen_bool(self.remove) {
+                    format!("{label}-")
+                } else {
+                    format!("{label}={}", rng.gen_range(1..=9))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        steps.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(15);
+        for size in [1, 10, 1000] {
+            let input = Sequence::sized(size).generate(&mut rng);
+            solvable(&day15::Day15, &input);
+        }
+    }
+}
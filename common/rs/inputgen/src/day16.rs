@@ -0,0 +1,51 @@
+//! Contraption of mirrors and splitters, that bend and split the beam.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator};
+
+pub struct Contraption {
+    pub side: usize,
+    /// Probability of a mirror or a splitter on a tile.
+    pub devices: f64,
+}
+
+impl Generator for Contraption {
+    const PUZZLE_SIZE: usize = 110;
+
+    fn sized(side: usize) -> Self {
+        Self { side, devices: 0.1 }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let side = self.side.max(1);
+        lines((0..side).map(|_| {
+            (0..side)
+                .map(|_| {
+                    if rng.gen_bool(self.devices) {
+                        *['/', '\\', '-', '|'].choose(rng).unwrap()
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(16);
+        for size in [1, 10, 50] {
+            let input = Contraption::sized(size).generate(&mut rng);
+            solvable(&day16::Day16, &input);
+        }
+    }
+}
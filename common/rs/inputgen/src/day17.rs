@@ -0,0 +1,50 @@
+//! City blocks, each with the heat it loses.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct City {
+    pub side: usize,
+    /// Largest heat loss of a block, from 1 to 9.
+    pub max_heat_loss: u8,
+}
+
+impl Generator for City {
+    const PUZZLE_SIZE: usize = 141;
+
+    fn sized(side: usize) -> Self {
+        Self {
+            side,
+            max_heat_loss: 9,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        // the ultra crucibles of part 2 need room to stop at the end
+        let side = self.side.max(5);
+        let max = self.max_heat_loss.clamp(1, 9);
+        lines((0..side).map(|_| {
+            (0..side)
+                .map(|_| char::from(b'0' + rng.gen_range(1..=max)))
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(17);
+        for size in [5, 10, 50] {
+            let input = City::sized(size).generate(&mut rng);
+            solvable(&day17::Day17, &input);
+        }
+    }
+}
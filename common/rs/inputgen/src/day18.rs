@@ -0,0 +1,125 @@
+//! Dig plan of a lagoon, with the steps of part 2 hidden in the colors.
+//!
+//! Both plans follow the boundary of the same random [`Polyomino`],
+//! with its columns and rows stretched by random widths: small for
+//! part 1, up to the five hex digits of the colors for part 2. The
+//! stretched cells give the area inside the boundary.
+
+use rand::Rng;
+
+use crate::shape::{Direction, Polyomino};
+use crate::{lines, Generator, Planted};
+
+pub struct DigPlan {
+    /// Columns and rows of the polyomino.
+    pub cells: usize,
+    /// Part of the polyomino grid inside the lagoon.
+    pub fill: f64,
+    /// Largest width of a column, or row, of part 1.
+    pub max_width: u64,
+}
+
+impl DigPlan {
+    /// Random dig plan, with the lagoon of both parts planted.
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let cells = self.cells.max(1);
+        let polyomino = Polyomino::random(rng, cells, cells, self.fill);
+
+        // the lengths of part 2 have 5 hex digits
+        let max_widths = [self.max_width.max(1), 0xf_ffff / cells as u64];
+        let widths = max_widths.map(|max| {
+            (0..cells)
+                .map(|_| rng.gen_range(1..=max))
+                .collect::<Vec<_>>()
+        });
+        let heights = max_widths.map(|max| {
+            (0..cells)
+                .map(|_| rng.gen_range(1..=max))
+                .collect::<Vec<_>>()
+        });
+
+        // merge the unit steps in the same direction
+        let mut segments: Vec<((usize, usize), Direction, usize)> = vec![];
+        for (vertex, direction) in polyomino.boundary() {
+            match segments.last_mut() {
+                Some((_, last, units)) if *last == direction => *units += 1,
+                _ => segments.push((vertex, direction, 1)),
+            }
+        }
+
+        let length =
+            |part: usize, ((x, y), direction, units): ((usize, usize), Direction, usize)| {
+                match direction {
+                    Direction::Right => widths[part][x..x + units].iter().sum::<u64>(),
+                    Direction::Left => widths[part][x - units..x].iter().sum(),
+                    Direction::Down => heights[part][y..y + units].iter().sum(),
+                    Direction::Up => heights[part][y - units..y].iter().sum(),
+                }
+            };
+        let plan = lines(segments.iter().map(|&segment| {
+            let (letter, digit) = match segment.1 {
+                Direction::Right => ('R', 0),
+                Direction::Down => ('D', 1),
+                Direction::Left => ('L', 2),
+                Direction::Up => ('U', 3),
+            };
+            format!(
+                "{letter} {} (#{:05x}{digit})",
+                length(0, segment),
+                length(1, segment)
+            )
+        }));
+
+        let [part_1, part_2] = [0, 1].map(|part| {
+            let area = polyomino
+                .cells()
+                .map(|(x, y)| widths[part][x] * heights[part][y])
+                .sum::<u64>();
+            let perimeter = segments
+                .iter()
+                .map(|&segment| length(part, segment))
+                .sum::<u64>();
+            area + perimeter / 2 + 1
+        });
+
+        Planted {
+            input: plan,
+            part_1: Some(part_1),
+            part_2: Some(part_2),
+        }
+    }
+}
+
+impl Generator for DigPlan {
+    const PUZZLE_SIZE: usize = 40;
+
+    fn sized(cells: usize) -> Self {
+        Self {
+            cells,
+            fill: 0.5,
+            max_width: 6,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(18);
+        for size in [1, 2, 10, 40] {
+            for _ in 0..10 {
+                let planted = DigPlan::sized(size).planted(&mut rng);
+                planted.check(&day18::Day18);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! System of workflows sorting parts, and the parts to sort.
+//!
+//! The workflows form a tree from `in`, so that every part ends
+//! accepted or rejected.
+
+use std::collections::VecDeque;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, names, Generator};
+
+pub struct System {
+    pub workflows: usize,
+    pub parts: usize,
+    /// Largest number of rules with a condition in a workflow.
+    pub max_rules: usize,
+}
+
+impl Generator for System {
+    const PUZZLE_SIZE: usize = 550;
+
+    fn sized(workflows: usize) -> Self {
+        Self {
+            workflows,
+            parts: 200,
+            max_rules: 3,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let count = self.workflows.max(1);
+        let mut ids = vec!["in".to_string()];
+        ids.extend(names(rng, count - 1, 3, b"abcdefghijklmnopqrstuvwxyz", &[]));
+
+        let mut workflows = vec![];
+        let mut pending = VecDeque::from([0]);
+        let mut next = 1;
+        while let Some(workflow) = pending.pop_front() {
+            let rules = rng.gen_range(1..=self.max_rules.max(1));
+            let mut targets = (0..=rules)
+                .map(|i| {
+                    // keep branching while workflows are left
+                    let last = i == rules && pending.is_empty();
+                    if next < count && (last || rng.gen_bool(0.5)) {
+                        pending.push_back(next);
+                        next += 1;
+                        ids[next - 1].clone()
+                    } else if rng.gen() {
+                        "A".to_string()
+                    } else {
+                        "R".to_string()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let otherwise = targets.pop().unwrap();
+            let rules = targets
+                .into_iter()
+                .map(|target| {
+                    let category = ["x", "m", "a", "s"].choose(rng).unwrap();
+                    let condition = if rng.gen() { '<' } else { '>' };
+                    let value = rng.gen_range(1..=4000);
+                    format!("{category}{condition}{value}:{target}")
+                })
+                .collect::<Vec<_>>();
+            workflows.push(format!(
+                "{}{{{},{otherwise}}}",
+                ids[workflow],
+                rules.join(",")
+            ));
+        }
+        workflows.shuffle(rng);
+
+        let parts = (0..self.parts).map(|_| {
+            let [x, m, a, s] = [(); 4].map(|()| rng.gen_range(1..=4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        });
+
+        format!("{}\n{}", lines(workflows), lines(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(19);
+        for size in [1, 10, 100] {
+            let input = System::sized(size).generate(&mut rng);
+            assert_eq!(
+                input.lines().filter(|line| line.contains('{')).count(),
+                size + 200
+            );
+            solvable(&day19::Day19, &input);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Modules wired into counters, whose conjunctions feed `rx`.
+//!
+//! Each counter is a chain of flip-flops counting the button presses in
+//! binary, with a conjunction watching the bits set in a prime: when the
+//! count gets there, the conjunction sends a low pulse and resets the
+//! counter to 0. Every counter feeds `rx` through an inverter and a final
+//! conjunction, so that part 2 is the product of the primes.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, names, primes, Generator, Planted};
+
+pub struct Modules {
+    /// Counters, at most as many as the primes of `bits` fitting in `u64`.
+    pub counters: usize,
+    /// Flip-flops of each counter.
+    pub bits: usize,
+}
+
+impl Modules {
+    /// Random modules, with the button presses of part 2 planted.
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let bits = self.bits.clamp(2, 16);
+        // the counters start from bit 0, that the conjunctions reset
+        let mut periods = primes(1 << (bits - 1)..1 << bits);
+        periods.retain(|period| period % 2 == 1);
+        let counters = self.counters.clamp(1, 63 / bits).min(periods.len());
+        periods.shuffle(rng);
+        periods.truncate(counters);
+
+        let count = counters * (bits + 2) + 1;
+        let len = if count + 1 < 26 * 26 { 2 } else { 3 };
+        let names = names(rng, count, len, b"abcdefghijklmnopqrstuvwxyz", &["rx"]);
+        let last = &names[0];
+
+        let mut modules = vec![format!("&{last} -> rx")];
+        let mut broadcast = vec![];
+        for (&period, names) in periods.iter().zip(names[1..].chunks(bits + 2)) {
+            let (flip_flops, rest) = names.split_at(bits);
+            let (hub, inverter) = (&rest[0], &rest[1]);
+
+            let mut hub_outputs = vec![flip_flops[0].clone()];
+            for (i, flip_flop) in flip_flops.iter().enumerate() {
+                let mut outputs = flip_flops
+                    .get(i + 1)
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if period & (1 << i) != 0 {
+                    outputs.push(hub.clone());
+                } else {
+                    hub_outputs.push(flip_flop.clone());
+                }
+                outputs.shuffle(rng);
+                modules.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+            }
+            hub_outputs.push(inverter.clone());
+            hub_outputs.shuffle(rng);
+            modules.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+            modules.push(format!("&{inverter} -> {last}"));
+
+            broadcast.push(flip_flops[0].clone());
+        }
+        modules.push(format!("broadcaster -> {}", broadcast.join(", ")));
+        modules.shuffle(rng);
+
+        Planted {
+            input: lines(modules),
+            part_1: None,
+            part_2: Some(periods.iter().product()),
+        }
+    }
+}
+
+impl Generator for Modules {
+    const PUZZLE_SIZE: usize = 4;
+
+    fn sized(counters: usize) -> Self {
+        Self { counters, bits: 12 }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(20);
+        for size in 1..=5 {
+            let planted = Modules::sized(size).planted(&mut rng);
+            planted.check(&day20::Day20);
+        }
+    }
+
+    #[test]
+    fn small_counters() {
+        let mut rng = StdRng::seed_from_u64(20);
+        for bits in 2..=6 {
+            let modules = Modules { counters: 2, bits };
+            modules.planted(&mut rng).check(&day20::Day20);
+        }
+    }
+}
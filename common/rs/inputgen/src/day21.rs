@@ -0,0 +1,88 @@
+//! Garden of plots and rocks, with the start in the middle.
+//!
+//! Part 2 expects the layout of the puzzle: a square map whose side
+//! fits 26501365 steps in a whole number of maps and a half, with the
+//! start in the middle and its row, column and the borders clear.
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+/// Sides of the maps whose half is 26501365 modulo the side.
+pub const SIDES: [usize; 3] = [3, 131, 393];
+
+pub struct Garden {
+    /// Side of the map, one of [`SIDES`].
+    pub side: usize,
+    /// Probability of a rock on a tile out of the clear lanes.
+    pub rocks: f64,
+}
+
+impl Generator for Garden {
+    const PUZZLE_SIZE: usize = 131;
+
+    /// The side of [`SIDES`] closest to `side`.
+    fn sized(side: usize) -> Self {
+        Self {
+            side: *SIDES.iter().min_by_key(|s| s.abs_diff(side)).unwrap(),
+            rocks: 0.15,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        assert!(SIDES.contains(&self.side), "invalid side {}", self.side);
+
+        let side = self.side;
+        let middle = side / 2;
+        lines((0..side).map(|y| {
+            (0..side)
+                .map(|x| {
+                    let diamond = (x.abs_diff(middle) + y.abs_diff(middle)).abs_diff(middle);
+                    if (x, y) == (middle, middle) {
+                        'S'
+                    } else if x == 0
+                        || y == 0
+                        || x == side - 1
+                        || y == side - 1
+                        || x == middle
+                        || y == middle
+                        || diamond <= 1
+                        || !rng.gen_bool(self.rocks)
+                    {
+                        '.'
+                    } else {
+                        '#'
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(21);
+        for size in [1, 131] {
+            let input = Garden::sized(size).generate(&mut rng);
+            solvable(&day21::Day21, &input);
+        }
+    }
+
+    #[test]
+    fn without_rocks() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let garden = Garden {
+            rocks: 0.,
+            ..Garden::puzzle()
+        };
+        // every plot at an even distance within 64 steps
+        assert_eq!(day21::try_solve_1(&garden.generate(&mut rng)), Ok(65 * 65));
+    }
+}
@@ -0,0 +1,85 @@
+//! Snapshot of falling bricks.
+
+use std::collections::HashSet;
+
+use rand::Rng;
+
+use crate::{lines, Generator};
+
+pub struct Snapshot {
+    pub bricks: usize,
+    /// Side of the area the bricks fall on.
+    pub side: usize,
+    /// Largest number of cubes of a brick.
+    pub max_len: usize,
+}
+
+impl Generator for Snapshot {
+    const PUZZLE_SIZE: usize = 1200;
+
+    fn sized(bricks: usize) -> Self {
+        Self {
+            bricks,
+            side: 10,
+            max_len: 5,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let side = self.side.max(1);
+        let max_len = self.max_len.clamp(1, side);
+        // about a third of the snapshot is bricks
+        let height = 3 * self.bricks * (max_len + 1) / 2 / (side * side) + 2;
+
+        let mut cubes = HashSet::new();
+        let mut bricks = Vec::with_capacity(self.bricks);
+        while bricks.len() < self.bricks {
+            let axis = rng.gen_range(0..3);
+            let len = rng.gen_range(1..=max_len);
+            let mut start = [
+                rng.gen_range(0..side),
+                rng.gen_range(0..side),
+                rng.gen_range(1..=height),
+            ];
+            if axis < 2 {
+                start[axis] = start[axis].min(side - len);
+            }
+            let mut end = start;
+            end[axis] += len - 1;
+
+            let brick = (0..len)
+                .map(|i| {
+                    let mut cube = start;
+                    cube[axis] += i;
+                    cube
+                })
+                .collect::<Vec<_>>();
+            if brick.iter().all(|cube| !cubes.contains(cube)) {
+                cubes.extend(brick);
+                bricks.push(format!(
+                    "{},{},{}~{},{},{}",
+                    start[0], start[1], start[2], end[0], end[1], end[2]
+                ));
+            }
+        }
+
+        lines(bricks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(22);
+        for size in [1, 10, 100] {
+            let input = Snapshot::sized(size).generate(&mut rng);
+            solvable(&day22::Day22, &input);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Map of hiking trails between crossings, with slopes going down and
+//! right.
+//!
+//! The crossings sit on a grid, joined by straight trails to the next
+//! crossing on the right and below, some of them dropped. Every path
+//! following the slopes goes only down and right, so the longest hike
+//! of part 1 is as long as any of them.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, Generator, Planted};
+
+pub struct Trails {
+    /// Crossings on each row, and on each column.
+    pub crossings: usize,
+    /// Range of the lengths of the trails between crossings.
+    pub lengths: std::ops::RangeInclusive<usize>,
+    /// Probability of dropping a trail.
+    pub drop: f64,
+}
+
+impl Trails {
+    /// Random trails, with the longest hike of part 1 planted.
+    ///
+    /// # Panics
+    /// Panic if `drop` is not a probability
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let k = self.crossings.max(1);
+        let lengths = *self.lengths.start().max(&4)..=*self.lengths.end().max(&4);
+        let position = |rng: &mut R| {
+            let mut position = vec![1];
+            for i in 1..k {
+                position.push(position[i - 1] + rng.gen_range(lengths.clone()));
+            }
+            position
+        };
+        let (xs, ys) = (position(rng), position(rng));
+        let (ncols, nrows) = (xs[k - 1] + 2, ys[k - 1] + 2);
+
+        // drop trails, as long as every crossing has one in and one out
+        let mut trails = (0..k)
+            .flat_map(|i| (0..k).map(move |j| (i, j)))
+            .flat_map(|(i, j)| {
+                [
+                    (i + 1 < k).then_some(((i, j), (i + 1, j))),
+                    (j + 1 < k).then_some(((i, j), (i, j + 1))),
+                ]
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        trails.shuffle(rng);
+        let mut outs = vec![vec![0; k]; k];
+        let mut ins = vec![vec![0; k]; k];
+        for &((i, j), (ni, nj)) in &trails {
+            outs[j][i] += 1;
+            ins[nj][ni] += 1;
+        }
+        trails.retain(|&((i, j), (ni, nj))| {
+            if outs[j][i] > 1 && ins[nj][ni] > 1 && rng.gen_bool(self.drop) {
+                outs[j][i] -= 1;
+                ins[nj][ni] -= 1;
+                false
+            } else {
+                true
+            }
+        });
+
+        let mut tiles = vec![vec![b'#'; ncols]; nrows];
+        tiles[0][1] = b'.';
+        tiles[nrows - 1][ncols - 2] = b'.';
+        for ((i, j), (ni, nj)) in trails {
+            let (x, y, nx, ny) = (xs[i], ys[j], xs[ni], ys[nj]);
+            for row in &mut tiles[y..=ny] {
+                row[x..=nx].fill(b'.');
+            }
+            let slope = if ny == y { b'>' } else { b'v' };
+            tiles[y + (ny - y).min(1)][x + (nx - x).min(1)] = slope;
+            tiles[ny - (ny - y).min(1)][nx - (nx - x).min(1)] = slope;
+        }
+        tiles[ys[0]][xs[0]] = b'.';
+
+        Planted {
+            input: lines(tiles.into_iter().map(|row| String::from_utf8(row).unwrap())),
+            part_1: Some((ncols + nrows - 4) as u64),
+            part_2: None,
+        }
+    }
+}
+
+impl Generator for Trails {
+    const PUZZLE_SIZE: usize = 6;
+
+    fn sized(crossings: usize) -> Self {
+        Self {
+            crossings,
+            lengths: 4..=30,
+            drop: 0.2,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(23);
+        for size in 1..=4 {
+            for _ in 0..5 {
+                let planted = Trails::sized(size).planted(&mut rng);
+                planted.check(&day23::Day23);
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Hailstones, all hit by a rock thrown from the right place.
+//!
+//! The rock is drawn first, then each hailstone gets a velocity and the
+//! time the rock hits it, which place the hailstone. The rock gives the
+//! answer of part 2.
+
+use rand::Rng;
+
+use crate::{lines, Generator, Planted};
+
+pub struct Hailstones {
+    pub hailstones: usize,
+    /// Range of the coordinates of the rock.
+    pub positions: std::ops::Range<i64>,
+    /// Largest absolute value of the velocities.
+    pub max_velocity: i64,
+    /// Range of the times the rock hits the hailstones.
+    pub times: std::ops::Range<i64>,
+}
+
+impl Hailstones {
+    /// Random hailstones, with the rock of part 2 planted.
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let max = self.max_velocity.max(2);
+        let velocity = |rng: &mut R| {
+            [(); 3].map(|()| loop {
+                let v = rng.gen_range(-max..=max);
+                if v != 0 {
+                    break v;
+                }
+            })
+        };
+        let rock = [(); 3].map(|()| rng.gen_range(self.positions.clone()));
+        let rock_velocity = velocity(rng);
+
+        let mut times = vec![];
+        let mut hailstones = vec![];
+        while hailstones.len() < self.hailstones.max(3) {
+            let time = rng.gen_range(self.times.clone());
+            let hail_velocity = velocity(rng);
+            if times.contains(&time)
+                || hail_velocity
+                    .iter()
+                    .zip(rock_velocity)
+                    .any(|(&v, r)| v == r)
+            {
+                continue;
+            }
+            let position =
+                [0, 1, 2].map(|i| rock[i] + (rock_velocity[i] - hail_velocity[i]) * time);
+            if position.iter().any(|&p| p < 0) {
+                continue;
+            }
+
+            times.push(time);
+            hailstones.push(format!(
+                "{}, {}, {} @ {}, {}, {}",
+                position[0],
+                position[1],
+                position[2],
+                hail_velocity[0],
+                hail_velocity[1],
+                hail_velocity[2]
+            ));
+        }
+
+        Planted {
+            input: lines(hailstones),
+            part_1: None,
+            part_2: u64::try_from(rock.iter().sum::<i64>()).ok(),
+        }
+    }
+}
+
+impl Generator for Hailstones {
+    const PUZZLE_SIZE: usize = 300;
+
+    fn sized(hailstones: usize) -> Self {
+        Self {
+            hailstones,
+            positions: 100_000_000_000_000..400_000_000_000_000,
+            max_velocity: 300,
+            times: 10_000_000_000..1_000_000_000_000,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvable;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = StdRng::seed_from_u64(24);
+        for size in [3, 10, 100] {
+            let planted = Hailstones::sized(size).planted(&mut rng);
+            // part 2 needs z3 to find the rock
+            solvable(&day24::Day24, &planted.input);
+        }
+    }
+}
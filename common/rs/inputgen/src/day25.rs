@@ -0,0 +1,163 @@
+//! Wiring diagram of components, with exactly three wires to cut.
+//!
+//! Two groups of components are wired densely inside, every component
+//! to at least `min_wires` others of its group, and to each other by
+//! just three wires, so that part 1 is the product of their sizes.
+//!
+//! Each group is a random cycle where every component is wired to the
+//! next two, the square of a Hamiltonian cycle: it is 4-regular and
+//! vertex-transitive, so no cut of less than 4 wires splits it, and the
+//! three wires between the groups are the only minimum cut.
+
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{lines, names, Generator, Planted};
+
+pub struct Components {
+    pub components: usize,
+    /// Least number of wires of a component inside its group.
+    pub min_wires: usize,
+}
+
+impl Components {
+    /// Random wires between `count` components, the first `first` of
+    /// them in the first group, the others in the second.
+    fn wires<R: Rng + ?Sized>(&self, rng: &mut R) -> (usize, usize, Vec<(usize, usize)>) {
+        let min_wires = self.min_wires.max(4);
+        let count = self.components.clamp(2 * (min_wires + 1), 26 * 26 * 26);
+        let first = rng
+            .gen_range(count * 3 / 10..=count * 7 / 10)
+            .clamp(min_wires + 1, count - min_wires - 1);
+
+        let mut wires = vec![];
+        let mut seen = HashSet::new();
+        let mut connect = |a: usize, b: usize, wires: &mut Vec<(usize, usize)>| {
+            a != b && seen.insert((a.min(b), a.max(b))) && {
+                wires.push((a, b));
+                true
+            }
+        };
+
+        let mut degrees = vec![0; count];
+        for group in [0..first, first..count] {
+            // a random cycle, each component also wired to the one after
+            // the next, then more wires for the components with few
+            let mut cycle = group.clone().collect::<Vec<_>>();
+            cycle.shuffle(rng);
+            for (i, &a) in cycle.iter().enumerate() {
+                for step in 1..=2 {
+                    let b = cycle[(i + step) % cycle.len()];
+                    connect(a, b, &mut wires);
+                    degrees[a] += 1;
+                    degrees[b] += 1;
+                }
+            }
+            for a in group.clone() {
+                while degrees[a] < min_wires {
+                    let b = rng.gen_range(group.clone());
+                    if connect(a, b, &mut wires) {
+                        degrees[a] += 1;
+                        degrees[b] += 1;
+                    }
+                }
+            }
+        }
+        let mut cut = 0;
+        while cut < 3 {
+            if connect(
+                rng.gen_range(0..first),
+                rng.gen_range(first..count),
+                &mut wires,
+            ) {
+                cut += 1;
+            }
+        }
+
+        (count, first, wires)
+    }
+
+    /// Random wiring diagram, with the groups of part 1 planted.
+    pub fn planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Planted {
+        let (count, first, wires) = self.wires(rng);
+
+        // every wire listed once, with either of its components
+        let names = names(rng, count, 3, b"abcdefghijklmnopqrstuvwxyz", &[]);
+        let mut lists = vec![vec![]; count];
+        for (a, b) in wires {
+            let (a, b) = if rng.gen() { (a, b) } else { (b, a) };
+            lists[a].push(names[b].as_str());
+        }
+        let mut diagram = lists
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, list)| !list.is_empty())
+            .map(|(a, list)| {
+                list.shuffle(rng);
+                format!("{}: {}", names[a], list.join(" "))
+            })
+            .collect::<Vec<_>>();
+        diagram.shuffle(rng);
+
+        Planted {
+            input: lines(diagram),
+            part_1: Some((first * (count - first)) as u64),
+            part_2: None,
+        }
+    }
+}
+
+impl Generator for Components {
+    const PUZZLE_SIZE: usize = 1500;
+
+    fn sized(components: usize) -> Self {
+        Self {
+            components,
+            min_wires: 4,
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.planted(rng).input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc::graph::min_cut;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn planted() {
+        let mut rng = StdRng::seed_from_u64(25);
+        for size in [1, 20, 100, 500] {
+            let planted = Components::sized(size).planted(&mut rng);
+            planted.check(&day25::Day25);
+        }
+    }
+
+    #[test]
+    fn only_cut_of_3() {
+        let mut rng = StdRng::seed_from_u64(25);
+        for size in [1, 20, 100] {
+            let (count, first, wires) = Components::sized(size).wires(&mut rng);
+            let mut neighbors = vec![vec![]; count];
+            for &(a, b) in &wires {
+                neighbors[a].push((b, 1));
+                neighbors[b].push((a, 1));
+            }
+            let graph = |&a: &usize| neighbors[a].clone();
+
+            for group in [0..first, first..count] {
+                let (cut, _) = min_cut(&graph, group).unwrap();
+                assert!(cut >= 4, "cut of {cut} wires in a group");
+            }
+            let (cut, side) = min_cut(&graph, 0..count).unwrap();
+            assert_eq!(cut, 3);
+            assert!(side.len() == first || side.len() == count - first);
+        }
+    }
+}
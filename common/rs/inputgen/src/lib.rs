@@ -0,0 +1,273 @@
+//! Random puzzle inputs for stress testing the days.
+//!
+//! Each `dayNN` module has a [`Generator`] of valid inputs for that day,
+//! with public fields to tune their size and structure. Some generators
+//! plant the answers while building the input, and return them with the
+//! input as a [`Planted`].
+//!
+//! ```
+//! use inputgen::{day25::Components, Generator};
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let mut rng = StdRng::seed_from_u64(25);
+//! let planted = Components::sized(100).planted(&mut rng);
+//! assert!(planted.part_1.is_some());
+//! ```
+
+#![deny(clippy::pedantic)]
+#![allow(clippy::must_use_candidate)]
+
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
+mod shape;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// Generator of random inputs of a day.
+pub trait Generator {
+    /// Size of the puzzle inputs, in the unit of [`Generator::sized`].
+    const PUZZLE_SIZE: usize;
+
+    /// Generator of inputs of `size`, measured in what the day scales
+    /// with: lines, side of the map, nodes of the graph...
+    fn sized(size: usize) -> Self;
+
+    /// Generator of inputs as large as the puzzle inputs.
+    fn puzzle() -> Self
+    where
+        Self: Sized,
+    {
+        Self::sized(Self::PUZZLE_SIZE)
+    }
+
+    /// Random input.
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String;
+}
+
+/// Random input with answers known by construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Planted {
+    pub input: String,
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+/// Generator of a day, with the types erased.
+pub struct Day {
+    pub puzzle_size: usize,
+    pub generate: fn(usize, &mut dyn RngCore) -> String,
+}
+
+impl Day {
+    /// Size of the inputs `scale` times as large as the puzzle inputs.
+    pub fn scaled(&self, scale: f64) -> usize {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let size = (self.puzzle_size as f64 * scale).round() as usize;
+        size.max(1)
+    }
+}
+
+/// Random number generator of the inputs of `day` from `seed`, so that
+/// the inputs of a day do not depend on the other days generated.
+pub fn day_rng(seed: u64, day: u8) -> StdRng {
+    StdRng::seed_from_u64(seed ^ u64::from(day) << 56)
+}
+
+macro_rules! days {
+    ($($module:ident :: $generator:ident),* $(,)?) => {
+        /// Every day, indexed by day number minus one.
+        pub static DAYS: [Day; 25] = [
+            $(Day {
+                puzzle_size: <$module::$generator as Generator>::PUZZLE_SIZE,
+                generate: |size, rng| $module::$generator::sized(size).generate(rng),
+            }),*
+        ];
+    };
+}
+
+days!(
+    day01::Calibrations,
+    day02::Games,
+    day03::Schematic,
+    day04::Scratchcards,
+    day05::Almanac,
+    day06::Races,
+    day07::Hands,
+    day08::Network,
+    day09::Histories,
+    day10::Pipes,
+    day11::Image,
+    day12::Records,
+    day13::Patterns,
+    day14::Platform,
+    day15::Sequence,
+    day16::Contraption,
+    day17::City,
+    day18::DigPlan,
+    day19::System,
+    day20::Modules,
+    day21::Garden,
+    day22::Snapshot,
+    day23::Trails,
+    day24::Hailstones,
+    day25::Components,
+);
+
+/// `count` distinct random names of `len` characters from `alphabet`,
+/// none in `reserved`.
+///
+/// # Panics
+/// Panic if there are not enough names
+pub(crate) fn names<R: Rng + ?Sized>(
+    rng: &mut R,
+    count: usize,
+    len: usize,
+    alphabet: &[u8],
+    reserved: &[&str],
+) -> Vec<String> {
+    assert!(
+        alphabet.len().pow(u32::try_from(len).unwrap()) >= count + reserved.len(),
+        "not enough names"
+    );
+
+    let mut seen = reserved
+        .iter()
+        .map(|&name| name.to_string())
+        .collect::<HashSet<_>>();
+    let mut names = Vec::with_capacity(count);
+    while names.len() < count {
+        let name = (0..len)
+            .map(|_| char::from(*alphabet.choose(rng).unwrap()))
+            .collect::<String>();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Primes in `range`.
+pub(crate) fn primes(range: std::ops::Range<u64>) -> Vec<u64> {
+    range
+        .filter(|&n| n > 1 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect()
+}
+
+/// Lines of text, each ended by a newline.
+pub(crate) fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().fold(String::new(), |mut text, line| {
+        text.push_str(&line);
+        text.push('\n');
+        text
+    })
+}
+
+/// Check that `puzzle` solves `input`.
+#[cfg(test)]
+pub(crate) fn solvable(puzzle: &dyn aoc::Puzzle, input: &str) {
+    for part in aoc::Part::ALL {
+        if let Err(err) = puzzle.solve(part, input) {
+            panic!("day {} part {part}: {err}\n{input}", puzzle.day());
+        }
+    }
+}
+
+#[cfg(test)]
+impl Planted {
+    /// Check the answers of `puzzle` against the planted ones.
+    pub(crate) fn check(&self, puzzle: &dyn aoc::Puzzle) {
+        for (part, planted) in aoc::Part::ALL.into_iter().zip([self.part_1, self.part_2]) {
+            let answer = puzzle
+                .solve(part, &self.input)
+                .unwrap_or_else(|err| panic!("day {} part {part}: {err}", puzzle.day()));
+            if let Some(planted) = planted {
+                assert_eq!(
+                    answer,
+                    planted.to_string(),
+                    "day {} part {part}\n{}",
+                    puzzle.day(),
+                    self.input
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_day() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (day, generator) in DAYS.iter().enumerate() {
+            let input = (generator.generate)(generator.puzzle_size / 4, &mut rng);
+            assert!(!input.is_empty(), "day {}", day + 1);
+            assert!(input.ends_with('\n'), "day {}", day + 1);
+        }
+    }
+
+    #[test]
+    fn day_inputs() {
+        let generate = |seed, day: u8| {
+            let mut rng = day_rng(seed, day);
+            (DAYS[usize::from(day) - 1].generate)(10, &mut rng)
+        };
+        assert_eq!(generate(1, 7), generate(1, 7));
+        assert_ne!(generate(1, 7), generate(2, 7));
+        assert_ne!(generate(1, 7), generate(1, 1));
+
+        assert_eq!(DAYS[0].scaled(0.5), 500);
+        assert_eq!(DAYS[5].scaled(0.1), 1);
+    }
+
+    #[test]
+    fn distinct_names() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let names = names(
+            &mut rng,
+            26 * 26 - 1,
+            2,
+            b"abcdefghijklmnopqrstuvwxyz",
+            &["in"],
+        );
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+        assert!(!names.iter().any(|name| name == "in"));
+    }
+
+    #[test]
+    fn first_primes() {
+        assert_eq!(primes(0..20), [2, 3, 5, 7, 11, 13, 17, 19]);
+    }
+}
@@ -0,0 +1,89 @@
+//! Write random puzzle inputs.
+//!
+//! ```text
+//! inputgen --output path
+//! inputgen --output path --day 12 --day 22 --count 10 --scale 0.5 --seed 42
+//! ```
+//!
+//! The inputs of every day go in its `dayNN` subdirectory of `--output`,
+//! the layout `difftest --inputs` reads.
+
+#![deny(clippy::pedantic)]
+
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use inputgen::{day_rng, DAYS};
+
+#[derive(Parser)]
+#[command(name = "inputgen", about = "Write random puzzle inputs")]
+struct Cli {
+    /// Day to generate, can be repeated, every day if missing
+    #[arg(short, long)]
+    day: Vec<u8>,
+
+    /// Directory of the inputs, in a `dayNN` subdirectory for every day
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Inputs of every day
+    #[arg(short, long, default_value_t = 1)]
+    count: usize,
+
+    /// Size of the inputs, relative to the puzzle inputs
+    #[arg(long, default_value_t = 1.)]
+    scale: f64,
+
+    /// Seed of the random inputs, a random one if missing
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+fn generate(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    if let Some(day) = cli.day.iter().find(|&&day| !(1..=25).contains(&day)) {
+        return Err(format!("invalid day {day}, must be from 1 to 25").into());
+    }
+    if !cli.scale.is_finite() || cli.scale <= 0. {
+        return Err(format!("invalid scale {}, must be positive", cli.scale).into());
+    }
+
+    let seed = cli.seed.unwrap_or_else(rand::random);
+    eprintln!("seed: {seed}");
+
+    for (day, generator) in (1..).zip(&DAYS) {
+        if !cli.day.is_empty() && !cli.day.contains(&day) {
+            continue;
+        }
+
+        let size = generator.scaled(cli.scale);
+
+        let dir = cli.output.join(format!("day{day:02}"));
+        fs::create_dir_all(&dir)?;
+
+        let mut rng = day_rng(seed, day);
+        for i in 0..cli.count {
+            let path = dir.join(format!("random-{seed}-{i}"));
+            fs::write(&path, (generator.generate)(size, &mut rng))?;
+        }
+        println!(
+            "day {day:02}: {} inputs of size {size} in {}",
+            cli.count,
+            dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match generate(&Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
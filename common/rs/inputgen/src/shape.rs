@@ -0,0 +1,227 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// Direction of a step on the boundary of a [`Polyomino`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub(crate) fn step(self, (x, y): (usize, usize)) -> (usize, usize) {
+        match self {
+            Direction::Up => (x, y - 1),
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x - 1, y),
+        }
+    }
+
+    pub(crate) fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+/// Connected set of cells of a grid, whose boundary is a simple loop:
+/// it has no holes, and no two cells touch only at a corner.
+pub(crate) struct Polyomino {
+    ncols: usize,
+    nrows: usize,
+    cells: Vec<bool>,
+}
+
+impl Polyomino {
+    /// Random polyomino covering about `fill` of the `ncols` x `nrows` cells.
+    pub(crate) fn random<R: Rng + ?Sized>(
+        rng: &mut R,
+        ncols: usize,
+        nrows: usize,
+        fill: f64,
+    ) -> Self {
+        assert!(ncols > 0 && nrows > 0, "empty grid");
+
+        let mut polyomino = Self {
+            ncols,
+            nrows,
+            cells: vec![false; ncols * nrows],
+        };
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let target = ((ncols * nrows) as f64 * fill).round().max(1.) as usize;
+
+        let start = (rng.gen_range(0..ncols), rng.gen_range(0..nrows));
+        let mut frontier = vec![start];
+        let mut size = 0;
+        while size < target && !frontier.is_empty() {
+            let i = rng.gen_range(0..frontier.len());
+            let cell = frontier.swap_remove(i);
+            if polyomino.contains(cell) {
+                continue;
+            }
+            polyomino.insert(cell);
+            size += 1;
+            frontier.extend(polyomino.neighbors(cell));
+        }
+
+        while polyomino.fill_holes() || polyomino.fill_corners(rng) {}
+
+        polyomino
+    }
+
+    pub(crate) fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.ncols && y < self.nrows && self.cells[y * self.ncols + x]
+    }
+
+    /// Cells of the polyomino.
+    pub(crate) fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.nrows)
+            .flat_map(|y| (0..self.ncols).map(move |x| (x, y)))
+            .filter(|&cell| self.contains(cell))
+    }
+
+    /// Unit steps around the polyomino, from a corner of the grid lines
+    /// and clockwise, as the cells are seen with `y` going down.
+    pub(crate) fn boundary(&self) -> Vec<((usize, usize), Direction)> {
+        let start = self.cells().next().expect("empty polyomino");
+
+        let mut boundary = vec![];
+        let mut vertex = start;
+        loop {
+            let direction = self.out_of(vertex);
+            boundary.push((vertex, direction));
+            vertex = direction.step(vertex);
+            if vertex == start {
+                break boundary;
+            }
+        }
+    }
+
+    /// Step leaving `vertex` with the polyomino on the right.
+    fn out_of(&self, (x, y): (usize, usize)) -> Direction {
+        let inside = |dx: usize, dy: usize| {
+            x + dx > 0 && y + dy > 0 && self.contains((x + dx - 1, y + dy - 1))
+        };
+        let (top_left, top_right, bottom_left, bottom_right) =
+            (inside(0, 0), inside(1, 0), inside(0, 1), inside(1, 1));
+
+        if bottom_right && !top_right {
+            Direction::Right
+        } else if bottom_left && !bottom_right {
+            Direction::Down
+        } else if top_left && !bottom_left {
+            Direction::Left
+        } else if top_right && !top_left {
+            Direction::Up
+        } else {
+            unreachable!("({x}, {y}) not on the boundary")
+        }
+    }
+
+    fn insert(&mut self, (x, y): (usize, usize)) {
+        self.cells[y * self.ncols + x] = true;
+    }
+
+    fn neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (ncols, nrows) = (self.ncols, self.nrows);
+        [
+            (x > 0).then(|| (x - 1, y)),
+            (x + 1 < ncols).then_some((x + 1, y)),
+            (y > 0).then(|| (x, y - 1)),
+            (y + 1 < nrows).then_some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Add the cells not reachable from outside, returns if any.
+    fn fill_holes(&mut self) -> bool {
+        let mut outside = vec![false; self.cells.len()];
+        let mut queue = (0..self.nrows)
+            .flat_map(|y| [(0, y), (self.ncols - 1, y)])
+            .chain((0..self.ncols).flat_map(|x| [(x, 0), (x, self.nrows - 1)]))
+            .collect::<Vec<_>>();
+        while let Some((x, y)) = queue.pop() {
+            if !self.contains((x, y)) && !outside[y * self.ncols + x] {
+                outside[y * self.ncols + x] = true;
+                queue.extend(self.neighbors((x, y)));
+            }
+        }
+
+        let mut filled = false;
+        for (cell, outside) in self.cells.iter_mut().zip(outside) {
+            if !*cell && !outside {
+                *cell = true;
+                filled = true;
+            }
+        }
+        filled
+    }
+
+    /// Add a cell where two cells touch only at a corner, returns if any.
+    fn fill_corners<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let mut filled = false;
+        for y in 0..self.nrows - 1 {
+            for x in 0..self.ncols - 1 {
+                let [top_left, top_right, bottom_left, bottom_right] =
+                    [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)];
+                let gaps = if self.contains(top_left) && self.contains(bottom_right) {
+                    [top_right, bottom_left]
+                } else if self.contains(top_right) && self.contains(bottom_left) {
+                    [top_left, bottom_right]
+                } else {
+                    continue;
+                };
+                if gaps.iter().all(|&gap| !self.contains(gap)) {
+                    self.insert(*gaps.choose(rng).unwrap());
+                    filled = true;
+                }
+            }
+        }
+        filled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn boundary_is_a_loop() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let fill = rng.gen_range(0.1..0.9);
+            let polyomino = Polyomino::random(&mut rng, 12, 9, fill);
+
+            let boundary = polyomino.boundary();
+            let vertices = boundary
+                .iter()
+                .map(|&(vertex, _)| vertex)
+                .collect::<std::collections::HashSet<_>>();
+            assert_eq!(vertices.len(), boundary.len());
+
+            // shoelace, positive when clockwise with y going down
+            let area = boundary
+                .iter()
+                .map(|&((x, y), direction)| {
+                    let (nx, ny) = direction.step((x, y));
+                    x as isize * ny as isize - nx as isize * y as isize
+                })
+                .sum::<isize>();
+            assert_eq!(area, 2 * polyomino.cells().count() as isize);
+        }
+    }
+}
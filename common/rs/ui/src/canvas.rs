@@ -0,0 +1,105 @@
+//! Map of the input drawn on a canvas, with the [`Overlay`] of an answer.
+
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use yew::prelude::*;
+
+use aoc::Overlay;
+
+/// Largest side of the canvas, in pixels.
+const SIZE: usize = 560;
+
+/// Largest side of a tile, in pixels, for the small maps.
+const MAX_TILE: usize = 24;
+
+#[derive(Properties, PartialEq)]
+pub struct CanvasProps {
+    /// Input with the map, a row of tiles per line.
+    pub input: AttrValue,
+    pub overlay: Overlay,
+}
+
+/// Canvas with the tiles of the input and the overlay over them.
+#[function_component]
+pub fn Canvas(props: &CanvasProps) -> Html {
+    let canvas = use_node_ref();
+
+    {
+        let canvas = canvas.clone();
+        use_effect_with(
+            (props.input.clone(), props.overlay.clone()),
+            move |(input, overlay)| {
+                if let Some(canvas) = canvas.cast::<HtmlCanvasElement>() {
+                    draw(&canvas, input, overlay);
+                }
+            },
+        );
+    }
+
+    html! {
+        <canvas class="grid" ref={canvas} />
+    }
+}
+
+/// Colour of a tile of the map.
+fn tile_color(tile: u8) -> &'static str {
+    match tile {
+        b'.' => "#f8f8f8",
+        b'#' => "#404040",
+        _ => "#b0b0b0",
+    }
+}
+
+fn draw(canvas: &HtmlCanvasElement, input: &str, overlay: &Overlay) {
+    let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let ncols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let tile = (SIZE / ncols.max(rows.len()).max(1)).clamp(1, MAX_TILE);
+
+    canvas.set_width((ncols * tile) as u32);
+    canvas.set_height((rows.len() * tile) as u32);
+
+    let Some(context) = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+    else {
+        return;
+    };
+
+    let side = tile as f64;
+    let fill = |color: &str, (x, y): (usize, usize)| {
+        context.set_fill_style_str(color);
+        context.fill_rect(x as f64 * side, y as f64 * side, side, side);
+    };
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, &t) in row.iter().enumerate() {
+            fill(tile_color(t), (x, y));
+        }
+    }
+
+    for layer in &overlay.cells {
+        let color = layer.color.to_string();
+        for &position in &layer.positions {
+            fill(&color, position);
+        }
+    }
+
+    context.set_line_width((side / 3.0).max(1.0));
+    context.set_line_join("round");
+    let center = |n: usize| (n as f64 + 0.5) * side;
+    for layer in &overlay.paths {
+        context.set_stroke_style_str(&layer.color.to_string());
+        context.begin_path();
+        for (i, &(x, y)) in layer.positions.iter().enumerate() {
+            if i == 0 {
+                context.move_to(center(x), center(y));
+            } else {
+                context.line_to(center(x), center(y));
+            }
+        }
+        context.stroke();
+    }
+}
@@ -1,80 +1,154 @@
-use std::marker::PhantomData;
-use std::fmt::Display;
 use std::time::Duration;
 
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 
 use instant::Instant;
 
 use yew::prelude::*;
 
+use aoc::input::Stats;
+use aoc::report::{Format, Record, Report};
+use aoc::{Overlay, ParseError, Part, Puzzle, Timed};
+
+pub mod canvas;
+pub mod picker;
+pub mod selftest;
+pub mod storage;
+
+use canvas::Canvas;
+use picker::Picker;
+use selftest::{Check, SelfTest};
+use storage::{History, Run, Saved};
+
 #[derive(Properties)]
-pub struct ModelProps<SOLVE1, SOLVE2> {
+pub struct ModelProps {
     pub input: String,
-    pub solve_1: SOLVE1,
-    pub solve_2: SOLVE2,
+    pub puzzle: &'static dyn Puzzle,
 }
 
 pub enum Msg {
     Run(String),
+    /// Input typed in the page.
+    Edit(String),
+    /// Input read from a file.
+    Load(String),
+    /// Forget the saved input and runs.
+    Clear,
+    /// Solve the examples of the day.
+    SelfTest,
 }
 
-pub struct Model<SOLVE1, P1T, SOLVE2, P2T> {
+pub struct Model {
     input_ref: NodeRef,
-    part1: Option<P1T>,
-    part2: Option<P2T>,
+    part1: Option<Result<Timed, ParseError>>,
+    part2: Option<Result<Timed, ParseError>>,
     input: String,
-    elapsed_part_1: Option<Duration>,
-    elapsed_part_2: Option<Duration>,
     elapsed_total: Option<Duration>,
-    _solve1: PhantomData<SOLVE1>,
-    _solve2: PhantomData<SOLVE2>,
+    report: Report,
+    saved: Saved,
+    /// Input of the last run, with the drawings of the answers.
+    drawn: AttrValue,
+    overlays: Vec<(Part, Overlay)>,
+    checks: Vec<Check>,
 }
 
-impl<SOLVE1, SOLVE2> PartialEq for ModelProps<SOLVE1, SOLVE2> {
+impl PartialEq for ModelProps {
     fn eq(&self, other: &Self) -> bool {
-        self.input.eq(&other.input)
+        self.input.eq(&other.input) && self.puzzle.day() == other.puzzle.day()
     }
 }
 
-impl<SOLVE1, P1T, SOLVE2, P2T> Component for Model<SOLVE1, P1T, SOLVE2, P2T>
-where SOLVE1: Fn(&str) -> P1T + 'static,
-      SOLVE2: Fn(&str) -> P2T + 'static,
-      P1T: Display + Clone + 'static,
-      P2T: Display + Clone + 'static,
-{
+impl Component for Model {
     type Message = Msg;
-    type Properties = ModelProps<SOLVE1, SOLVE2>;
+    type Properties = ModelProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let input = ctx.props().input.clone();
-        
+        let saved = Saved::load(ctx.props().puzzle.day());
+        let input = saved
+            .input
+            .clone()
+            .unwrap_or_else(|| ctx.props().input.clone());
+        let last = saved.runs.last();
+
         Self {
             input_ref: NodeRef::default(),
-            part1: None,
-            part2: None,
+            part1: last.and_then(|run| run.timed(Part::One)).map(Ok),
+            part2: last.and_then(|run| run.timed(Part::Two)).map(Ok),
             input,
-            elapsed_part_1: None,
-            elapsed_part_2: None,
-            elapsed_total: None,
-            _solve1: PhantomData,
-            _solve2: PhantomData,
+            elapsed_total: last.and_then(|run| run.elapsed),
+            report: Report {
+                records: saved.records(),
+            },
+            saved,
+            drawn: AttrValue::default(),
+            overlays: vec![],
+            checks: Check::all(ctx.props().puzzle.examples().iter().copied()),
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Run(input) => {
-                let now_part_1 = Instant::now();
-                self.part1 = Some((ctx.props().solve_1)(&input));
-                self.elapsed_part_1 = Some(now_part_1.elapsed());
-                let now_part_2 = Instant::now();
-                self.part2 = Some((ctx.props().solve_2)(&input));
-                self.elapsed_part_2 = Some(now_part_2.elapsed());
-                self.elapsed_total = Some(now_part_1.elapsed());
+                let puzzle = ctx.props().puzzle;
+
+                let now = Instant::now();
+                let mut lap = laps();
+                // drawn by the same solves as the answers
+                let mut overlays = vec![];
+                let [part1, part2] = Part::ALL.map(|part| {
+                    let solved = puzzle.solve_overlay(part, &input, &mut lap);
+                    solved.map(|(timed, overlay)| {
+                        overlays.extend(overlay.map(|overlay| (part, overlay)));
+                        timed
+                    })
+                });
+                self.elapsed_total = Some(now.elapsed());
+
+                self.part1 = Some(part1);
+                self.part2 = Some(part2);
+                self.overlays = overlays;
+                self.drawn = AttrValue::from(input.clone());
+
+                let mut run = Run::new(&input);
+                run.elapsed = self.elapsed_total;
+                for (part, result) in Part::ALL.into_iter().zip([&self.part1, &self.part2]) {
+                    if let Some(Ok(timed)) = result {
+                        run.records
+                            .push(Record::new(puzzle, part, &input, timed.clone()));
+                    }
+                }
+                self.report.records.extend(run.records.iter().cloned());
+
+                self.saved.input = Some(input.clone());
+                self.saved.push(run);
+                self.saved.save(puzzle.day());
+
+                self.input = input;
+                true
+            }
+            Msg::Edit(input) => {
+                self.input = input;
+                true
+            }
+            Msg::Load(input) => {
+                self.saved.input = Some(input.clone());
+                self.saved.save(ctx.props().puzzle.day());
+
                 self.input = input;
                 true
             }
+            Msg::Clear => {
+                Saved::clear(ctx.props().puzzle.day());
+                self.saved = Saved::default();
+                true
+            }
+            Msg::SelfTest => {
+                let puzzle = ctx.props().puzzle;
+                for check in &mut self.checks {
+                    check.outcome = Some(check.example.check(puzzle, &mut laps()));
+                }
+                true
+            }
         }
     }
 
@@ -87,30 +161,109 @@ where SOLVE1: Fn(&str) -> P1T + 'static,
             input.map(|input| Msg::Run(input.value()))
         });
 
+        let oninput = link.callback(|event: InputEvent| {
+            Msg::Edit(event.target_unchecked_into::<HtmlTextAreaElement>().value())
+        });
+        let onload = link.callback(Msg::Load);
+        let onclear = link.callback(|()| Msg::Clear);
+        let onrun = link.callback(|()| Msg::SelfTest);
+
         fn format_duration(elapsed: Option<Duration>) -> String {
             elapsed
                 .map(|v| format!("{}ms ({}us)", v.as_millis(), v.as_micros()))
                 .unwrap_or_else(|| "not run".to_string())
         }
-        
+
+        fn format_timed(result: Option<&Result<Timed, ParseError>>) -> String {
+            match result {
+                Some(Ok(timed)) => format!(
+                    "{} (parse {}us, solve {}us)",
+                    format_duration(Some(timed.elapsed())),
+                    timed.parse.as_micros(),
+                    timed.solve.as_micros(),
+                ),
+                _ => format_duration(None),
+            }
+        }
+
         html! {
             <>
                 <label for="input"> { "Input: " }
-            <textarea id="input" ref={self.input_ref.clone()} rows="4" cols="50" value={self.input.clone()} />
+            <textarea id="input" ref={self.input_ref.clone()} rows="4" cols="50" value={self.input.clone()} {oninput} />
                 </label>
+                <Picker {onload} />
+                <div class="stats">{ Stats::of(&self.input).to_string() }</div>
                 <button {onclick}>{ "\u{23F5}" }</button>
                 <label for="results"> { "Results: " }
             <div id="results" class="output">
-                <div class="result"><label> { "Part 1: " } </label> { self.part1.clone() }</div>
-                <div class="result"><label> { "Part 2: " } </label> { self.part2.clone() }</div>
+                <div class="result"><label> { "Part 1: " } </label> { view_result(self.part1.as_ref()) }</div>
+                <div class="result"><label> { "Part 2: " } </label> { view_result(self.part2.as_ref()) }</div>
             </div>
             <div id="elapsed" class="output">
-                <div class="result"><label> { "Part 1 Elapsed: " } </label> { format_duration(self.elapsed_part_1) }</div>
-                <div class="result"><label> { "Part 2 Elapsed: " } </label> { format_duration(self.elapsed_part_2) }</div>
+                <div class="result"><label> { "Part 1 Elapsed: " } </label> { format_timed(self.part1.as_ref()) }</div>
+                <div class="result"><label> { "Part 2 Elapsed: " } </label> { format_timed(self.part2.as_ref()) }</div>
                 <div class="result"><label> { "Elapsed: " } </label> { format_duration(self.elapsed_total) }</div>
             </div>
             </label>
+            { view_overlays(&self.drawn, &self.overlays) }
+            { view_report(ctx.props().puzzle, &self.report) }
+            <History runs={self.saved.runs.clone()} {onclear} />
+            <SelfTest checks={self.checks.clone()} {onrun} />
                 </>
         }
     }
 }
+
+/// Lap timer returning the time elapsed since it was last called.
+fn laps() -> impl FnMut() -> Duration {
+    let mut last = Instant::now();
+    move || {
+        let now = Instant::now();
+        let elapsed = now - last;
+        last = now;
+        elapsed
+    }
+}
+
+fn view_result(result: Option<&Result<Timed, ParseError>>) -> Html {
+    match result {
+        Some(Ok(timed)) => html! { timed.answer.clone() },
+        Some(Err(err)) => html! { <span class="error">{ format!("invalid input: {err}") }</span> },
+        None => html! {},
+    }
+}
+
+/// Drawings of the answers over the map of `input`.
+pub fn view_overlays(input: &AttrValue, overlays: &[(Part, Overlay)]) -> Html {
+    if overlays.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div id="overlays" class="output">
+            { for overlays.iter().map(|(part, overlay)| html! {
+                <label> { format!("Part {part}: ") }
+                    <Canvas input={input.clone()} overlay={overlay.clone()} />
+                </label>
+            }) }
+        </div>
+    }
+}
+
+fn view_report(puzzle: &dyn Puzzle, report: &Report) -> Html {
+    if report.records.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div id="report" class="output">
+            <label> { format!("Report of {} parts: ", report.records.len()) } </label>
+            { for Format::ALL.into_iter().map(|format| {
+                let download = format!("day{:02}.{}", puzzle.day(), format.extension());
+                html! {
+                    <a href={format.data_url(report)} {download}>{ format.extension().to_uppercase() }</a>
+                }
+            }) }
+        </div>
+    }
+}
@@ -0,0 +1,104 @@
+//! Input read from a file, picked or dropped on the page.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{File, FileList, FileReader, HtmlInputElement};
+
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct PickerProps {
+    /// Called with the text of the file read.
+    pub onload: Callback<String>,
+}
+
+/// File picker and drop zone reading the input from a file.
+#[function_component]
+pub fn Picker(props: &PickerProps) -> Html {
+    let dragging = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let load = {
+        let onload = props.onload.clone();
+        let error = error.clone();
+        move |files: Option<FileList>| match files.and_then(|files| files.get(0)) {
+            Some(file) => {
+                error.set(None);
+                let onerror = error.setter();
+                read(
+                    &file,
+                    onload.clone(),
+                    Callback::from(move |err| onerror.set(Some(err))),
+                );
+            }
+            None => error.set(Some("no file".to_string())),
+        }
+    };
+
+    let onchange = {
+        let load = load.clone();
+        Callback::from(move |event: Event| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            load(input.files());
+            input.set_value("");
+        })
+    };
+    let ondragover = {
+        let dragging = dragging.clone();
+        Callback::from(move |event: DragEvent| {
+            event.prevent_default();
+            dragging.set(true);
+        })
+    };
+    let ondragleave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| dragging.set(false))
+    };
+    let ondrop = {
+        let dragging = dragging.clone();
+        Callback::from(move |event: DragEvent| {
+            event.prevent_default();
+            dragging.set(false);
+            load(event.data_transfer().and_then(|data| data.files()));
+        })
+    };
+
+    html! {
+        <div class={classes!("dropzone", dragging.then_some("dragging"))} {ondragover} {ondragleave} {ondrop}>
+            <label> { "Input file: " }
+                <input type="file" {onchange} />
+            </label>
+            { " or drop it here" }
+            if let Some(err) = &*error {
+                <span class="error">{ format!(" {err}") }</span>
+            }
+        </div>
+    }
+}
+
+/// Read `file` as text, calling `onload` with the text or `onerror` if
+/// it cannot be read.
+fn read(file: &File, onload: Callback<String>, onerror: Callback<String>) {
+    let Ok(reader) = FileReader::new() else {
+        onerror.emit("cannot read files".to_string());
+        return;
+    };
+
+    let error = format!("cannot read {}", file.name());
+    let done = reader.clone();
+    let failed = onerror.clone();
+    let message = error.clone();
+    let onloadend =
+        Closure::once_into_js(
+            move || match done.result().ok().and_then(|text| text.as_string()) {
+                Some(text) => onload.emit(text),
+                None => failed.emit(message),
+            },
+        );
+    reader.set_onloadend(Some(onloadend.unchecked_ref()));
+
+    if reader.read_as_text(file).is_err() {
+        reader.set_onloadend(None);
+        onerror.emit(error);
+    }
+}
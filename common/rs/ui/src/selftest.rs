@@ -0,0 +1,149 @@
+//! Self-test solving the examples of a day with their expected answers.
+
+use yew::prelude::*;
+
+use aoc::example::{Example, Outcome};
+
+/// Example with the outcome of its last run, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub example: Example,
+    pub outcome: Option<Outcome>,
+}
+
+impl Check {
+    /// Checks of `examples`, not run yet.
+    pub fn all(examples: impl IntoIterator<Item = Example>) -> Vec<Check> {
+        examples
+            .into_iter()
+            .map(|example| Check {
+                example,
+                outcome: None,
+            })
+            .collect()
+    }
+}
+
+/// Summary of the checks, as `3/4 passed`.
+pub fn summary(checks: &[Check]) -> String {
+    let passed = checks
+        .iter()
+        .filter(|check| check.outcome.as_ref().is_some_and(Outcome::passed))
+        .count();
+    format!("{passed}/{} passed", checks.len())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SelfTestProps {
+    pub checks: Vec<Check>,
+    /// Whether the examples without an outcome are still solving.
+    #[prop_or_default]
+    pub running: bool,
+    /// Called to run every example again.
+    pub onrun: Callback<()>,
+}
+
+/// Table of the examples of the day, with their outcomes and timings.
+#[function_component]
+pub fn SelfTest(props: &SelfTestProps) -> Html {
+    if props.checks.is_empty() {
+        return html! {};
+    }
+
+    fn view_outcome(outcome: Option<&Outcome>, running: bool) -> Html {
+        match outcome {
+            Some(Outcome::Passed(timed)) => html! {
+                <>
+                    <td>{ timed.answer.clone() }</td>
+                    <td class="passed">{ "pass" }</td>
+                    <td>{ format!("{}us", timed.elapsed().as_micros()) }</td>
+                </>
+            },
+            Some(Outcome::Failed(timed)) => html! {
+                <>
+                    <td>{ timed.answer.clone() }</td>
+                    <td class="error">{ "fail" }</td>
+                    <td>{ format!("{}us", timed.elapsed().as_micros()) }</td>
+                </>
+            },
+            Some(Outcome::Invalid(err)) => html! {
+                <>
+                    <td class="error">{ format!("invalid input: {err}") }</td>
+                    <td class="error">{ "fail" }</td>
+                    <td>{ "-" }</td>
+                </>
+            },
+            None if running => html! {
+                <>
+                    <td>{ "Running..." }</td>
+                    <td>{ "-" }</td>
+                    <td>{ "-" }</td>
+                </>
+            },
+            None => html! {
+                <>
+                    <td>{ "-" }</td>
+                    <td>{ "-" }</td>
+                    <td>{ "-" }</td>
+                </>
+            },
+        }
+    }
+
+    let onclick = props.onrun.reform(|_| ());
+
+    html! {
+        <div id="selftest" class="output">
+            <label> { format!("Self-test, {}: ", summary(&props.checks)) }
+                <button {onclick} disabled={props.running}>{ "Run examples" }</button>
+            </label>
+            <table>
+                <tr>
+                    <th>{ "Example" }</th>
+                    <th>{ "Part" }</th>
+                    <th>{ "Expected" }</th>
+                    <th>{ "Answer" }</th>
+                    <th>{ "Result" }</th>
+                    <th>{ "Elapsed" }</th>
+                </tr>
+                { for props.checks.iter().map(|check| html! {
+                    <tr>
+                        <td>{ check.example.name }</td>
+                        <td>{ check.example.part.to_string() }</td>
+                        <td>{ check.example.answer }</td>
+                        { view_outcome(check.outcome.as_ref(), props.running) }
+                    </tr>
+                }) }
+            </table>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc::{Part, Timed};
+
+    use super::*;
+
+    #[test]
+    fn summary_of_checks() {
+        let example = Example {
+            name: "example1",
+            input: "1",
+            part: Part::One,
+            answer: "1",
+        };
+        let mut checks = Check::all([example; 3]);
+        assert_eq!(summary(&checks), "0/3 passed");
+
+        checks[0].outcome = Some(example.outcome(Ok(Timed {
+            answer: "1".to_string(),
+            parse: Duration::ZERO,
+            solve: Duration::ZERO,
+        })));
+        checks[1].outcome = Some(example.outcome(Err("invalid".to_string())));
+        assert_eq!(summary(&checks), "1/3 passed");
+    }
+}
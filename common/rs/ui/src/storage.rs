@@ -0,0 +1,190 @@
+//! Inputs and runs of the days kept in the local storage of the browser.
+//!
+//! Every day has its own key, so the apps of the days and the web app of
+//! all of them share what was saved. Failing to save, as when the storage
+//! is full or disabled, only loses the history.
+
+use std::time::Duration;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use yew::prelude::*;
+
+use aoc::report::Record;
+use aoc::{Part, Timed};
+
+/// Runs kept for every day.
+pub const HISTORY: usize = 10;
+
+/// Run of the parts of a day on an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    pub input_hash: u64,
+    /// Parts solved, the invalid ones left out.
+    pub records: Vec<Record>,
+    /// Time spent solving both parts, if timed.
+    pub elapsed: Option<Duration>,
+}
+
+impl Run {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input_hash: aoc::input::hash(input),
+            records: vec![],
+            elapsed: None,
+        }
+    }
+
+    /// Answer of `part`, if solved.
+    pub fn timed(&self, part: Part) -> Option<Timed> {
+        self.records
+            .iter()
+            .find(|record| record.part == part)
+            .map(|record| Timed {
+                answer: record.answer.clone(),
+                parse: record.parse,
+                solve: record.solve,
+            })
+    }
+}
+
+/// What is saved of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Saved {
+    /// Input last run or read from a file.
+    pub input: Option<String>,
+    /// Last runs, the oldest first.
+    pub runs: Vec<Run>,
+}
+
+impl Saved {
+    fn key(day: u8) -> String {
+        format!("aoc2023/day{day:02}")
+    }
+
+    /// What is saved of `day`, nothing if missing or unreadable.
+    pub fn load(day: u8) -> Self {
+        LocalStorage::get(Self::key(day)).unwrap_or_default()
+    }
+
+    pub fn save(&self, day: u8) {
+        // The history is only a convenience, lost if it cannot be saved.
+        let _ = LocalStorage::set(Self::key(day), self);
+    }
+
+    /// Forget everything saved of `day`.
+    pub fn clear(day: u8) {
+        LocalStorage::delete(Self::key(day));
+    }
+
+    /// Add `run`, dropping the oldest ones beyond [`HISTORY`].
+    pub fn push(&mut self, run: Run) {
+        self.runs.push(run);
+        let excess = self.runs.len().saturating_sub(HISTORY);
+        self.runs.drain(..excess);
+    }
+
+    /// Every record of the runs, for the report.
+    pub fn records(&self) -> Vec<Record> {
+        self.runs
+            .iter()
+            .flat_map(|run| run.records.iter().cloned())
+            .collect()
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct HistoryProps {
+    pub runs: Vec<Run>,
+    /// Called to forget the history.
+    pub onclear: Callback<()>,
+}
+
+/// Table of the last runs, the newest first, to compare them.
+#[function_component]
+pub fn History(props: &HistoryProps) -> Html {
+    if props.runs.is_empty() {
+        return html! {};
+    }
+
+    fn view_part(run: &Run, part: Part) -> Html {
+        match run.timed(part) {
+            Some(timed) => html! {
+                <td>{ format!("{} ({}us)", timed.answer, timed.elapsed().as_micros()) }</td>
+            },
+            None => html! { <td>{ "-" }</td> },
+        }
+    }
+
+    let onclick = props.onclear.reform(|_| ());
+
+    html! {
+        <div id="history" class="output">
+            <label> { format!("Last {} runs: ", props.runs.len()) }
+                <button {onclick}>{ "Clear" }</button>
+            </label>
+            <table>
+                <tr>
+                    <th>{ "Input" }</th>
+                    <th>{ "Part 1" }</th>
+                    <th>{ "Part 2" }</th>
+                    <th>{ "Elapsed" }</th>
+                </tr>
+                { for props.runs.iter().rev().map(|run| html! {
+                    <tr>
+                        <td>{ format!("{:016x}", run.input_hash) }</td>
+                        { view_part(run, Part::One) }
+                        { view_part(run, Part::Two) }
+                        <td>{ run.elapsed.map_or_else(|| "-".to_string(), |elapsed| format!("{}us", elapsed.as_micros())) }</td>
+                    </tr>
+                }) }
+            </table>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(part: Part, answer: &str) -> Record {
+        Record {
+            day: 1,
+            part,
+            variant: String::new(),
+            input_hash: 0,
+            parse: Duration::from_micros(1),
+            solve: Duration::from_micros(2),
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn history() {
+        let mut saved = Saved::default();
+        for i in 0..HISTORY + 2 {
+            let mut run = Run::new(&i.to_string());
+            run.records.push(record(Part::One, &i.to_string()));
+            saved.push(run);
+        }
+        assert_eq!(saved.runs.len(), HISTORY);
+        assert_eq!(saved.runs[0].input_hash, aoc::input::hash("2"));
+        assert_eq!(saved.records().len(), HISTORY);
+    }
+
+    #[test]
+    fn timed() {
+        let mut run = Run::new("input");
+        run.records.push(record(Part::Two, "42"));
+        assert_eq!(run.timed(Part::One), None);
+        assert_eq!(
+            run.timed(Part::Two),
+            Some(Timed {
+                answer: "42".to_string(),
+                parse: Duration::from_micros(1),
+                solve: Duration::from_micros(2),
+            })
+        );
+    }
+}
@@ -1,72 +1,301 @@
 #![deny(clippy::pedantic)]
 
-use std::time::Duration;
+use std::collections::VecDeque;
 use std::marker::PhantomData;
-use std::fmt::Debug;
+use std::time::Duration;
+
+use gloo_timers::callback::Timeout;
 
 use instant::Instant;
 
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 
 use yew::prelude::*;
 
+use aoc::input::Stats;
+use aoc::progress::Progress;
+use aoc::report::{Format, Record, Report};
+use aoc::{Example, Overlay, ParseError, Part, Solution, Timed};
+
 use gloo_console::log;
-use gloo_worker::{HandlerId, Spawnable, Worker, WorkerBridge, WorkerScope};
+
+use ui::picker::Picker;
+use ui::selftest::{Check, SelfTest};
+use ui::storage::{History, Run, Saved};
+use ui::view_overlays;
+
+mod worker;
+
+use worker::{Bridge, FromWorker};
 
 pub trait Function
-where Self: 'static,
+where
+    Self: 'static,
 {
-    fn f(input: &str) -> impl Debug;
+    /// Day of the puzzle.
+    const DAY: u8;
+
+    /// Part solved.
+    const PART: Part;
+
+    /// Enabled cargo features of the day choosing other solvers.
+    const FEATURES: &'static [&'static str];
+
+    /// Examples of the puzzle with their answers, those of
+    /// [`Function::PART`] checked by the self-test.
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Solve `input` with the drawing of the answer over its map, timing
+    /// parsing and solving with `lap` as [`Solution::try_solve_overlay`]
+    /// does.
+    ///
+    /// # Errors
+    /// Return an error if the input is invalid
+    fn f(
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError>;
+
+    /// Like [`Function::f`], calling `progress` with the progress reported
+    /// by the solver with [`aoc::progress::report`].
+    ///
+    /// # Errors
+    /// Return an error if the input is invalid
+    fn f_with_progress(
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+        progress: impl Fn(Progress) + 'static,
+    ) -> Result<(Timed, Option<Overlay>), ParseError> {
+        aoc::progress::watch(progress, || Self::f(input, lap))
+    }
 }
 
-pub struct Solve<F>(PhantomData<F>);
+/// [`Function`] solving part 1 of a [`Solution`].
+pub struct Part1<S>(PhantomData<S>);
 
-impl<F: Function> Worker for Solve<F> {
-    type Input = String;
-    type Message = ();
-    type Output = (String, Duration);
+impl<S: Solution + 'static> Function for Part1<S> {
+    const DAY: u8 = S::DAY;
+    const PART: Part = Part::One;
+    const FEATURES: &'static [&'static str] = S::FEATURES;
+    const EXAMPLES: &'static [Example] = S::EXAMPLES;
 
-    fn create(_scope: &WorkerScope<Self>) -> Self {
-        Self(PhantomData)
+    fn f(
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError> {
+        S::try_solve_overlay(Part::One, input, lap)
     }
+}
 
-    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+/// [`Function`] solving part 2 of a [`Solution`].
+pub struct Part2<S>(PhantomData<S>);
 
-    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
-        let now = Instant::now();
-        let result = format!("{:?}", F::f(&msg));
-        let elapsed = now.elapsed();
-        scope.respond(id, (result, elapsed));
+impl<S: Solution + 'static> Function for Part2<S> {
+    const DAY: u8 = S::DAY;
+    const PART: Part = Part::Two;
+    const FEATURES: &'static [&'static str] = S::FEATURES;
+    const EXAMPLES: &'static [Example] = S::EXAMPLES;
+
+    fn f(
+        input: &str,
+        lap: &mut dyn FnMut() -> Duration,
+    ) -> Result<(Timed, Option<Overlay>), ParseError> {
+        S::try_solve_overlay(Part::Two, input, lap)
+    }
+}
+
+/// Answer with the parse and solve times, as sent back by [`Solve`].
+pub type Solved = Result<(String, Duration, Duration), String>;
+
+/// Web worker solving with `F`.
+pub struct Solve<F>(PhantomData<F>);
+
+impl<F: Function> Solve<F> {
+    /// Serve the solves of `F`, from the `main` of the worker.
+    pub fn register() {
+        worker::register::<F>();
     }
 }
 
+/// Time given to the solves when not told otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Properties, PartialEq)]
 pub struct ModelProps {
     input: String,
+    workers: [String; 2],
+    timeout: Duration,
 }
 
 impl ModelProps {
+    /// Props of a model solving the parts in the `solve1` and `solve2`
+    /// workers of the page, within [`DEFAULT_TIMEOUT`].
     #[must_use]
     pub fn new(input: String) -> Self {
-        Self { input }
+        Self {
+            input,
+            workers: ["./solve1.js".to_string(), "./solve2.js".to_string()],
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Solve the parts in the workers loaded from `solve_1` and `solve_2`
+    /// instead, as when more days share the page.
+    #[must_use]
+    pub fn with_workers(self, solve_1: impl Into<String>, solve_2: impl Into<String>) -> Self {
+        Self {
+            workers: [solve_1.into(), solve_2.into()],
+            ..self
+        }
+    }
+
+    /// Stop the solves taking longer than `timeout`, unless changed in
+    /// the page.
+    #[must_use]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
     }
 }
 
 pub enum Msg {
-    Run(String),
-    Solve1(String, Duration),
-    Solve2(String, Duration),
+    Run(String, Duration),
+    Progress(Part, u32, String, u8),
+    Overlay(Part, u32, Overlay),
+    Solved(Part, u32, Solved),
+    Cancel,
+    Timeout(u32, Duration),
+    /// Input typed in the page.
+    Edit(String),
+    /// Input read from a file.
+    Load(String),
+    /// Forget the saved input and runs.
+    Clear,
+    /// Solve the examples of the day.
+    SelfTest,
+    Checked(Part, u32, Solved),
+}
+
+/// State of the solve of a part.
+enum Status {
+    NotRun,
+    /// Solving, with the last progress reported if any.
+    Running(Option<(String, u8)>),
+    Solved(String, Duration, Duration),
+    Invalid(String),
+    Cancelled,
+    TimedOut(Duration),
+}
+
+/// Worker solving a part, respawned to stop a solve.
+struct Solver {
+    path: String,
+    bridge: Bridge,
+    /// Times the worker was spawned, to drop the answers of the previous
+    /// ones.
+    spawned: u32,
+    status: Status,
+    /// Drawing of the answer, sent before it.
+    overlay: Option<Overlay>,
+}
+
+impl Solver {
+    fn spawn<C: Component<Message = Msg>>(
+        ctx: &Context<C>,
+        part: Part,
+        path: &str,
+        spawned: u32,
+    ) -> Bridge {
+        let link = ctx.link().clone();
+        Bridge::spawn(path, move |message| match message {
+            FromWorker::Progress(phase, percent) => {
+                link.send_message(Msg::Progress(part, spawned, phase, percent));
+            }
+            FromWorker::Overlay(overlay) => {
+                link.send_message(Msg::Overlay(part, spawned, overlay));
+            }
+            FromWorker::Solved(r) => {
+                log!(format!("solve_{part} {r:?}"));
+                link.send_message(Msg::Solved(part, spawned, r));
+            }
+        })
+    }
+
+    /// Worker of `part` solving the examples of the `tests` self-test.
+    fn spawn_tester<C: Component<Message = Msg>>(
+        ctx: &Context<C>,
+        part: Part,
+        path: &str,
+        tests: u32,
+    ) -> Bridge {
+        let link = ctx.link().clone();
+        Bridge::spawn(path, move |message| {
+            if let FromWorker::Solved(r) = message {
+                link.send_message(Msg::Checked(part, tests, r));
+            }
+        })
+    }
+
+    /// Solver of `part`, showing its answer in `last` if any.
+    fn new<C: Component<Message = Msg>>(
+        ctx: &Context<C>,
+        part: Part,
+        path: &str,
+        last: Option<&Run>,
+    ) -> Self {
+        let status = last
+            .and_then(|run| run.timed(part))
+            .map_or(Status::NotRun, |timed| {
+                Status::Solved(timed.answer, timed.parse, timed.solve)
+            });
+
+        Self {
+            path: path.to_string(),
+            bridge: Self::spawn(ctx, part, path, 0),
+            spawned: 0,
+            status,
+            overlay: None,
+        }
+    }
+
+    fn running(&self) -> bool {
+        matches!(self.status, Status::Running(_))
+    }
+
+    /// Terminate the worker if solving, dropping the answer, and start
+    /// another.
+    fn stop<C: Component<Message = Msg>>(&mut self, ctx: &Context<C>, part: Part, status: Status) {
+        if self.running() {
+            self.spawned += 1;
+            self.bridge = Self::spawn(ctx, part, &self.path, self.spawned);
+            self.status = status;
+        }
+    }
 }
 
 pub struct Model<F1: Function, F2: Function> {
     input_ref: NodeRef,
-    part1: Option<String>,
-    part2: Option<String>,
+    timeout_ref: NodeRef,
     input: String,
-    elapsed_part_1: Option<Duration>,
-    elapsed_part_2: Option<Duration>,
-    bridge_solve_1: WorkerBridge<Solve<F1>>,
-    bridge_solve_2: WorkerBridge<Solve<F2>>,
+    /// Runs started, to drop the timeouts of the previous ones.
+    runs: u32,
+    /// Run being solved and when it started, saved when both parts are
+    /// done, or when stopped with an answer.
+    run: Option<(Run, Instant)>,
+    timeout: Option<Timeout>,
+    solvers: [Solver; 2],
+    report: Report,
+    saved: Saved,
+    /// Input of the last run, drawn under the overlays.
+    drawn: AttrValue,
+    checks: Vec<Check>,
+    /// Workers solving the examples, apart from the solvers not to stop
+    /// them, while a self-test runs.
+    testers: Option<[Bridge; 2]>,
+    /// Self-tests started, to drop the answers of the previous ones.
+    tests: u32,
+    /// Checks waiting for the answers of the testers, in order.
+    pending: [VecDeque<usize>; 2],
+    _functions: PhantomData<(F1, F2)>,
 }
 
 impl<F1: Function, F2: Function> Component for Model<F1, F2> {
@@ -74,95 +303,373 @@ impl<F1: Function, F2: Function> Component for Model<F1, F2> {
     type Properties = ModelProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let input = ctx.props().input.clone();
-
-        let this = ctx.link().clone();
-        let bridge_solve_1 = Solve::<F1>::spawner()
-            .callback(move |(r, d)| {
-                log!(format!("solve_1 {r} {d:?}"));
-                this.clone().send_message(Msg::Solve1(r, d));
-            })
-            .spawn("./solve1.js");
-
-        let this = ctx.link().clone();
-        let bridge_solve_2 = Solve::<F2>::spawner()
-            .callback(move |(r, d)| {
-                log!(format!("solve_2 {r} {d:?}"));
-                this.clone().send_message(Msg::Solve2(r, d));
-            })
-            .spawn("./solve2.js");
+        let saved = Saved::load(F1::DAY);
+        let input = saved
+            .input
+            .clone()
+            .unwrap_or_else(|| ctx.props().input.clone());
+        let last = saved.runs.last();
+        let [solve_1, solve_2] = &ctx.props().workers;
 
         Self {
             input_ref: NodeRef::default(),
-            part1: None,
-            part2: None,
+            timeout_ref: NodeRef::default(),
             input,
-            elapsed_part_1: None,
-            elapsed_part_2: None,
-            bridge_solve_1,
-            bridge_solve_2,
+            runs: 0,
+            run: None,
+            timeout: None,
+            solvers: [
+                Solver::new(ctx, Part::One, solve_1, last),
+                Solver::new(ctx, Part::Two, solve_2, last),
+            ],
+            report: Report {
+                records: saved.records(),
+            },
+            saved,
+            drawn: AttrValue::default(),
+            checks: Check::all(
+                F1::EXAMPLES
+                    .iter()
+                    .filter(|example| example.part == F1::PART)
+                    .chain(
+                        F2::EXAMPLES
+                            .iter()
+                            .filter(|example| example.part == F2::PART),
+                    )
+                    .copied(),
+            ),
+            testers: None,
+            tests: 0,
+            pending: Default::default(),
+            _functions: PhantomData,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::Run(input) => {
+            Msg::Run(input, timeout) => {
+                self.stop(ctx, || Status::Cancelled);
                 self.input = input;
+                self.runs += 1;
 
-                self.part1 = Some("Running...".to_string());
-                self.part2 = Some("Running...".to_string());
+                self.saved.input = Some(self.input.clone());
+                self.saved.save(F1::DAY);
+                self.run = Some((Run::new(&self.input), Instant::now()));
 
-                self.bridge_solve_1.send(self.input.clone());
-                self.bridge_solve_2.send(self.input.clone());
+                for solver in &mut self.solvers {
+                    solver.bridge.send(self.input.clone());
+                    solver.status = Status::Running(None);
+                    solver.overlay = None;
+                }
+                self.drawn = AttrValue::from(self.input.clone());
 
+                let link = ctx.link().clone();
+                let run = self.runs;
+                let millis = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+                self.timeout = Some(Timeout::new(millis, move || {
+                    link.send_message(Msg::Timeout(run, timeout));
+                }));
+
+                true
+            }
+            Msg::Progress(part, spawned, phase, percent) => {
+                let solver = &mut self.solvers[part_index(part)];
+                if solver.spawned != spawned || !solver.running() {
+                    return false;
+                }
+                solver.status = Status::Running(Some((phase, percent)));
+                true
+            }
+            Msg::Overlay(part, spawned, overlay) => {
+                let solver = &mut self.solvers[part_index(part)];
+                if solver.spawned != spawned || !solver.running() {
+                    return false;
+                }
+                solver.overlay = Some(overlay);
+                false
+            }
+            Msg::Solved(part, spawned, result) => {
+                let solver = &self.solvers[part_index(part)];
+                if solver.spawned != spawned || !solver.running() {
+                    return false;
+                }
+                match part {
+                    Part::One => self.solved::<F1>(result),
+                    Part::Two => self.solved::<F2>(result),
+                }
+                if !self.solvers.iter().any(Solver::running) {
+                    self.timeout = None;
+                    self.finish(true);
+                }
+                true
+            }
+            Msg::Cancel => {
+                self.stop(ctx, || Status::Cancelled);
                 true
             }
-            Msg::Solve1(result, elapsed) => {
-                self.part1 = Some(result);
-                self.elapsed_part_1 = Some(elapsed);
+            Msg::Timeout(run, timeout) => {
+                if run != self.runs {
+                    return false;
+                }
+                self.stop(ctx, || Status::TimedOut(timeout));
+                true
+            }
+            Msg::Edit(input) => {
+                self.input = input;
+                true
+            }
+            Msg::Load(input) => {
+                self.saved.input = Some(input.clone());
+                self.saved.save(F1::DAY);
+
+                self.input = input;
                 true
             }
-            Msg::Solve2(result, elapsed) => {
-                self.part2 = Some(result);
-                self.elapsed_part_2 = Some(elapsed);
+            Msg::Clear => {
+                Saved::clear(F1::DAY);
+                self.saved = Saved::default();
                 true
             }
+            Msg::SelfTest => self.self_test(ctx),
+            Msg::Checked(part, tests, result) => {
+                if tests != self.tests {
+                    return false;
+                }
+                self.checked(part, result)
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let link = ctx.link();
         let input_ref = self.input_ref.clone();
+        let timeout_ref = self.timeout_ref.clone();
+        let default_timeout = ctx.props().timeout;
 
         let onclick = link.batch_callback(move |_| {
             let input = input_ref.cast::<HtmlInputElement>();
-            input.map(|input| Msg::Run(input.value()))
+            let timeout = timeout_ref
+                .cast::<HtmlInputElement>()
+                .and_then(|timeout| parse_timeout(&timeout.value()))
+                .unwrap_or(default_timeout);
+            input.map(|input| Msg::Run(input.value(), timeout))
+        });
+        let oncancel = link.callback(|_| Msg::Cancel);
+        let oninput = link.callback(|event: InputEvent| {
+            Msg::Edit(event.target_unchecked_into::<HtmlTextAreaElement>().value())
         });
+        let onload = link.callback(Msg::Load);
+        let onclear = link.callback(|()| Msg::Clear);
+        let onrun = link.callback(|()| Msg::SelfTest);
+        let running = self.solvers.iter().any(Solver::running);
 
         html! {
             <>
                 <label for="input"> { "Input: " }
-            <textarea id="input" ref={self.input_ref.clone()} rows="4" cols="50" value={self.input.clone()} />
+            <textarea id="input" ref={self.input_ref.clone()} rows="4" cols="50" value={self.input.clone()} {oninput} />
+                </label>
+                <Picker {onload} />
+                <div class="stats">{ Stats::of(&self.input).to_string() }</div>
+                <label for="timeout"> { "Timeout (s): " }
+            <input id="timeout" type="number" min="1" ref={self.timeout_ref.clone()} value={default_timeout.as_secs().to_string()} />
                 </label>
                 <button {onclick}>{ "\u{23F5}" }</button>
+                <button onclick={oncancel} disabled={!running}>{ "\u{23F9}" }</button>
                 <label for="results"> { "Results: " }
             <div id="results" class="output">
-                <div class="result"><label> { "Part 1: " } </label> { self.part1.clone() }</div>
-                <div class="result"><label> { "Part 2: " } </label> { self.part2.clone() }</div>
+                <div class="result"><label> { "Part 1: " } </label> { view_result(&self.solvers[0].status) }</div>
+                <div class="result"><label> { "Part 2: " } </label> { view_result(&self.solvers[1].status) }</div>
             </div>
             <div id="elapsed" class="output">
-                <div class="result"><label> { "Part 1 Elapsed: " } </label> { format_duration(self.elapsed_part_1) }</div>
-                <div class="result"><label> { "Part 2 Elapsed: " } </label> { format_duration(self.elapsed_part_2) }</div>
+                <div class="result"><label> { "Part 1 Elapsed: " } </label> { format_elapsed(&self.solvers[0].status) }</div>
+                <div class="result"><label> { "Part 2 Elapsed: " } </label> { format_elapsed(&self.solvers[1].status) }</div>
             </div>
             </label>
+            { view_overlays(&self.drawn, &self.overlays()) }
+            { view_report(F1::DAY, &self.report) }
+            <History runs={self.saved.runs.clone()} {onclear} />
+            <SelfTest checks={self.checks.clone()} running={self.testers.is_some()} {onrun} />
                 </>
         }
     }
 }
 
+impl<F1: Function, F2: Function> Model<F1, F2> {
+    /// Stop the parts still solving, with the status made by `status`.
+    fn stop(&mut self, ctx: &Context<Self>, status: impl Fn() -> Status) {
+        for (solver, part) in self.solvers.iter_mut().zip(Part::ALL) {
+            solver.stop(ctx, part, status());
+        }
+        self.timeout = None;
+        self.finish(false);
+    }
+
+    /// Save the run being solved in the history, timed if both parts are
+    /// `done`, dropped if stopped before any answer.
+    fn finish(&mut self, done: bool) {
+        let Some((mut run, started)) = self.run.take() else {
+            return;
+        };
+        if done {
+            run.elapsed = Some(started.elapsed());
+        } else if run.records.is_empty() {
+            return;
+        }
+        self.saved.push(run);
+        self.saved.save(F1::DAY);
+    }
+
+    /// Solve the examples again in new testers, returning whether there
+    /// are any.
+    fn self_test(&mut self, ctx: &Context<Self>) -> bool {
+        if self.checks.is_empty() {
+            return false;
+        }
+        self.tests += 1;
+        let [solve_1, solve_2] = &ctx.props().workers;
+        let testers = [(Part::One, solve_1), (Part::Two, solve_2)]
+            .map(|(part, path)| Solver::spawn_tester(ctx, part, path, self.tests));
+
+        self.pending = Default::default();
+        for (i, check) in self.checks.iter_mut().enumerate() {
+            let part = part_index(check.example.part);
+            testers[part].send(check.example.input.to_string());
+            self.pending[part].push_back(i);
+            check.outcome = None;
+        }
+        self.testers = Some(testers);
+        true
+    }
+
+    /// Show the outcome of the next example of `part`, stopping the
+    /// testers after the last one.
+    fn checked(&mut self, part: Part, result: Solved) -> bool {
+        let Some(i) = self.pending[part_index(part)].pop_front() else {
+            return false;
+        };
+        let check = &mut self.checks[i];
+        check.outcome = Some(
+            check
+                .example
+                .outcome(result.map(|(answer, parse, solve)| Timed {
+                    answer,
+                    parse,
+                    solve,
+                })),
+        );
+        if self.pending.iter().all(VecDeque::is_empty) {
+            self.testers = None;
+        }
+        true
+    }
+
+    /// Drawings of the answers solved.
+    fn overlays(&self) -> Vec<(Part, Overlay)> {
+        Part::ALL
+            .into_iter()
+            .zip(&self.solvers)
+            .filter(|(_, solver)| matches!(solver.status, Status::Solved(..)))
+            .filter_map(|(part, solver)| Some((part, solver.overlay.clone()?)))
+            .collect()
+    }
+
+    /// Show the answer of `F`, recording it in the report and the history.
+    fn solved<F: Function>(&mut self, result: Solved) {
+        let status = match result {
+            Ok((answer, parse, solve)) => {
+                // the input may have been edited since the run started
+                if let Some((run, _)) = &mut self.run {
+                    let record = Record {
+                        day: F::DAY,
+                        part: F::PART,
+                        variant: F::FEATURES.join("+"),
+                        input_hash: run.input_hash,
+                        parse,
+                        solve,
+                        answer: answer.clone(),
+                    };
+                    run.records.push(record.clone());
+                    self.report.records.push(record);
+                }
+                Status::Solved(answer, parse, solve)
+            }
+            Err(err) => Status::Invalid(err),
+        };
+
+        self.solvers[part_index(F::PART)].status = status;
+    }
+}
+
+fn part_index(part: Part) -> usize {
+    match part {
+        Part::One => 0,
+        Part::Two => 1,
+    }
+}
+
+/// Timeout in seconds, as typed in the page.
+fn parse_timeout(secs: &str) -> Option<Duration> {
+    secs.trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+}
+
+fn view_result(status: &Status) -> Html {
+    match status {
+        Status::NotRun => html! {},
+        Status::Running(None) => html! { "Running..." },
+        Status::Running(Some((phase, percent))) => html! {
+            <>
+                <progress max="100" value={percent.to_string()} />
+                { format!(" {phase} {percent}%") }
+            </>
+        },
+        Status::Solved(answer, _, _) => html! { answer },
+        Status::Invalid(err) => {
+            html! { <span class="error">{ format!("invalid input: {err}") }</span> }
+        }
+        Status::Cancelled => html! { <span class="error">{ "cancelled" }</span> },
+        Status::TimedOut(timeout) => html! {
+            <span class="error">{ format!("timed out after {}s", timeout.as_secs()) }</span>
+        },
+    }
+}
+
 fn format_duration(elapsed: Option<Duration>) -> String {
     elapsed.map_or_else(
         || "not run".to_string(),
         |v| format!("{}ms ({}us)", v.as_millis(), v.as_micros()),
     )
 }
+
+fn format_elapsed(status: &Status) -> String {
+    match status {
+        Status::Solved(_, parse, solve) => format!(
+            "{} (parse {}us, solve {}us)",
+            format_duration(Some(*parse + *solve)),
+            parse.as_micros(),
+            solve.as_micros(),
+        ),
+        _ => format_duration(None),
+    }
+}
+
+fn view_report(day: u8, report: &Report) -> Html {
+    if report.records.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div id="report" class="output">
+            <label> { format!("Report of {} parts: ", report.records.len()) } </label>
+            { for Format::ALL.into_iter().map(|format| {
+                let download = format!("day{day:02}.{}", format.extension());
+                html! {
+                    <a href={format.data_url(report)} {download}>{ format.extension().to_uppercase() }</a>
+                }
+            }) }
+        </div>
+    }
+}
@@ -0,0 +1,226 @@
+//! Web workers solving the parts, terminated to stop a solve.
+//!
+//! The workers of `gloo-worker` cannot be terminated while busy, as they
+//! are told to stop with a message only read once the solve returns. A
+//! [`Bridge`] owns its worker instead, and terminates it when dropped.
+//!
+//! The messages are text: the input to the worker, and back an empty
+//! message once the worker is loaded, then the progress reported while
+//! solving and a [`Solved`] for every input, after the [`Overlay`] of
+//! the answer if any.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use instant::Instant;
+
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, MessageEvent, Url, Worker};
+
+use aoc::progress::Progress;
+use aoc::Overlay;
+
+use crate::{Function, Solved};
+
+/// Message of a worker solving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FromWorker {
+    /// Phase of the solve and percentage done.
+    Progress(String, u8),
+    Overlay(Overlay),
+    Solved(Solved),
+}
+
+/// Message of the worker as text.
+pub(crate) fn encode(message: &FromWorker) -> String {
+    match message {
+        FromWorker::Progress(phase, percent) => format!("progress\t{percent}\t{phase}"),
+        FromWorker::Overlay(overlay) => format!("overlay\t{overlay}"),
+        FromWorker::Solved(Ok((answer, parse, solve))) => {
+            format!("ok\t{}\t{}\t{answer}", parse.as_nanos(), solve.as_nanos())
+        }
+        FromWorker::Solved(Err(err)) => format!("err\t{err}"),
+    }
+}
+
+/// Message of the worker from text.
+pub(crate) fn decode(text: &str) -> FromWorker {
+    let invalid = || FromWorker::Solved(Err(format!("invalid answer from worker: {text:?}")));
+    let nanos = |nanos: &str| nanos.parse().map(Duration::from_nanos).ok();
+    match text.split_once('\t') {
+        Some(("progress", progress)) => match progress.split_once('\t') {
+            Some((percent, phase)) => match percent.parse() {
+                Ok(percent) => FromWorker::Progress(phase.to_string(), percent),
+                Err(_) => invalid(),
+            },
+            None => invalid(),
+        },
+        Some(("overlay", overlay)) => match overlay.parse() {
+            Ok(overlay) => FromWorker::Overlay(overlay),
+            Err(_) => invalid(),
+        },
+        Some(("ok", solved)) => match solved.splitn(3, '\t').collect::<Vec<_>>()[..] {
+            [parse, solve, answer] => match (nanos(parse), nanos(solve)) {
+                (Some(parse), Some(solve)) => {
+                    FromWorker::Solved(Ok((answer.to_string(), parse, solve)))
+                }
+                _ => invalid(),
+            },
+            _ => invalid(),
+        },
+        Some(("err", err)) => FromWorker::Solved(Err(err.to_string())),
+        _ => invalid(),
+    }
+}
+
+/// Serve the solves of `F` in the worker running this program.
+pub(crate) fn register<F: Function>() {
+    let scope = js_sys::global().unchecked_into::<DedicatedWorkerGlobalScope>();
+
+    let responder = scope.clone();
+    let onmessage = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+        let input = event.data().as_string().unwrap_or_default();
+
+        let mut last = Instant::now();
+        let mut lap = || {
+            let now = Instant::now();
+            let elapsed = now - last;
+            last = now;
+            elapsed
+        };
+        let progress = {
+            let responder = responder.clone();
+            move |progress: Progress| {
+                let message = FromWorker::Progress(progress.phase.to_string(), progress.percent);
+                responder
+                    .post_message(&encode(&message).into())
+                    .expect_throw("cannot tell the progress");
+            }
+        };
+        let solved = match F::f_with_progress(&input, &mut lap, progress) {
+            Ok((timed, overlay)) => {
+                if let Some(overlay) = overlay {
+                    responder
+                        .post_message(&encode(&FromWorker::Overlay(overlay)).into())
+                        .expect_throw("cannot draw the answer");
+                }
+                Ok((timed.answer, timed.parse, timed.solve))
+            }
+            Err(err) => Err(err.to_string()),
+        };
+        responder
+            .post_message(&encode(&FromWorker::Solved(solved)).into())
+            .expect_throw("cannot answer");
+    });
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    scope
+        .post_message(&JsValue::from_str(""))
+        .expect_throw("cannot tell the worker is loaded");
+}
+
+/// Worker of a part, terminated when dropped.
+pub(crate) struct Bridge {
+    worker: Worker,
+    /// Inputs sent before the worker is loaded.
+    pending: Rc<RefCell<Option<Vec<String>>>>,
+    _onmessage: Closure<dyn Fn(MessageEvent)>,
+}
+
+impl Bridge {
+    /// Start the worker of the `trunk` script at `path`, calling
+    /// `callback` with every message.
+    pub(crate) fn spawn(path: &str, callback: impl Fn(FromWorker) + 'static) -> Self {
+        let script =
+            Url::new_with_base(path, &gloo_utils::window().location().href().unwrap_throw())
+                .expect_throw("invalid worker script")
+                .to_string();
+        let wasm = script.replace(".js", "_bg.wasm");
+
+        let shim =
+            Array::of1(&format!(r#"importScripts("{script}");wasm_bindgen("{wasm}");"#).into());
+        let options = BlobPropertyBag::new();
+        options.set_type("application/javascript");
+        let blob = Blob::new_with_str_sequence_and_options(&shim, &options).unwrap_throw();
+        let url = Url::create_object_url_with_blob(&blob).unwrap_throw();
+        let worker = Worker::new(&url).expect_throw("cannot start worker");
+
+        let pending = Rc::new(RefCell::new(Some(Vec::<String>::new())));
+        let onmessage = {
+            let worker = worker.clone();
+            let pending = pending.clone();
+            Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+                let text = event.data().as_string().unwrap_or_default();
+                if let Some(inputs) = pending.borrow_mut().take() {
+                    for input in inputs {
+                        worker.post_message(&input.into()).unwrap_throw();
+                    }
+                } else {
+                    callback(decode(&text));
+                }
+            })
+        };
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        Self {
+            worker,
+            pending,
+            _onmessage: onmessage,
+        }
+    }
+
+    /// Send `input` to solve, once the worker is loaded.
+    pub(crate) fn send(&self, input: String) {
+        match self.pending.borrow_mut().as_mut() {
+            Some(inputs) => inputs.push(input),
+            None => self.worker.post_message(&input.into()).unwrap_throw(),
+        }
+    }
+}
+
+impl Drop for Bridge {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        for message in [
+            FromWorker::Solved(Ok((
+                "a\tb\nc".to_string(),
+                Duration::from_micros(15),
+                Duration::from_secs(2),
+            ))),
+            FromWorker::Solved(Ok((String::new(), Duration::ZERO, Duration::ZERO))),
+            FromWorker::Solved(Err("line 1, column 3: expected a word: \"1\"".to_string())),
+            FromWorker::Progress("chain reaction".to_string(), 42),
+            FromWorker::Progress("min\tcut".to_string(), 100),
+            FromWorker::Overlay(Overlay::default()),
+            FromWorker::Overlay(
+                Overlay::default()
+                    .cells(aoc::overlay::Color::RED, [(1, 0)])
+                    .path(aoc::overlay::Color::BLUE, [(1, 0), (1, 5)]),
+            ),
+        ] {
+            assert_eq!(decode(&encode(&message)), message);
+        }
+        for text in [
+            "",
+            "ok\t1\tx\t42",
+            "progress\tfall",
+            "progress\t256\tfall",
+            "overlay\tpath",
+        ] {
+            assert!(matches!(decode(text), FromWorker::Solved(Err(_))));
+        }
+    }
+}
@@ -0,0 +1 @@
+web::page!(day01::Day01);
@@ -0,0 +1 @@
+web::page!(day02::Day02);
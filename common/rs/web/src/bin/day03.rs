@@ -0,0 +1 @@
+web::page!(day03::Day03);
@@ -0,0 +1 @@
+web::page!(day04::Day04);
@@ -0,0 +1 @@
+web::page!(day05::Day05);
@@ -0,0 +1 @@
+web::page!(day06::Day06);
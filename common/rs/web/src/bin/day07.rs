@@ -0,0 +1 @@
+web::page!(day07::Day07);
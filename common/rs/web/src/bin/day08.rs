@@ -0,0 +1 @@
+web::page!(day08::Day08);
@@ -0,0 +1 @@
+web::page!(day09::Day09);
@@ -0,0 +1 @@
+web::page!(day10::Day10);
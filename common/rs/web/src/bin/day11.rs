@@ -0,0 +1 @@
+web::page!(day11::Day11);
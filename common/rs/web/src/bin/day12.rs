@@ -0,0 +1 @@
+web::page!(day12::Day12);
@@ -0,0 +1 @@
+web::page!(day13::Day13);
@@ -0,0 +1 @@
+web::page!(day14::Day14);
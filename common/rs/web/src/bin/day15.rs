@@ -0,0 +1 @@
+web::page!(day15::Day15);
@@ -0,0 +1 @@
+web::page!(day16::Day16);
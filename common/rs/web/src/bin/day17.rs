@@ -0,0 +1 @@
+web::page!(day17::Day17);
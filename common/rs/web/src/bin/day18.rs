@@ -0,0 +1 @@
+web::page!(day18::Day18);
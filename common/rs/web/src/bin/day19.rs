@@ -0,0 +1 @@
+web::page!(day19::Day19);
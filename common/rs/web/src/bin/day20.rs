@@ -0,0 +1 @@
+web::page!(day20::Day20);
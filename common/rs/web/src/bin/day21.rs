@@ -0,0 +1 @@
+web::page!(day21::Day21);
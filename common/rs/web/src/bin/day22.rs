@@ -0,0 +1 @@
+web::page!(day22::Day22 in web::Day22Part1, web::Day22Part2);
@@ -0,0 +1,7 @@
+use web::Day22Part1;
+
+fn main() {
+    console_error_panic_hook::set_once();
+
+    rsui::Solve::<Day22Part1>::register();
+}
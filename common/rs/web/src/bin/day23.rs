@@ -0,0 +1 @@
+web::page!(day23::Day23 in web::Day23Part1, web::Day23Part2);
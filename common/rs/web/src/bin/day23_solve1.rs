@@ -0,0 +1,7 @@
+use web::Day23Part1;
+
+fn main() {
+    console_error_panic_hook::set_once();
+
+    rsui::Solve::<Day23Part1>::register();
+}
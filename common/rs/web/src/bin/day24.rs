@@ -0,0 +1 @@
+web::page!(day24::Day24);
@@ -0,0 +1 @@
+web::page!(day25::Day25 in web::Day25Part1, web::Day25Part2);
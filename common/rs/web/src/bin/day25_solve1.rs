@@ -0,0 +1,7 @@
+use web::Day25Part1;
+
+fn main() {
+    console_error_panic_hook::set_once();
+
+    rsui::Solve::<Day25Part1>::register();
+}
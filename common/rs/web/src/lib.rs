@@ -0,0 +1,340 @@
+//! Web app of every day, with a page for each day behind a hash route.
+//!
+//! `#/day/17` shows day 17 with its input, the other routes only the
+//! days to pick from. The app itself only routes and lists the days:
+//! every day is a wasm module of its own, `dayNN.js` made by [`page!`]
+//! with its solver and input, imported the first time its page is shown.
+//! Most days solve on the main thread with [`ui::Model`], days 22, 23 and
+//! 25 solve in the workers of [`rsui::Model`], started from their own
+//! `dayNN_solveN.js` scripts by the module of the day.
+
+#![deny(clippy::pedantic)]
+
+use gloo_events::EventListener;
+use wasm_bindgen::prelude::*;
+use web_sys::Element;
+
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use aoc::Solution;
+
+pub type Day22Part1 = rsui::Part1<day22::Day22>;
+
+pub type Day22Part2 = rsui::Part2<day22::Day22>;
+
+pub type Day23Part1 = rsui::Part1<day23::Day23>;
+
+pub type Day23Part2 = rsui::Part2<day23::Day23>;
+
+pub type Day25Part1 = rsui::Part1<day25::Day25>;
+
+pub type Day25Part2 = rsui::Part2<day25::Day25>;
+
+/// Day of the app, only its constants: the solvers are linked in the
+/// modules of the days.
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+}
+
+macro_rules! days {
+    ($($krate:ident :: $solution:ident),* $(,)?) => {
+        /// Every day, indexed by day number minus one.
+        pub static DAYS: [Day; 25] = [
+            $(Day {
+                day: <$krate::$solution as Solution>::DAY,
+                title: <$krate::$solution as Solution>::TITLE,
+            }),*
+        ];
+    };
+}
+
+days!(
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+);
+
+/// Module of a day page, exporting `mount` and `unmount` to show and hide
+/// its page in the app.
+///
+/// ```ignore
+/// web::page!(day17::Day17);
+/// web::page!(day22::Day22 in web::Day22Part1, web::Day22Part2);
+/// ```
+///
+/// The page solves on the main thread, or in the `dayNN_solve1.js` and
+/// `dayNN_solve2.js` workers with `in`.
+#[macro_export]
+macro_rules! page {
+    ($krate:ident :: $solution:ident) => {
+        $crate::page!(@mount ui::Model, ui::ModelProps {
+            input: $krate::INPUT.to_string(),
+            puzzle: &$krate::$solution,
+        });
+    };
+    ($krate:ident :: $solution:ident in $part1:ty, $part2:ty) => {
+        $crate::page!(@mount rsui::Model<$part1, $part2>,
+            rsui::ModelProps::new($krate::INPUT.to_string()).with_workers(
+                format!("./day{:02}_solve1.js", <$part1 as rsui::Function>::DAY),
+                format!("./day{:02}_solve2.js", <$part2 as rsui::Function>::DAY),
+            )
+        );
+    };
+    (@mount $model:ty, $props:expr) => {
+        thread_local! {
+            static PAGE: std::cell::RefCell<Option<yew::AppHandle<$model>>> =
+                std::cell::RefCell::default();
+        }
+
+        /// Show the page of the day in `root`.
+        #[wasm_bindgen::prelude::wasm_bindgen]
+        pub fn mount(root: web_sys::Element) {
+            unmount();
+            let page = yew::Renderer::<$model>::with_root_and_props(root, $props).render();
+            PAGE.with_borrow_mut(|shown| *shown = Some(page));
+        }
+
+        /// Remove the page of the day, stopping its workers.
+        #[wasm_bindgen::prelude::wasm_bindgen]
+        pub fn unmount() {
+            if let Some(page) = PAGE.with_borrow_mut(Option::take) {
+                page.destroy();
+            }
+        }
+
+        fn main() {
+            console_error_panic_hook::set_once();
+        }
+    };
+}
+
+#[wasm_bindgen(module = "/src/pages.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn show_day(path: &str, root: &Element) -> Result<JsValue, JsValue>;
+
+    async fn hide_day(path: &str) -> JsValue;
+}
+
+/// Script of the module of `day`.
+fn module(day: u8) -> String {
+    format!("./day{day:02}.js")
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct PageProps {
+    pub day: u8,
+}
+
+pub enum PageMsg {
+    Failed(String),
+}
+
+/// Page of a day, showing the page of its module once imported.
+pub struct Page {
+    root: NodeRef,
+    failed: Option<String>,
+}
+
+impl Component for Page {
+    type Message = PageMsg;
+    type Properties = PageProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            root: NodeRef::default(),
+            failed: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PageMsg::Failed(err) => {
+                self.failed = Some(err);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                if let Some(err) = &self.failed {
+                    <p class="error">{ format!("cannot load day {}: {err}", ctx.props().day) }</p>
+                }
+                <div ref={self.root.clone()} />
+            </>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        let Some(root) = self.root.cast::<Element>() else {
+            return;
+        };
+        let path = module(ctx.props().day);
+        let link = ctx.link().clone();
+        spawn_local(async move {
+            if let Err(err) = show_day(&path, &root).await {
+                let err = err.as_string().unwrap_or_else(|| format!("{err:?}"));
+                link.send_message(PageMsg::Failed(err));
+            }
+        });
+    }
+
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        let path = module(ctx.props().day);
+        spawn_local(async move {
+            hide_day(&path).await;
+        });
+    }
+}
+
+/// Page shown, from the hash of the location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Days,
+    Day(u8),
+}
+
+impl Route {
+    /// Route of `hash`, as `#/day/17`, the list of the days if unknown.
+    #[must_use]
+    pub fn parse(hash: &str) -> Self {
+        hash.strip_prefix("#/day/")
+            .and_then(|day| day.parse().ok())
+            .filter(|day| (1..=25).contains(day))
+            .map_or(Route::Days, Route::Day)
+    }
+
+    /// Hash of the route.
+    #[must_use]
+    pub fn hash(self) -> String {
+        match self {
+            Route::Days => "#/".to_string(),
+            Route::Day(day) => format!("#/day/{day}"),
+        }
+    }
+
+    /// Route of the current location.
+    fn current() -> Self {
+        Self::parse(&gloo_utils::window().location().hash().unwrap_or_default())
+    }
+}
+
+pub enum Msg {
+    Route(Route),
+}
+
+pub struct App {
+    route: Route,
+    _hashchange: EventListener,
+}
+
+impl Component for App {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let hashchange = EventListener::new(&gloo_utils::window(), "hashchange", move |_| {
+            link.send_message(Msg::Route(Route::current()));
+        });
+
+        Self {
+            route: Route::current(),
+            _hashchange: hashchange,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Route(route) => {
+                let changed = self.route != route;
+                self.route = route;
+                changed
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let page = match self.route {
+            Route::Days => html! {
+                <p>{ "Advent of Code 2023, pick a day." }</p>
+            },
+            Route::Day(day) => html! { <Page key={day} {day} /> },
+        };
+
+        html! {
+            <div class="app">
+                <nav>
+                    { for DAYS.iter().map(|day| {
+                        let route = Route::Day(day.day);
+                        let class = (route == self.route).then_some("selected");
+                        html! {
+                            <a href={route.hash()} {class} title={day.title}>
+                                { format!("{:02}", day.day) }
+                            </a>
+                        }
+                    }) }
+                </nav>
+                <main>
+                    if let Route::Day(day) = self.route {
+                        <h1>{ format!("Day {day}: {}", DAYS[usize::from(day) - 1].title) }</h1>
+                    }
+                    { page }
+                </main>
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(day.day), i + 1);
+        }
+    }
+
+    #[test]
+    fn routes() {
+        assert_eq!(Route::parse("#/day/17"), Route::Day(17));
+        assert_eq!(Route::parse("#/day/05"), Route::Day(5));
+        for hash in ["", "#/", "#/day/0", "#/day/26", "#/day/x", "#/days"] {
+            assert_eq!(Route::parse(hash), Route::Days);
+        }
+        for route in [Route::Days, Route::Day(1), Route::Day(25)] {
+            assert_eq!(Route::parse(&route.hash()), route);
+        }
+    }
+}
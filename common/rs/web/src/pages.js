@@ -0,0 +1,42 @@
+// Pages of the days, each a wasm module of its own built by trunk as
+// `dayNN.js`, imported the first time its page is shown.
+
+const loaded = new Map();
+let shown = null;
+
+export async function show_day(path, root) {
+    shown = path;
+    let day = loaded.get(path);
+    if (day === undefined) {
+        day = import(new URL(path, document.baseURI).href)
+            .then(async (module) => {
+                await module.default();
+                return module;
+            })
+            .catch((err) => {
+                loaded.delete(path);
+                throw String(err);
+            });
+        loaded.set(path, day);
+    }
+
+    const module = await day;
+    // another page may have been shown meanwhile
+    if (shown === path) {
+        module.mount(root);
+    }
+}
+
+export async function hide_day(path) {
+    if (shown === path) {
+        shown = null;
+    }
+    const day = loaded.get(path);
+    if (day !== undefined) {
+        try {
+            (await day).unmount();
+        } catch {
+            // the page showed why it could not be loaded
+        }
+    }
+}
@@ -29,12 +29,17 @@
 
 #![allow(clippy::must_use_candidate)]
 
+use std::io::BufRead;
 use std::iter;
 
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 trait FirstAndLast: Iterator {
@@ -90,6 +95,30 @@ fn match_number(r: &[u8]) -> Option<(u32, usize)> {
     None
 }
 
+fn has_digit(r: &[u8]) -> bool {
+    r.iter().any(u8::is_ascii_digit)
+}
+
+fn has_number(r: &[u8]) -> bool {
+    (0..r.len()).any(|i| r[i].is_ascii_digit() || match_number(&r[i..]).is_some())
+}
+
+fn check_line(parser: Parser, line: &str, valid: fn(&[u8]) -> bool) -> Result<(), ParseError> {
+    if valid(line.as_bytes()) {
+        Ok(())
+    } else {
+        Err(parser.error(line, ErrorKind::Missing("digit")))
+    }
+}
+
+fn check_lines(input: &str, valid: fn(&[u8]) -> bool) -> Result<&str, ParseError> {
+    let parser = Parser::new(input);
+    input
+        .lines()
+        .try_for_each(|line| check_line(parser, line, valid))
+        .map(|()| input)
+}
+
 /// The newly-improved calibration document consists of lines of text;
 /// each line originally contained a specific calibration value that
 /// the Elves now need to recover. On each line, the calibration value
@@ -202,6 +231,86 @@ pub fn solve_2(input: &str) -> u32 {
         .sum()
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 142),
+        aoc::example!("example2", Part::Two, 281),
+    ];
+
+    type Input<'a> = &'a str;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_lines(input, has_number)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
+        solve_2(input)
+    }
+
+    fn try_part_1(input: &Self::Input<'_>, _: &str) -> Result<Self::Output1, ParseError> {
+        check_lines(input, has_digit).map(solve_1)
+    }
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day01::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day01::try_solve_2(input)
+}
+
+fn solve_reader<R: BufRead>(
+    reader: R,
+    valid: fn(&[u8]) -> bool,
+    solve: fn(&str) -> u32,
+) -> Result<u32, ReadError> {
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        check_line(Parser::new(line), line, valid)?;
+        sum += solve(line);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Solve part 1 reading the input a line at a time, as
+/// [`try_solve_1`] does
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    solve_reader(reader, has_digit, solve_1)
+}
+
+/// Solve part 2 reading the input a line at a time, as
+/// [`try_solve_2`] does
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    solve_reader(reader, has_number, solve_2)
+}
+
 pub fn part_1() -> u32 {
     solve_1(&INPUT)
 }
@@ -237,4 +346,40 @@ zoneight234
     fn same_results_2() {
         assert_eq!(solve_2(&INPUT_2), 281);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1(&INPUT_2).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "eightwothree"));
+
+        assert_eq!(
+            try_solve_2("abc").unwrap_err().kind,
+            ErrorKind::Missing("digit")
+        );
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*INPUT_1, *INPUT_2, *INPUT] {
+            assert_eq!(
+                solve_1_reader(input.as_bytes()).ok(),
+                try_solve_1(input).ok()
+            );
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) = solve_1_reader(INPUT_2.as_bytes()) else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.text.as_str()), (2, "eightwothree"));
+
+        let Err(ReadError::Parse(error)) = solve_2_reader("1\n\n2\n".as_bytes()) else {
+            panic!("invalid input solved");
+        };
+        assert_eq!(error.line, 2);
+        assert!(try_solve_2("1\n\n2\n").is_err());
+    }
 }
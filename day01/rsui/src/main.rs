@@ -1,8 +1,7 @@
 fn main() {
     let model_props = ui::ModelProps {
         input: day01::INPUT.to_string(),
-        solve_1: day01::solve_1,
-        solve_2: day01::solve_2,
+        puzzle: &day01::Day01,
     };
-    yew::Renderer::<ui::Model<_, _, _, _>>::with_props(model_props).render();
+    yew::Renderer::<ui::Model>::with_props(model_props).render();
 }
@@ -1,7 +1,13 @@
 #![allow(clippy::must_use_candidate)]
 
+use std::io::BufRead;
+
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -13,7 +19,7 @@ use nom::{
 };
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 fn parse_game_id<'a>() -> impl FnMut(&'a str) -> IResult<&'a str, u32> {
@@ -62,38 +68,67 @@ fn solve_1_nom(input: &str) -> u32 {
         .sum()
 }
 
+/// Game with the maximum number of cubes of each color shown.
+pub struct Game {
+    id: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+fn parse_line(parser: Parser, line: &str) -> Result<Game, ParseError> {
+    let (game_part, sets_part) = parser.split_once(line, ":")?;
+    let id = parser.number(parser.strip_prefix(game_part, "Game ")?)?;
+
+    let mut game = Game {
+        id,
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+    for set in sets_part.split(';') {
+        for cube in set.split(',') {
+            let (count, color) = parser.split_once(cube.trim_start(), " ")?;
+            let count = parser.number(count)?;
+            match color {
+                "red" => game.red = game.red.max(count),
+                "green" => game.green = game.green.max(count),
+                "blue" => game.blue = game.blue.max(count),
+                _ => return Err(parser.error(color, ErrorKind::Expected("color"))),
+            }
+        }
+    }
+
+    Ok(game)
+}
+
+fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let parser = Parser::new(input);
+
+    input.lines().map(|line| parse_line(parser, line)).collect()
+}
+
+fn possible_games(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.red <= 12 && game.green <= 13 && game.blue <= 14)
+        .map(|game| game.id)
+        .sum()
+}
+
+fn powers(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| game.red * game.green * game.blue)
+        .sum()
+}
+
 /// Solve part 1
 ///
 /// # Panics
 /// Panics if invalid input
 pub fn solve_1(input: &str) -> u32 {
-    input
-        .lines()
-        .filter_map(|line| {
-            let (game_part, sets_part) = line.split_once(':').expect("invalid game");
-            let game_id = game_part["Game ".len()..]
-                .parse::<u32>()
-                .expect("invalid game id");
-
-            for set in sets_part.split(';') {
-                for cube in set.split(',') {
-                    let (count, color) = cube[1..].split_once(' ').expect("invalid cube part");
-                    let count = count.parse::<u32>().expect("invalid number of cubes");
-                    let valid = match color {
-                        "red" => count <= 12,
-                        "green" => count <= 13,
-                        "blue" => count <= 14,
-                        _ => panic!("invalid color"),
-                    };
-                    if !valid {
-                        return None;
-                    }
-                }
-            }
-
-            Some(game_id)
-        })
-        .sum()
+    Day02::solve_1(input)
 }
 
 /// Solve part 2
@@ -101,28 +136,76 @@ pub fn solve_1(input: &str) -> u32 {
 /// # Panics
 /// Panics if invalid input
 pub fn solve_2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let (_, sets_part) = line.split_once(':').expect("invalid game");
-
-            let (mut red, mut green, mut blue) = (0, 0, 0);
-            for set in sets_part.split(';') {
-                for cube in set.split(',') {
-                    let (count, color) = cube[1..].split_once(' ').expect("invalid cube part");
-                    let count = count.parse::<u32>().expect("invalid number of cubes");
-                    match color {
-                        "red" => red = red.max(count),
-                        "green" => green = green.max(count),
-                        "blue" => blue = blue.max(count),
-                        _ => panic!("invalid color"),
-                    }
-                }
-            }
+    Day02::solve_2(input)
+}
 
-            red * green * blue
-        })
-        .sum()
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day02::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day02::try_solve_2(input)
+}
+
+fn solve_reader<R: BufRead>(reader: R, value: fn(&Game) -> u32) -> Result<u32, ReadError> {
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        sum += value(&parse_line(Parser::new(line), line)?);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Solve part 1 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    solve_reader(reader, |game| possible_games(std::slice::from_ref(game)))
+}
+
+/// Solve part 2 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    solve_reader(reader, |game| powers(std::slice::from_ref(game)))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 8),
+        aoc::example!("example1", Part::Two, 2286),
+    ];
+
+    type Input<'a> = Vec<Game>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(games: &Self::Input<'_>) -> Self::Output1 {
+        possible_games(games)
+    }
+
+    fn part_2(games: &Self::Input<'_>) -> Self::Output2 {
+        powers(games)
+    }
 }
 
 pub fn part_1() -> u32 {
@@ -154,4 +237,29 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 2286);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("Game 1: 3 blue\nGame 2: 4 purple").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.kind, ErrorKind::Expected("color"));
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_1_reader("Game 1: 3 blue\nGame 2: 4 purple".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 11));
+    }
 }
@@ -2,19 +2,21 @@
 
 use lazy_static::lazy_static;
 
+use aoc::parse::Parser;
+use aoc::{Example, ParseError, Part, Solution};
+
 use std::collections::HashMap;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-pub fn solve_1(input: &str) -> u32 {
-    let engine = input
-        .lines()
-        .map(str::as_bytes)
-        .collect::<Vec<_>>();
+fn parse(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    Parser::new(input).rows(input, |tile| tile.is_ascii_graphic())
+}
 
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+fn part_numbers(engine: &[&[u8]]) -> u32 {
     let mut sum = 0;
     for (r, row) in engine.iter().enumerate() {
         let mut c = 0;
@@ -61,12 +63,7 @@ pub fn solve_1(input: &str) -> u32 {
 }
 
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-pub fn solve_2(input: &str) -> u32 {
-    let engine = input
-        .lines()
-        .map(str::as_bytes)
-        .collect::<Vec<_>>();
-
+fn gear_ratios(engine: &[&[u8]]) -> u32 {
     let mut gears = HashMap::with_capacity(1024);
     for (r, row) in engine.iter().enumerate() {
         let mut c = 0;
@@ -121,6 +118,58 @@ pub fn solve_2(input: &str) -> u32 {
         .sum()
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 4361),
+        aoc::example!("example1", Part::Two, 467835),
+    ];
+
+    type Input<'a> = Vec<&'a [u8]>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(engine: &Self::Input<'_>) -> Self::Output1 {
+        part_numbers(engine)
+    }
+
+    fn part_2(engine: &Self::Input<'_>) -> Self::Output2 {
+        gear_ratios(engine)
+    }
+}
+
+pub fn solve_1(input: &str) -> u32 {
+    Day03::solve_1(input)
+}
+
+pub fn solve_2(input: &str) -> u32 {
+    Day03::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day03::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day03::try_solve_2(input)
+}
+
 pub fn part_1() -> u32 {
     solve_1(&INPUT)
 }
@@ -146,4 +195,11 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 467835);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("467..114..\n...*......\n..35..633").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidRowLength);
+    }
 }
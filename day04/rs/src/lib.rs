@@ -2,43 +2,49 @@
 
 use lazy_static::lazy_static;
 
-use std::collections::HashSet;
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{Example, ParseError, Part, Solution};
+
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
+}
+
+fn parse_line(parser: Parser, line: &str) -> Result<usize, ParseError> {
+    let (_, card_numbers_part) = parser.split_once(line, ":")?;
+    let (winning_numbers_part, numbers_part) = parser.split_once(card_numbers_part, "|")?;
+    let winning_numbers = winning_numbers_part
+        .split_ascii_whitespace()
+        .map(|number| parser.number::<u32>(number))
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    let numbers = numbers_part
+        .split_ascii_whitespace()
+        .map(|number| parser.number(number))
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    Ok(numbers.intersection(&winning_numbers).count())
 }
 
-fn parse(input: &str) -> impl Iterator<Item = usize> + '_ {
-    input.lines().map(|line| {
-        let (_, card_numbers_part) = line.split_once(':').expect("invalid card");
-        let (winning_numbers_part, numbers_part) = card_numbers_part
-            .split_once('|')
-            .expect("invalid numbers part");
-        let winning_numbers = winning_numbers_part
-            .split_ascii_whitespace()
-            .map(|number| number.parse::<u32>().expect("invalid winning number"))
-            .collect::<HashSet<_>>();
-
-        let numbers = numbers_part
-            .split_ascii_whitespace()
-            .map(|number| number.parse().expect("invalid number"))
-            .collect::<HashSet<_>>();
-
-        numbers.intersection(&winning_numbers).count()
-    })
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let parser = Parser::new(input);
+
+    input.lines().map(|line| parse_line(parser, line)).collect()
 }
 
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_1(input: &str) -> u32 {
-    parse(input)
-        .filter(|value| *value > 0)
-        .map(|value| 2_u32.pow(value as u32 - 1))
+fn points(cards: &[usize]) -> u32 {
+    cards
+        .iter()
+        .filter(|value| **value > 0)
+        .map(|value| 2_u32.pow(*value as u32 - 1))
         .sum()
 }
 
-pub fn solve_2(input: &str) -> u32 {
-    let cards = parse(input).collect::<Vec<_>>();
-
+fn scratchcards(cards: &[usize]) -> u32 {
     cards
         .iter()
         .enumerate()
@@ -54,6 +60,94 @@ pub fn solve_2(input: &str) -> u32 {
         .sum()
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 13),
+        aoc::example!("example1", Part::Two, 30),
+    ];
+
+    type Input<'a> = Vec<usize>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(cards: &Self::Input<'_>) -> Self::Output1 {
+        points(cards)
+    }
+
+    fn part_2(cards: &Self::Input<'_>) -> Self::Output2 {
+        scratchcards(cards)
+    }
+}
+
+pub fn solve_1(input: &str) -> u32 {
+    Day04::solve_1(input)
+}
+
+pub fn solve_2(input: &str) -> u32 {
+    Day04::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day04::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day04::try_solve_2(input)
+}
+
+/// Solve part 1 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        sum += points(&[parse_line(Parser::new(line), line)?]);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Solve part 2 reading the input a line at a time, keeping only the
+/// copies won of the next cards
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<u32, ReadError> {
+    let mut won = VecDeque::new();
+    let mut previous = 1;
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        let card = parse_line(Parser::new(line), line)?;
+        if won.len() < card {
+            won.resize(card, 0);
+        }
+        won.iter_mut().take(card).for_each(|v| *v += previous);
+
+        previous = 1 + won.pop_front().unwrap_or(0);
+        sum += previous;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
 pub fn part_1() -> u32 {
     solve_1(&INPUT)
 }
@@ -79,4 +173,32 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 30);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 12, "x2")
+        );
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidNumber);
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_2_reader("Card 1: 41 48 | 83 86\n\nCard 3: 13 32 | 61 30".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!(error.line, 2);
+    }
 }
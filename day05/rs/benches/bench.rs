@@ -3,6 +3,11 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use day05::*;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
+    // read the input first, for the usage of the parts alone
+    lazy_static::initialize(&INPUT);
+    aoc::memory::report("part 1", part_1);
+    aoc::memory::report("part 2", part_2);
+
     c.bench_function("part 1", |b| b.iter(part_1));
     c.bench_function("part 2", |b| b.iter(part_2));
 }
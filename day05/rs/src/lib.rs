@@ -2,13 +2,15 @@
 
 use lazy_static::lazy_static;
 
-use std::ops::Range;
+use aoc::interval::{Cuboid, Interval, IntervalSet};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 trait Chunks2: Iterator + Sized {
@@ -50,61 +52,43 @@ impl<I: Iterator> Chunks2 for I {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Seeds(Range<u64>);
-
-impl Seeds {
-    fn intersection(&self, range: &Range<u64>) -> Option<Range<u64>> {
-        let range = self.0.start.max(range.start)..self.0.end.min(range.end);
-        if range.is_empty() {
-            None
-        } else {
-            Some(range)
-        }
-    }
-}
-
+/// Range of a map, moving the values in `source` to `destination`.
 #[derive(Debug)]
-struct MapEntry {
-    source: Range<u64>,
-    destination: Range<u64>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapEntry {
+    pub source: Interval<u64>,
+    pub destination: Interval<u64>,
 }
 
 impl MapEntry {
     fn get(&self, seed: u64) -> Option<u64> {
-        if self.source.contains(&seed) {
+        if self.source.contains(seed) {
             Some(self.destination.start + (seed - self.source.start))
         } else {
             None
         }
     }
 
-    fn map(&self, seeds: &Seeds) -> (Option<Seeds>, Vec<Seeds>) {
+    fn map(&self, seeds: &Interval<u64>) -> (Option<Interval<u64>>, Vec<Interval<u64>>) {
         if let Some(intersection) = seeds.intersection(&self.source) {
-            let mut unmapped = Vec::with_capacity(2);
+            let unmapped = seeds.difference(&self.source).collect();
 
-            let low = seeds.0.start..intersection.start;
-            if !low.is_empty() {
-                unmapped.push(Seeds(low));
-            }
+            let mapped = Interval::new(
+                self.destination.start + (intersection.start - self.source.start),
+                self.destination.start + (intersection.end - self.source.start),
+            );
 
-            let high = intersection.end..seeds.0.end;
-            if !high.is_empty() {
-                unmapped.push(Seeds(high));
-            }
-
-            let mapped = (self.destination.start + (intersection.start - self.source.start))
-                ..(self.destination.start + (intersection.end - self.source.start));
-
-            (Some(Seeds(mapped)), unmapped)
+            (Some(mapped), unmapped)
         } else {
-            (None, vec![seeds.clone()])
+            (None, vec![*seeds])
         }
     }
 }
 
+/// Map from a category to the next, keeping the values out of its ranges.
 #[derive(Debug)]
-struct Map(Vec<MapEntry>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Map(pub Vec<MapEntry>);
 
 impl Map {
     fn get(&self, seed: u64) -> u64 {
@@ -115,9 +99,9 @@ impl Map {
         }
     }
 
-    fn map(&self, seeds: &Seeds) -> Vec<Seeds> {
+    fn map(&self, seeds: &Interval<u64>) -> Vec<Interval<u64>> {
         let mut result = vec![];
-        let mut list = vec![seeds.clone()];
+        let mut list = vec![*seeds];
         for map_entry in &self.0 {
             let mut new_list = vec![];
             for seeds in &list {
@@ -135,40 +119,145 @@ impl Map {
         result
     }
 
-    fn parse<'a>(parts: impl Iterator<Item = &'a str>) -> Vec<Self> {
+    fn parse<'a>(
+        parser: Parser,
+        parts: impl Iterator<Item = &'a str>,
+    ) -> Result<Vec<Self>, ParseError> {
         parts
             .map(|part| {
-                Map(part
-                    .lines()
+                part.lines()
                     .skip(1)
                     .map(|map_entry| {
-                        let mut i = map_entry.split_whitespace();
-
-                        let destination_range_start = i
-                            .next()
-                            .expect("cannot find destination range start")
-                            .parse()
-                            .expect("invalid destination start");
-                        let source_range_start = i
-                            .next()
-                            .expect("cannot find source range start")
-                            .parse()
-                            .expect("invalid range start");
-                        let range_length: u64 = i
-                            .next()
-                            .expect("cannot find range length")
-                            .parse()
-                            .expect("invalid range length");
-
-                        MapEntry {
-                            source: source_range_start..(source_range_start + range_length),
-                            destination: destination_range_start
-                                ..(destination_range_start + range_length),
-                        }
+                        let mut numbers = map_entry
+                            .split_whitespace()
+                            .map(|number| parser.number::<u64>(number));
+                        let mut next = || {
+                            numbers.next().unwrap_or_else(|| {
+                                Err(parser.error(map_entry, ErrorKind::Expected("3 numbers")))
+                            })
+                        };
+
+                        let destination_range_start = next()?;
+                        let source_range_start = next()?;
+                        let range_length = next()?;
+
+                        let (Some(source_range_end), Some(destination_range_end)) = (
+                            source_range_start.checked_add(range_length),
+                            destination_range_start.checked_add(range_length),
+                        ) else {
+                            return Err(parser.error(map_entry, ErrorKind::InvalidNumber));
+                        };
+
+                        Ok(MapEntry {
+                            source: Interval::new(source_range_start, source_range_end),
+                            destination: Interval::new(
+                                destination_range_start,
+                                destination_range_end,
+                            ),
+                        })
                     })
-                    .collect::<Vec<_>>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Map)
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, _>>()
+    }
+}
+
+/// Seeds and the maps from seeds to locations, in order.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Map>,
+}
+
+impl Almanac {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let parser = Parser::new(input);
+
+        let mut parts = input.split("\n\n");
+
+        let seeds_part = parts.next().unwrap_or_default();
+        let seeds = parser
+            .strip_prefix(seeds_part, "seeds: ")?
+            .split_whitespace()
+            .map(|seed| parser.number::<u64>(seed))
+            .collect::<Result<Vec<_>, _>>()?;
+        if seeds.is_empty() {
+            return Err(parser.error(seeds_part, ErrorKind::Missing("seeds")));
+        }
+
+        let maps = Map::parse(parser, parts)?;
+
+        Ok(Self { seeds, maps })
+    }
+
+    fn lowest_location(&self) -> u64 {
+        self.seeds
+            .iter()
+            .map(|&seed| self.maps.iter().fold(seed, |s, map| map.get(s)))
+            .min()
+            .expect("invalid input")
+    }
+
+    fn lowest_location_of_ranges(&self) -> u64 {
+        let seeds_list = self
+            .seeds
+            .iter()
+            .copied()
+            .chunks_2(|a, b| Interval::new(a, a + b));
+
+        #[cfg(feature = "rayon")]
+        let seeds_list = seeds_list.par_bridge();
+
+        seeds_list
+            .filter_map(|seeds| {
+                let locations = self.maps.iter().fold(IntervalSet::from(seeds), |set, map| {
+                    let mut set = set
+                        .iter()
+                        .flat_map(|Cuboid([seeds])| map.map(seeds))
+                        .map(Cuboid::from)
+                        .collect::<IntervalSet<_>>();
+                    set.coalesce();
+                    set
+                });
+
+                locations.iter().map(|Cuboid([seeds])| seeds.start).min()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 35),
+        aoc::example!("example1", Part::Two, 46),
+    ];
+
+    type Input<'a> = Almanac;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Almanac::parse(input)
+    }
+
+    fn part_1(almanac: &Self::Input<'_>) -> Self::Output1 {
+        almanac.lowest_location()
+    }
+
+    fn part_2(almanac: &Self::Input<'_>) -> Self::Output2 {
+        almanac.lowest_location_of_ranges()
     }
 }
 
@@ -177,50 +266,31 @@ impl Map {
 /// # Panics
 /// Panics if input is invalid
 pub fn solve_1(input: &str) -> u64 {
-    let mut parts = input.split("\n\n");
-
-    let seeds = parts.next().expect("cannot find seeds")["seeds: ".len()..]
-        .split_whitespace()
-        .map(|seed| seed.parse::<u64>().expect("invalid seed"));
-
-    let maps = Map::parse(parts);
-
-    seeds
-        .map(|seed| maps.iter().fold(seed, |s, map| map.get(s)))
-        .min()
-        .expect("invalid input")
+    Day05::solve_1(input)
 }
 
-/// Solve part 1
+/// Solve part 2
 ///
 /// # Panics
 /// Panics if input is invalid
 pub fn solve_2(input: &str) -> u64 {
-    let mut parts = input.split("\n\n");
-
-    let seeds_list_part = parts.next().expect("cannot find seeds");
-
-    let maps = Map::parse(parts);
-
-    let seeds_list = seeds_list_part["seeds: ".len()..]
-        .split_whitespace()
-        .map(|value| value.parse::<u64>().expect("invalid value"))
-        .chunks_2(|a, b| Seeds(a..(a + b)));
+    Day05::solve_2(input)
+}
 
-    #[cfg(feature = "rayon")]
-    let seeds_list = seeds_list.par_bridge();
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day05::try_solve_1(input)
+}
 
-    seeds_list
-        .flat_map(|seeds| {
-            maps.iter().fold(vec![seeds], |list, map| {
-                list.into_iter()
-                    .flat_map(|seeds| map.map(&seeds))
-                    .collect::<Vec<_>>()
-            })
-        })
-        .map(|Seeds(r)| r.start)
-        .min()
-        .unwrap()
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day05::try_solve_2(input)
 }
 
 pub fn part_1() -> u64 {
@@ -249,62 +319,81 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 46);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("seeds: 79 14\n\nseed-to-soil map:\n50 98\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.kind, ErrorKind::Expected("3 numbers"));
+
+        let error = try_solve_2("seeds:\n\nseed-to-soil map:\n").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Expected("seeds: "));
+    }
+
     #[test]
     fn test_map_entry_none() {
         let map_entry = MapEntry {
-            source: 0..10,
-            destination: 100..110,
+            source: Interval::new(0, 10),
+            destination: Interval::new(100, 110),
         };
 
-        assert_eq!(map_entry.map(&Seeds(10..20)), (None, vec![Seeds(10..20)]));
+        assert_eq!(
+            map_entry.map(&Interval::new(10, 20)),
+            (None, vec![Interval::new(10, 20)])
+        );
     }
 
     #[test]
     fn test_map_entry_full() {
         let map_entry = MapEntry {
-            source: 0..10,
-            destination: 100..110,
+            source: Interval::new(0, 10),
+            destination: Interval::new(100, 110),
         };
 
-        assert_eq!(map_entry.map(&Seeds(5..7)), (Some(Seeds(105..107)), vec![]));
+        assert_eq!(
+            map_entry.map(&Interval::new(5, 7)),
+            (Some(Interval::new(105, 107)), vec![])
+        );
     }
 
     #[test]
     fn test_map_entry_low() {
         let map_entry = MapEntry {
-            source: 0..10,
-            destination: 100..110,
+            source: Interval::new(0, 10),
+            destination: Interval::new(100, 110),
         };
 
         assert_eq!(
-            map_entry.map(&Seeds(8..12)),
-            (Some(Seeds(108..110)), vec![Seeds(10..12)])
+            map_entry.map(&Interval::new(8, 12)),
+            (Some(Interval::new(108, 110)), vec![Interval::new(10, 12)])
         );
     }
 
     #[test]
     fn test_map_entry_high() {
         let map_entry = MapEntry {
-            source: 10..20,
-            destination: 100..110,
+            source: Interval::new(10, 20),
+            destination: Interval::new(100, 110),
         };
 
         assert_eq!(
-            map_entry.map(&Seeds(8..12)),
-            (Some(Seeds(100..102)), vec![Seeds(8..10)])
+            map_entry.map(&Interval::new(8, 12)),
+            (Some(Interval::new(100, 102)), vec![Interval::new(8, 10)])
         );
     }
 
     #[test]
     fn test_map_entry_middle_inner() {
         let map_entry = MapEntry {
-            source: 10..20,
-            destination: 100..110,
+            source: Interval::new(10, 20),
+            destination: Interval::new(100, 110),
         };
 
         assert_eq!(
-            map_entry.map(&Seeds(0..30)),
-            (Some(Seeds(100..110)), vec![Seeds(0..10), Seeds(20..30)])
+            map_entry.map(&Interval::new(0, 30)),
+            (
+                Some(Interval::new(100, 110)),
+                vec![Interval::new(0, 10), Interval::new(20, 30)]
+            )
         );
     }
 
@@ -312,18 +401,22 @@ mod tests {
     fn test_map() {
         let map = Map(vec![
             MapEntry {
-                source: 10..20,
-                destination: 100..110,
+                source: Interval::new(10, 20),
+                destination: Interval::new(100, 110),
             },
             MapEntry {
-                source: 20..30,
-                destination: 50..60,
+                source: Interval::new(20, 30),
+                destination: Interval::new(50, 60),
             },
         ]);
 
         assert_eq!(
-            map.map(&Seeds(0..30)),
-            vec![Seeds(100..110), Seeds(50..60), Seeds(0..10)]
+            map.map(&Interval::new(0, 30)),
+            vec![
+                Interval::new(100, 110),
+                Interval::new(50, 60),
+                Interval::new(0, 10)
+            ]
         );
     }
 }
@@ -2,8 +2,11 @@
 
 use lazy_static::lazy_static;
 
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 fn find_min(time: u64, distance: u64) -> u64 {
@@ -73,23 +76,64 @@ fn solve(time: u64, distance: u64) -> u64 {
     max - min + 1
 }
 
-/// solve part 1
-///
-/// # Panics
-/// If input is invalid
-pub fn solve_1(input: &str) -> u64 {
+fn winnable(time: u64, distance: u64) -> bool {
+    let t = time / 2;
+    t.checked_mul(time - t).is_some_and(|d| d > distance)
+}
+
+/// Races of part 1, and the single race of part 2.
+pub struct Races {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
+}
+
+fn parse(input: &str) -> Result<Races, ParseError> {
+    let parser = Parser::new(input);
+
     let mut lines = input.lines();
 
-    let mut parse = move |msg| {
-        lines.next().expect(msg)[10..]
+    let mut parse = move |prefix| {
+        let line = lines
+            .next()
+            .ok_or_else(|| parser.error_end(ErrorKind::Missing(prefix)))?;
+        let numbers = parser.strip_prefix(line, prefix)?;
+
+        let list = numbers
+            .split_whitespace()
+            .map(|number| parser.number::<u64>(number))
+            .collect::<Result<Vec<_>, _>>()?;
+        let single = numbers
             .split_whitespace()
-            .map(|number| number.parse::<u64>().expect("invalid number"))
+            .collect::<String>()
+            .parse::<u64>()
+            .map_err(|_| parser.error(numbers, ErrorKind::InvalidNumber))?;
+
+        Ok((line, list, single))
     };
 
-    let time = parse("cannot find time part");
-    let distance = parse("cannot find distance part");
+    let (_, times, time) = parse("Time:")?;
+    let (line, distances, distance) = parse("Distance:")?;
+
+    if times.len() != distances.len() {
+        return Err(parser.error(line, ErrorKind::Expected("a distance for each time")));
+    }
+
+    let races = times.into_iter().zip(distances).collect::<Vec<_>>();
+    let race = (time, distance);
 
-    time.zip(distance).map(|(t, d)| solve(t, d)).product()
+    if !races.iter().chain([&race]).all(|&(t, d)| winnable(t, d)) {
+        return Err(parser.error(line, ErrorKind::Invalid("race, record cannot be beaten")));
+    }
+
+    Ok(Races { races, race })
+}
+
+/// solve part 1
+///
+/// # Panics
+/// If input is invalid
+pub fn solve_1(input: &str) -> u64 {
+    Day06::solve_1(input)
 }
 
 /// solve part 2
@@ -97,20 +141,52 @@ pub fn solve_1(input: &str) -> u64 {
 /// # Panics
 /// If input is invalid
 pub fn solve_2(input: &str) -> u64 {
-    let mut lines = input.lines();
+    Day06::solve_2(input)
+}
 
-    let mut parse = move |msg| {
-        lines.next().expect(msg)[10..]
-            .split_whitespace()
-            .collect::<String>()
-            .parse::<u64>()
-            .expect("invalid number")
-    };
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day06::try_solve_1(input)
+}
 
-    let time = parse("cannot find time part");
-    let distance = parse("cannot find distance part");
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day06::try_solve_2(input)
+}
 
-    solve(time, distance)
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 288),
+        aoc::example!("example1", Part::Two, 71503),
+    ];
+
+    type Input<'a> = Races;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(races: &Self::Input<'_>) -> Self::Output1 {
+        races.races.iter().map(|&(t, d)| solve(t, d)).product()
+    }
+
+    fn part_2(races: &Self::Input<'_>) -> Self::Output2 {
+        let (time, distance) = races.race;
+        solve(time, distance)
+    }
 }
 
 pub fn part_1() -> u64 {
@@ -139,6 +215,19 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 71503);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.kind, ErrorKind::Expected("a distance for each time"));
+
+        let error = try_solve_2("Time: 7\nDistance: 90").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("race, record cannot be beaten")
+        );
+    }
+
     #[test]
     fn test_find_min_7_9() {
         assert_eq!(find_min(7, 9), 2);
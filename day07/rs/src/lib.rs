@@ -2,7 +2,12 @@
 
 use lazy_static::lazy_static;
 
-use std::collections::HashMap;
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
 
 use std::cmp::Ordering;
 
@@ -10,7 +15,7 @@ const J_AS_A_CARD: u8 = 3;
 const J_AS_A_JOKER: u8 = 100;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
@@ -24,6 +29,30 @@ enum Hand<'a, const J: u8> {
     HighCard(CamelCards<'a, J>),
 }
 
+/// Kind and card priorities of a hand, ordered as [`Hand`] without
+/// borrowing the cards.
+type Strength = (u8, [u8; 5]);
+
+impl<const J: u8> Hand<'_, J> {
+    fn strength(&self) -> Strength {
+        let (kind, CamelCards(cards)) = match self {
+            Hand::FiveOfAKind(cards) => (0, cards),
+            Hand::FourOfAKind(cards) => (1, cards),
+            Hand::FullHouse(cards) => (2, cards),
+            Hand::ThreeOfAKind(cards) => (3, cards),
+            Hand::TwoPair(cards) => (4, cards),
+            Hand::OnePair(cards) => (5, cards),
+            Hand::HighCard(cards) => (6, cards),
+        };
+
+        let mut priorities = [0; 5];
+        for (p, card) in priorities.iter_mut().zip(cards.bytes()) {
+            *p = priority::<J>(card);
+        }
+        (kind, priorities)
+    }
+}
+
 impl<'a> Hand<'a, J_AS_A_CARD> {
     fn parse(input: &'a str) -> Self {
         let groups = input
@@ -135,24 +164,25 @@ impl<'a> Hand<'a, J_AS_A_JOKER> {
 #[derive(Debug, PartialEq, Eq, PartialOrd)]
 struct CamelCards<'a, const J_PRIORITY: u8>(&'a str);
 
+fn priority<const J_PRIORITY: u8>(card: u8) -> u8 {
+    match card {
+        b'A' => 0,
+        b'K' => 1,
+        b'Q' => 2,
+        b'J' => J_PRIORITY,
+        b'T' => 10,
+        b'2'..=b'9' => 20 - (card - b'0'),
+        _ => panic!("invalid card"),
+    }
+}
+
 impl<'a, const J_PRIORITY: u8> Ord for CamelCards<'a, J_PRIORITY> {
     fn cmp(&self, CamelCards(b): &Self) -> Ordering {
-        let priority = |card: &u8| match card {
-            b'A' => 0,
-            b'K' => 1,
-            b'Q' => 2,
-            b'J' => J_PRIORITY,
-            b'T' => 10,
-            b'2'..=b'9' => 20 - (card - b'0'),
-            _ => panic!("invalid card"),
-        };
-
         for (a, b) in self
             .0
-            .as_bytes()
-            .iter()
-            .map(priority)
-            .zip(b.as_bytes().iter().map(priority))
+            .bytes()
+            .map(priority::<J_PRIORITY>)
+            .zip(b.bytes().map(priority::<J_PRIORITY>))
         {
             let c = a.cmp(&b);
             if c != Ordering::Equal {
@@ -164,13 +194,30 @@ impl<'a, const J_PRIORITY: u8> Ord for CamelCards<'a, J_PRIORITY> {
     }
 }
 
-fn solve<'a, const J: u8>(input: &'a str, parse: impl Fn(&'a str) -> Hand<'a, J>) -> u64 {
-    let mut hands = input
-        .lines()
-        .map(|line| {
-            let (hand, bid) = line.split_once(' ').expect("invalid input");
-            (parse(hand), bid.parse::<u64>().expect("invalid bid"))
-        })
+fn parse_line<'a>(parser: Parser, line: &'a str) -> Result<(&'a str, u64), ParseError> {
+    let (hand, bid) = parser.split_once(line, " ")?;
+    if hand.len() != 5 {
+        return Err(parser.error(hand, ErrorKind::Expected("5 cards")));
+    }
+    if let Some(i) = hand
+        .bytes()
+        .position(|card| !matches!(card, b'A' | b'K' | b'Q' | b'J' | b'T' | b'2'..=b'9'))
+    {
+        return Err(parser.error(&hand[i..=i], ErrorKind::Expected("card")));
+    }
+    Ok((hand, parser.number(bid)?))
+}
+
+fn parse(input: &str) -> Result<Vec<(&str, u64)>, ParseError> {
+    let parser = Parser::new(input);
+
+    input.lines().map(|line| parse_line(parser, line)).collect()
+}
+
+fn solve<'a, const J: u8>(hands: &[(&'a str, u64)], parse: impl Fn(&'a str) -> Hand<'a, J>) -> u64 {
+    let mut hands = hands
+        .iter()
+        .map(|&(hand, bid)| (parse(hand), bid))
         .collect::<Vec<_>>();
 
     hands.sort_by(|a, b| b.cmp(a));
@@ -182,12 +229,94 @@ fn solve<'a, const J: u8>(input: &'a str, parse: impl Fn(&'a str) -> Hand<'a, J>
         .sum()
 }
 
+/// Like [`solve`], counting the hands by strength and bid instead of
+/// keeping them: at most 13^5 different hands for every bid.
+fn solve_reader<R: BufRead>(reader: R, strength: fn(&str) -> Strength) -> Result<u64, ReadError> {
+    let mut hands = BTreeMap::new();
+    input::for_each_line(reader, |line| {
+        let (hand, bid) = parse_line(Parser::new(line), line)?;
+        *hands.entry((strength(hand), bid)).or_insert(0_u64) += 1;
+        Ok(())
+    })?;
+
+    let mut rank = 0;
+    Ok(hands
+        .into_iter()
+        .rev()
+        .map(|((_, bid), count)| {
+            let first = rank + 1;
+            rank += count;
+            (first + rank) * count / 2 * bid
+        })
+        .sum())
+}
+
 pub fn solve_1(input: &str) -> u64 {
-    solve(input, Hand::<J_AS_A_CARD>::parse)
+    Day07::solve_1(input)
 }
 
 pub fn solve_2(input: &str) -> u64 {
-    solve(input, Hand::<J_AS_A_JOKER>::parse)
+    Day07::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day07::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day07::try_solve_2(input)
+}
+
+/// Solve part 1 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<u64, ReadError> {
+    solve_reader(reader, |hand| Hand::<J_AS_A_CARD>::parse(hand).strength())
+}
+
+/// Solve part 2 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<u64, ReadError> {
+    solve_reader(reader, |hand| Hand::<J_AS_A_JOKER>::parse(hand).strength())
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 6440),
+        aoc::example!("example1", Part::Two, 5905),
+    ];
+
+    type Input<'a> = Vec<(&'a str, u64)>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(hands: &Self::Input<'_>) -> Self::Output1 {
+        solve(hands, Hand::<J_AS_A_CARD>::parse)
+    }
+
+    fn part_2(hands: &Self::Input<'_>) -> Self::Output2 {
+        solve(hands, Hand::<J_AS_A_JOKER>::parse)
+    }
 }
 
 pub fn part_1() -> u64 {
@@ -215,4 +344,38 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 5905);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 4, "X"));
+        assert_eq!(error.kind, ErrorKind::Expected("card"));
+    }
+
+    #[test]
+    fn trailing_blank_line() {
+        let input = format!("{}\n\n", *EXAMPLE_1);
+        assert_eq!(try_solve_1(&input), Ok(6440));
+        assert_eq!(try_solve_2(&input), Ok(5905));
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [
+            *EXAMPLE_1,
+            *INPUT,
+            "KK677 28\nKK677 28\nKK677 3\n32T3K 765\n",
+        ] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) = solve_1_reader("32T3K 765\nT55X5 684".as_bytes()) else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 4, "X"));
+    }
 }
@@ -7,6 +7,9 @@ compile_error!(r#"Feature "simd" and "rayon" are mutual exclusive"#);
 
 use lazy_static::lazy_static;
 
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 #[allow(unused_imports)]
 use num::integer::lcm;
 
@@ -19,32 +22,49 @@ use rayon::prelude::*;
 mod simd;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 type Network<'a> = HashMap<&'a str, (&'a str, &'a str)>;
 
-fn parse(input: &str) -> (&str, Network) {
-    let mut parts = input.split("\n\n");
+fn parse(input: &str) -> Result<(&str, Network<'_>), ParseError> {
+    let parser = Parser::new(input);
+
+    let (path, nodes) = parser.split_once(input, "\n\n")?;
 
-    let path = parts.next().expect("invalid input, cannot find path");
+    if path.is_empty() {
+        return Err(parser.error(path, ErrorKind::Missing("path")));
+    }
+    if let Some(i) = path
+        .bytes()
+        .position(|direction| !matches!(direction, b'L' | b'R'))
+    {
+        return Err(parser.error(&path[i..=i], ErrorKind::Expected("L or R")));
+    }
 
-    let network = parts
-        .next()
-        .expect("invalid input, cannot find network")
+    let nodes = nodes
         .lines()
         .map(|line| {
-            let (source_node, destination_nodes) = line[..line.len() - 1]
-                .split_once(" = (")
-                .expect("invalid line");
-            let (left_node, right_node) = destination_nodes
-                .split_once(", ")
-                .expect("invalid destination");
-            (source_node, (left_node, right_node))
+            let (source_node, destination_nodes) = parser.split_once(line, " = (")?;
+            let destination_nodes = destination_nodes
+                .strip_suffix(')')
+                .ok_or_else(|| parser.error(line, ErrorKind::Expected(")")))?;
+            let (left_node, right_node) = parser.split_once(destination_nodes, ", ")?;
+            Ok((source_node, (left_node, right_node)))
         })
-        .collect::<HashMap<_, _>>();
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let network = nodes.iter().copied().collect::<HashMap<_, _>>();
 
-    (path, network)
+    if let Some(node) = nodes
+        .iter()
+        .flat_map(|&(_, (left_node, right_node))| [left_node, right_node])
+        .find(|node| !network.contains_key(node))
+    {
+        return Err(parser.error(node, ErrorKind::Undefined("node")));
+    }
+
+    Ok((path, network))
 }
 
 #[allow(clippy::maybe_infinite_iter)]
@@ -71,21 +91,15 @@ fn steps(
         + 1
 }
 
-pub fn solve_1(input: &str) -> u64 {
-    let (path, network) = parse(input);
-
-    steps(path, &network, "AAA", |current| current == "ZZZ")
+fn steps_from_aaa(path: &str, network: &Network) -> u64 {
+    steps(path, network, "AAA", |current| current == "ZZZ")
 }
 
-pub fn solve_2(input: &str) -> u64 {
-    let (path, network) = parse(input);
-
+fn ghost_steps(path: &str, network: &Network) -> u64 {
     #[cfg(not(feature = "simd"))]
     let from_a_to_z = |&node: &&str| {
         if node.ends_with('A') {
-            Some(steps(path, &network, node, |current| {
-                current.ends_with('Z')
-            }))
+            Some(steps(path, network, node, |current| current.ends_with('Z')))
         } else {
             None
         }
@@ -111,6 +125,79 @@ pub fn solve_2(input: &str) -> u64 {
     result
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+        #[cfg(feature = "simd")]
+        "simd",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 2),
+        aoc::example!("example2", Part::One, 6),
+        aoc::example!("example3", Part::Two, 6),
+    ];
+
+    type Input<'a> = (&'a str, Network<'a>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1((path, network): &Self::Input<'_>) -> Self::Output1 {
+        steps_from_aaa(path, network)
+    }
+
+    fn part_2((path, network): &Self::Input<'_>) -> Self::Output2 {
+        ghost_steps(path, network)
+    }
+
+    fn try_part_1(parsed: &Self::Input<'_>, input: &str) -> Result<Self::Output1, ParseError> {
+        if !parsed.1.contains_key("AAA") || !parsed.1.contains_key("ZZZ") {
+            return Err(Parser::new(input).error_end(ErrorKind::Missing("node AAA or ZZZ")));
+        }
+        Ok(Self::part_1(parsed))
+    }
+
+    fn try_part_2(parsed: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        if !parsed.1.keys().any(|node| node.ends_with('A')) {
+            return Err(Parser::new(input).error_end(ErrorKind::Missing("node ending with A")));
+        }
+        Ok(Self::part_2(parsed))
+    }
+}
+
+pub fn solve_1(input: &str) -> u64 {
+    Day08::solve_1(input)
+}
+
+pub fn solve_2(input: &str) -> u64 {
+    Day08::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day08::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day08::try_solve_2(input)
+}
+
 pub fn part_1() -> u64 {
     solve_1(&INPUT)
 }
@@ -143,4 +230,17 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_3), 6);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, CCC)").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 8, "BBB")
+        );
+        assert_eq!(error.kind, ErrorKind::Undefined("node"));
+
+        let error = try_solve_1(&EXAMPLE_3).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Missing("node AAA or ZZZ"));
+    }
 }
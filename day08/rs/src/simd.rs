@@ -5,7 +5,7 @@ use num::integer::lcm;
 
 use super::Network;
 
-pub(crate) fn solve(path: &str, network: Network) -> u64 {
+pub(crate) fn solve(path: &str, network: &Network) -> u64 {
     const ZERO: usizex8 = usizex8::from_slice(&[0; 8]);
     const ONE: usizex8 = usizex8::from_slice(&[1; 8]);
     const TWO: usizex8 = usizex8::from_slice(&[2; 8]);
@@ -1,9 +1,15 @@
 #![allow(clippy::must_use_candidate)]
 
+use std::io::BufRead;
+
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[inline(always)]
@@ -35,33 +41,34 @@ fn extrapolate(i: impl Iterator<Item = i64>) -> i64 {
     extrapolate_norec(0, i.collect())
 }
 
-fn parse_rev(line: &str) -> impl Iterator<Item = i64> + '_ {
-    line.split_whitespace()
-        .map(|n| n.parse::<i64>().expect("invalid number"))
-        .rev()
+fn parse_line(parser: Parser, line: &str) -> Result<Vec<i64>, ParseError> {
+    let history = line
+        .split_whitespace()
+        .map(|n| parser.number(n))
+        .collect::<Result<Vec<_>, _>>()?;
+    if history.is_empty() {
+        return Err(parser.error(line, ErrorKind::Missing("values")));
+    }
+    Ok(history)
 }
 
-fn parse_fwd(line: &str) -> impl Iterator<Item = i64> + '_ {
-    line.split_whitespace()
-        .map(|n| n.parse::<i64>().expect("invalid number"))
+fn parse(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let parser = Parser::new(input);
+
+    input.lines().map(|line| parse_line(parser, line)).collect()
 }
 
-fn solve<'a, F, I>(input: &'a str, parse: F) -> i64
-where
-    F: Fn(&'a str) -> I + Sync + Send + 'a,
-    I: Iterator<Item = i64>,
-{
+fn solve(histories: &[Vec<i64>], extrapolate: impl Fn(&[i64]) -> i64 + Sync) -> i64 {
     #[cfg(not(feature = "rayon"))]
-    let r = input.lines().map(|line| extrapolate(parse(line))).sum();
+    let r = histories.iter().map(|history| extrapolate(history)).sum();
 
     #[cfg(feature = "rayon")]
     let r = {
         use rayon::prelude::*;
 
-        input
-            .lines()
-            .par_bridge()
-            .map(|line| extrapolate(parse(line)))
+        histories
+            .par_iter()
+            .map(|history| extrapolate(history))
             .sum()
     };
 
@@ -69,11 +76,89 @@ where
 }
 
 pub fn solve_1(input: &str) -> i64 {
-    solve(input, parse_rev)
+    Day09::solve_1(input)
 }
 
 pub fn solve_2(input: &str) -> i64 {
-    solve(input, parse_fwd)
+    Day09::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<i64, ParseError> {
+    Day09::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<i64, ParseError> {
+    Day09::try_solve_2(input)
+}
+
+fn solve_reader<R: BufRead>(
+    reader: R,
+    extrapolate: impl Fn(&[i64]) -> i64,
+) -> Result<i64, ReadError> {
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        sum += extrapolate(&parse_line(Parser::new(line), line)?);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Solve part 1 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<i64, ReadError> {
+    solve_reader(reader, |history| extrapolate(history.iter().rev().copied()))
+}
+
+/// Solve part 2 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<i64, ReadError> {
+    solve_reader(reader, |history| extrapolate(history.iter().copied()))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 114),
+        aoc::example!("example1", Part::Two, 2),
+    ];
+
+    type Input<'a> = Vec<Vec<i64>>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(histories: &Self::Input<'_>) -> Self::Output1 {
+        solve(histories, |history| {
+            extrapolate(history.iter().rev().copied())
+        })
+    }
+
+    fn part_2(histories: &Self::Input<'_>) -> Self::Output2 {
+        solve(histories, |history| extrapolate(history.iter().copied()))
+    }
 }
 
 pub fn part_1() -> i64 {
@@ -102,24 +187,52 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 2);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("0 3 6 9 12 15\n1 3 6 1O 15 21").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 7, "1O")
+        );
+        assert_eq!(error.kind, ErrorKind::InvalidNumber);
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_2_reader("0 3 6 9 12 15\n1 3 6 1O 15 21".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 7));
+    }
+
     #[test]
     fn example_1_1() {
-        let example = parse_rev("0 3 6 9 12 15");
+        let example = [0, 3, 6, 9, 12, 15];
 
-        assert_eq!(extrapolate(example), 18);
+        assert_eq!(extrapolate(example.into_iter().rev()), 18);
     }
 
     #[test]
     fn example_1_2() {
-        let example = parse_rev("1 3 6 10 15 21");
+        let example = [1, 3, 6, 10, 15, 21];
 
-        assert_eq!(extrapolate(example), 28);
+        assert_eq!(extrapolate(example.into_iter().rev()), 28);
     }
 
     #[test]
     fn example_1_3() {
-        let example = parse_rev("10 13 16 21 30 45");
+        let example = [10, 13, 16, 21, 30, 45];
 
-        assert_eq!(extrapolate(example), 68);
+        assert_eq!(extrapolate(example.into_iter().rev()), 68);
     }
 }
@@ -2,12 +2,19 @@
 
 use lazy_static::lazy_static;
 
+use aoc::grid::Grid;
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, Overlay, ParseError, Part, Solution};
+
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 type DPoint = (isize, isize);
 
+type Loop<'a> = (u32, Grid<&'a [u8]>, Vec<Vec<bool>>, u8);
+
 const NEIGHBORS: [(DPoint, [u8; 3], [u8; 3]); 4] = [
     ((-1, 0), *b"-J7", *b"-LF"),
     ((0, 1), *b"|7F", *b"|JL"),
@@ -15,55 +22,48 @@ const NEIGHBORS: [(DPoint, [u8; 3], [u8; 3]); 4] = [
     ((0, -1), *b"|JL", *b"|7F"),
 ];
 
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss, clippy::maybe_infinite_iter)]
-fn solve(input: &str) -> (u32, Vec<&[u8]>, Vec<Vec<bool>>, u8) {
-    let tiles = input
-        .lines()
-        .map(str::as_bytes)
-        .collect::<Vec<_>>();
-
-    let (nrows, ncols) = (tiles.len() as isize, tiles[0].len() as isize);
-
-    let (x, y) = {
-        let mut y = 0;
-        loop {
-            let row = tiles.get(y).expect("S not found");
-            if let Some(x) = row.iter().position(|&c| c == b'S') {
-                break (x as isize, y as isize);
-            }
-            y += 1;
-        }
+#[allow(clippy::maybe_infinite_iter)]
+fn find_loop(input: &str) -> Result<Loop<'_>, ParseError> {
+    let parser = Parser::new(input);
+
+    let tiles = Grid::parse(parser, input, |tile| b"|-LJ7F.S".contains(&tile))?;
+
+    let (x, y) = tiles
+        .find(b'S')
+        .ok_or_else(|| parser.error_end(ErrorKind::Missing("start tile S")))?;
+
+    let not_a_loop = || {
+        parser.error_bytes(
+            &tiles.row(y)[x..=x],
+            ErrorKind::Invalid("start tile, not on a loop"),
+        )
     };
 
-    let mut visited = vec![vec![false; ncols as usize]; nrows as usize];
+    let mut visited = vec![vec![false; tiles.ncols()]; tiles.nrows()];
 
-    visited[y as usize][x as usize] = true;
+    visited[y][x] = true;
 
     let (current, s) = {
-        let mut neighbors = NEIGHBORS.iter().filter_map(|((dx, dy), srcs, valid)| {
-            let (nx, ny) = (x + dx, y + dy);
-
-            tiles
-                .get(ny as usize)
-                .and_then(|row| row.get(nx as usize))
-                .and_then(|t| {
-                    if valid.contains(t) {
-                        Some(((nx, ny), srcs))
-                    } else {
-                        None
-                    }
-                })
+        let mut neighbors = NEIGHBORS.iter().filter_map(|(delta, srcs, valid)| {
+            tiles.step((x, y), *delta).and_then(|next| {
+                if valid.contains(&tiles[next]) {
+                    Some((next, srcs))
+                } else {
+                    None
+                }
+            })
         });
 
-        let ((first, first_tiles), (_, second_tiles)) = (
-            neighbors.next().expect("cannot find first path"),
-            neighbors.next().expect("cannot find second path"),
-        );
+        let (Some((first, first_tiles)), Some((_, second_tiles))) =
+            (neighbors.next(), neighbors.next())
+        else {
+            return Err(not_a_loop());
+        };
 
         let tile = first_tiles
             .iter()
             .find(|t| second_tiles.contains(t))
-            .expect("cannot find S equivalence");
+            .ok_or_else(not_a_loop)?;
 
         (first, tile)
     };
@@ -72,31 +72,19 @@ fn solve(input: &str) -> (u32, Vec<&[u8]>, Vec<Vec<bool>>, u8) {
         .scan(current, |current, i| {
             let (x, y) = *current;
 
-            visited[y as usize][x as usize] = true;
-
-            let pipe = tiles[y as usize][x as usize];
-
-            if let Some(r) = NEIGHBORS
-                .iter()
-                .find_map(|((dx, dy), valid_src, valid_dest)| {
-                    if valid_src.contains(&pipe) {
-                        let (nx, ny) = (x + dx, y + dy);
-
-                        tiles
-                            .get(ny as usize)
-                            .and_then(|row| row.get(nx as usize))
-                            .and_then(|t| {
-                                if !visited[ny as usize][nx as usize] && valid_dest.contains(t) {
-                                    Some((nx, ny))
-                                } else {
-                                    None
-                                }
-                            })
-                    } else {
-                        None
-                    }
-                })
-            {
+            visited[y][x] = true;
+
+            let pipe = tiles[(x, y)];
+
+            if let Some(r) = NEIGHBORS.iter().find_map(|(delta, valid_src, valid_dest)| {
+                if valid_src.contains(&pipe) {
+                    tiles.step((x, y), *delta).filter(|&(nx, ny)| {
+                        !visited[ny][nx] && valid_dest.contains(&tiles[(nx, ny)])
+                    })
+                } else {
+                    None
+                }
+            }) {
                 *current = r;
 
                 Some(i)
@@ -105,46 +93,127 @@ fn solve(input: &str) -> (u32, Vec<&[u8]>, Vec<Vec<bool>>, u8) {
             }
         })
         .last()
-        .unwrap();
+        .ok_or_else(not_a_loop)?;
 
-    (steps, tiles, visited, *s)
+    Ok((steps, tiles, visited, *s))
 }
 
-pub fn solve_1(input: &str) -> u32 {
-    let (steps, _, _, _) = solve(input);
-
+fn farthest((steps, _, _, _): &Loop) -> u32 {
     (steps + 1) / 2
 }
 
-pub fn solve_2(input: &str) -> u32 {
-    let (_, tiles, visited, s) = solve(input);
-
-    visited
-        .iter()
-        .enumerate()
-        .map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .scan(false, |inside, (x, &v)| {
-                    if v {
-                        match tiles[y][x] {
+/// Tiles enclosed by the loop.
+fn inside<'a>((_, tiles, visited, s): &'a Loop) -> impl Iterator<Item = (usize, usize)> + 'a {
+    visited.iter().enumerate().flat_map(move |(y, row)| {
+        row.iter()
+            .enumerate()
+            .scan(false, move |inside, (x, &v)| {
+                if v {
+                    match tiles[(x, y)] {
+                        b'|' | b'L' | b'J' => *inside = !*inside,
+                        b'S' => match *s {
                             b'|' | b'L' | b'J' => *inside = !*inside,
-                            b'S' => match s {
-                                b'|' | b'L' | b'J' => *inside = !*inside,
-                                _ => {}
-                            },
                             _ => {}
-                        }
-                        Some(0)
-                    } else if *inside {
-                        Some(1)
-                    } else {
-                        Some(0)
+                        },
+                        _ => {}
                     }
-                })
-                .sum::<u32>()
+                    Some(None)
+                } else if *inside {
+                    Some(Some((x, y)))
+                } else {
+                    Some(None)
+                }
+            })
+            .flatten()
+    })
+}
+
+fn enclosed(input: &Loop) -> u32 {
+    inside(input).count() as u32
+}
+
+/// Tiles of the loop.
+fn pipes<'a>((_, _, visited, _): &'a Loop) -> impl Iterator<Item = (usize, usize)> + 'a {
+    visited.iter().enumerate().flat_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .filter(|(_, &v)| v)
+            .map(move |(x, _)| (x, y))
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 4),
+        aoc::example!("example2", Part::One, 8),
+        aoc::example!("example1", Part::Two, 1),
+        aoc::example!("example3", Part::Two, 4),
+        aoc::example!("example4", Part::Two, 8),
+        aoc::example!("example5", Part::Two, 10),
+    ];
+
+    type Input<'a> = Loop<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        find_loop(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Output1 {
+        farthest(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Output2 {
+        enclosed(input)
+    }
+
+    fn try_part_overlay(
+        part: Part,
+        input: &Self::Input<'_>,
+        _: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let overlay = Overlay::default().cells(Color::ORANGE, pipes(input));
+        Ok(match part {
+            Part::One => (farthest(input).to_string(), Some(overlay)),
+            Part::Two => {
+                let inside = inside(input).collect::<Vec<_>>();
+                (
+                    inside.len().to_string(),
+                    Some(overlay.cells(Color::GREEN, inside)),
+                )
+            }
         })
-        .sum()
+    }
+}
+
+pub fn solve_1(input: &str) -> u32 {
+    Day10::solve_1(input)
+}
+
+pub fn solve_2(input: &str) -> u32 {
+    Day10::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day10::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day10::try_solve_2(input)
 }
 
 pub fn part_1() -> u32 {
@@ -157,6 +226,8 @@ pub fn part_2() -> u32 {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     lazy_static! {
@@ -196,4 +267,29 @@ mod tests {
     fn same_results_2_1() {
         assert_eq!(solve_2(&EXAMPLE_1), 1);
     }
+
+    #[test]
+    fn overlay() {
+        let (timed, overlay) =
+            Day10::try_solve_overlay(Part::Two, &EXAMPLE_4, &mut Duration::default).unwrap();
+        let overlay = overlay.unwrap();
+        assert_eq!(overlay.cells.len(), 2);
+        assert_eq!(overlay.cells[1].positions.len(), 8);
+        assert_eq!(timed.answer, "8");
+
+        let (timed, overlay) =
+            Day10::try_solve_overlay(Part::One, &EXAMPLE_2, &mut Duration::default).unwrap();
+        assert_eq!(overlay.unwrap().cells[0].positions.len(), 16);
+        assert_eq!(timed.answer, "8");
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1(".....\n.S-7.\n...|.\n.L-J.\n.....").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "S"));
+        assert_eq!(error.kind, ErrorKind::Invalid("start tile, not on a loop"));
+
+        let error = try_solve_1(".....\n.F-7.\n.|.|.\n.L-J.\n.....").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Missing("start tile S"));
+    }
 }
@@ -2,31 +2,26 @@
 
 use lazy_static::lazy_static;
 
+use aoc::grid::Grid;
+use aoc::parse::Parser;
+use aoc::{Example, ParseError, Part, Solution};
+
 use std::collections::HashSet;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-fn solve<const EXPANSION: u64>(input: &str) -> u64 {
-    let input = input.as_bytes();
-    let ncols = input
-        .iter()
-        .position(|&c| c == b'\n')
-        .expect("invalid input");
-
-    // assuming square input
-    let nrows = ncols;
-
-    let empty_columns = (0..ncols)
-        .filter(|c| (0..nrows).all(|r| input[r * (ncols + 1) + c] == b'.'))
+fn solve<const EXPANSION: u64>(image: &Grid<&[u8]>) -> u64 {
+    let empty_columns = (0..image.ncols())
+        .filter(|&c| image.column(c).all(|tile| tile == b'.'))
         .collect::<HashSet<_>>();
 
-    let galaxy = input
-        .chunks(ncols + 1)
+    let galaxy = image
+        .rows()
         .scan(0, |row_index, row| {
-            let galaxy_row = row[..row.len() - usize::from(row[row.len() - 1] == b'\n')]
+            let galaxy_row = row
                 .iter()
                 .enumerate()
                 .scan(0, |column_index, (c, tile)| {
@@ -71,12 +66,59 @@ fn solve<const EXPANSION: u64>(input: &str) -> u64 {
         .sum::<i64>() as _
 }
 
+fn parse(input: &str) -> Result<Grid<&[u8]>, ParseError> {
+    Grid::parse(Parser::new(input), input, |tile| {
+        matches!(tile, b'.' | b'#')
+    })
+}
+
 pub fn solve_1(input: &str) -> u64 {
-    solve::<2>(input)
+    Day11::solve_1(input)
 }
 
 pub fn solve_2(input: &str) -> u64 {
-    solve::<1_000_000>(input)
+    Day11::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day11::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day11::try_solve_2(input)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    const EXAMPLES: &'static [Example] = &[aoc::example!("example1", Part::One, 374)];
+
+    type Input<'a> = Grid<&'a [u8]>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(image: &Self::Input<'_>) -> Self::Output1 {
+        solve::<2>(image)
+    }
+
+    fn part_2(image: &Self::Input<'_>) -> Self::Output2 {
+        solve::<1_000_000>(image)
+    }
 }
 
 pub fn part_1() -> u64 {
@@ -102,11 +144,26 @@ mod tests {
 
     #[test]
     fn same_results_2_1() {
-        assert_eq!(solve::<10>(&EXAMPLE_1), 1030);
+        assert_eq!(solve::<10>(&Day11::parse(&EXAMPLE_1)), 1030);
+    }
+
+    #[test]
+    fn not_square() {
+        assert_eq!(solve_1("#..#\n....\n.#.."), 4 + 4 + 6);
     }
 
     #[test]
     fn same_results_2_2() {
-        assert_eq!(solve::<100>(&EXAMPLE_1), 8410);
+        assert_eq!(solve::<100>(&Day11::parse(&EXAMPLE_1)), 8410);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("...#\n.#.\n....").unwrap_err();
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidRowLength);
+
+        let error = try_solve_1("..\n.*").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidTile);
     }
 }
@@ -1,8 +1,7 @@
 fn main() {
     let model_props = ui::ModelProps {
         input: day11::INPUT.to_string(),
-        solve_1: day11::solve_1,
-        solve_2: day11::solve_2,
+        puzzle: &day11::Day11,
     };
-    yew::Renderer::<ui::Model<_, _, _, _>>::with_props(model_props).render();
+    yew::Renderer::<ui::Model>::with_props(model_props).render();
 }
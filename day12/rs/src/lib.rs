@@ -3,8 +3,12 @@
 
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
+use std::io::BufRead;
 use std::iter;
-use std::str::FromStr;
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -20,33 +24,85 @@ mod norecursion;
 use norecursion::arrangements;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> u64 {
+/// Condition record: springs and sizes of the groups of damaged
+/// springs.
+type Record<'a> = (&'a [u8], Vec<usize>);
+
+fn parse_line<'a>(parser: Parser, line: &'a str) -> Result<Record<'a>, ParseError> {
+    let (springs, groups) = parser.split_once(line, " ")?;
+
+    if let Some(i) = springs
+        .bytes()
+        .position(|spring| !matches!(spring, b'.' | b'#' | b'?'))
+    {
+        return Err(parser.error(&springs[i..=i], ErrorKind::InvalidTile));
+    }
+
+    let groups = groups
+        .split(',')
+        .map(|group| parser.number(group))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((springs.as_bytes(), groups))
+}
+
+fn parse(input: &str) -> Result<Vec<Record<'_>>, ParseError> {
+    let parser = Parser::new(input);
+
+    input.lines().map(|line| parse_line(parser, line)).collect()
+}
+
+fn arrangements_sum(records: &[Record]) -> u64 {
     #[cfg(feature = "rayon")]
-    let lines = input.par_lines();
+    let records = records.par_iter();
 
     #[cfg(not(feature = "rayon"))]
-    let lines = input.lines();
+    let records = records.iter();
 
-    lines
-        .map(|line| {
-            let (line, groups) = line.split_once(' ').expect("invalid input");
+    records
+        .map(|(line, groups)| arrangements(line, groups))
+        .sum()
+}
 
-            let groups = groups
-                .split(',')
-                .map(usize::from_str)
-                .collect::<Result<Vec<_>, _>>()
-                .expect("invalid number");
+fn unfolded_arrangements_sum(records: &[Record]) -> u64 {
+    #[cfg(feature = "rayon")]
+    let records = records.par_iter();
 
-            arrangements(line.as_bytes(), &groups)
-        })
-        .sum()
+    #[cfg(not(feature = "rayon"))]
+    let records = records.iter();
+
+    records.map(unfolded_arrangements).sum()
+}
+
+fn unfolded_arrangements((line, groups): &Record) -> u64 {
+    let groups = iter::once(groups)
+        .cycle()
+        .take(5)
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+
+    let l = line.len();
+    let line = line
+        .iter()
+        .chain(iter::once(&b'?'))
+        .cycle()
+        .take((l + 1) * 5 - 1)
+        .copied()
+        .collect::<Vec<_>>();
+
+    arrangements(&line, &groups)
+}
+
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> u64 {
+    Day12::solve_1(input)
 }
 
 /// Solve part 2
@@ -54,41 +110,87 @@ pub fn solve_1(input: &str) -> u64 {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> u64 {
-    #[cfg(feature = "rayon")]
-    let lines = input.par_lines();
+    Day12::solve_2(input)
+}
 
-    #[cfg(not(feature = "rayon"))]
-    let lines = input.lines();
-
-    lines
-        .map(|input| {
-            let (line, groups) = input.split_once(' ').expect("invalid input");
-
-            let groups = groups
-                .split(',')
-                .map(usize::from_str)
-                .collect::<Result<Vec<_>, _>>()
-                .expect("invalid number");
-
-            let groups = iter::once(groups)
-                .cycle()
-                .take(5)
-                .flatten()
-                .collect::<Vec<_>>();
-
-            let l = line.as_bytes().len();
-            let line = line
-                .as_bytes()
-                .iter()
-                .chain(iter::once(&b'?'))
-                .cycle()
-                .take((l + 1) * 5 - 1)
-                .copied()
-                .collect::<Vec<_>>();
-
-            arrangements(&line, &groups) as u64
-        })
-        .sum()
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day12::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day12::try_solve_2(input)
+}
+
+fn solve_reader<R: BufRead>(
+    reader: R,
+    arrangements: impl Fn(&Record) -> u64,
+) -> Result<u64, ReadError> {
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        sum += arrangements(&parse_line(Parser::new(line), line)?);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Solve part 1 reading the input a line at a time, one record after
+/// the other even with the `rayon` feature
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<u64, ReadError> {
+    solve_reader(reader, |(line, groups)| arrangements(line, groups))
+}
+
+/// Solve part 2 reading the input a line at a time, one record after
+/// the other even with the `rayon` feature
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<u64, ReadError> {
+    solve_reader(reader, unfolded_arrangements)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hot Springs";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+        #[cfg(feature = "recursion")]
+        "recursion",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 21),
+        aoc::example!("example1", Part::Two, 525152),
+    ];
+
+    type Input<'a> = Vec<Record<'a>>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(records: &Self::Input<'_>) -> Self::Output1 {
+        arrangements_sum(records)
+    }
+
+    fn part_2(records: &Self::Input<'_>) -> Self::Output2 {
+        unfolded_arrangements_sum(records)
+    }
 }
 
 pub fn part_1() -> u64 {
@@ -117,6 +219,35 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 525152);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("???.### 1,1,3\n.??..??...?##. 1,,3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 18));
+        assert_eq!(error.kind, ErrorKind::InvalidNumber);
+
+        let error = try_solve_2("???.#x# 1,1,3").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 6, "x"));
+        assert_eq!(error.kind, ErrorKind::InvalidTile);
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_1_reader("???.### 1,1,3\n.??..??...?##. 1,,3".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 18));
+    }
+
     #[test]
     fn test_sample_1_1() {
         assert_eq!(solve_1("???.### 1,1,3"), 1);
@@ -3,168 +3,112 @@
 
 use lazy_static::lazy_static;
 
-use std::str::from_utf8;
+use aoc::grid::Grid;
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
-struct Land<'a> {
-    land: &'a [u8],
-    ncols: usize,
-}
+pub type Land<'a> = Grid<&'a [u8]>;
 
-impl<'a> Land<'a> {
-    fn parse(land: &'a str) -> Result<Self, &'static str> {
-        let land = land.as_bytes();
-        let ncols = land
-            .iter()
-            .position(|&c| c == b'\n')
-            .ok_or("invalid land")?;
+fn parse_land<'a>(parser: Parser, land: &'a str) -> Result<Land<'a>, ParseError> {
+    Grid::parse(parser, land, |tile| matches!(tile, b'.' | b'#'))
+}
 
-        Ok(Self { land, ncols })
-    }
+/// Mirrored rows or columns around the mirror before `index`.
+fn reflections(index: usize, len: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..index).rev().zip(index..len)
 }
 
-fn find_horizontal_mirror(Land { land, ncols }: &Land) -> Option<usize> {
-    let mut r = 1;
-    loop {
-        let found = (0..r).rev().zip(r..).all(|(lr, hr)| {
-            let row_upper = land
-                .get((ncols + 1) * lr..(ncols + 1) * lr + ncols)
-                .unwrap();
-            if let Some(row_lower) = land.get((ncols + 1) * hr..(ncols + 1) * hr + ncols) {
-                row_upper == row_lower
-            } else {
-                true
-            }
-        });
-        if found {
-            break Some(r);
-        }
-
-        r += 1;
-        if land.get((ncols + 1) * r).is_none() {
-            break None;
-        }
-    }
+fn find_horizontal_mirror(land: &Land) -> Option<usize> {
+    (1..land.nrows())
+        .find(|&r| reflections(r, land.nrows()).all(|(lr, hr)| land.row(lr) == land.row(hr)))
 }
 
-#[allow(clippy::maybe_infinite_iter)]
-fn find_vertical_mirror(Land { land, ncols }: &Land) -> Option<usize> {
-    let mut c = 1;
-    loop {
-        let found = (0..c).rev().zip(c..*ncols).all(|(lc, hc)| {
-            (0..)
-                .scan((), |_, r| {
-                    land.get((ncols + 1) * r + lc)
-                        .and_then(|l| land.get((ncols + 1) * r + hc).map(|h| l == h))
-                })
-                .all(|v| v)
-        });
-        if found {
-            break Some(c);
-        }
-
-        c += 1;
-        if c >= *ncols {
-            break None;
-        }
-    }
+fn find_vertical_mirror(land: &Land) -> Option<usize> {
+    (1..land.ncols())
+        .find(|&c| reflections(c, land.ncols()).all(|(lc, hc)| land.column(lc).eq(land.column(hc))))
 }
 
-fn find_horizontal_mirror_1(Land { land, ncols }: &Land) -> Option<usize> {
-    let mut r = 1;
-    loop {
-        let count = (0..r).rev().zip(r..).fold(0, |mut count, (lr, hr)| {
-            let row_upper = land
-                .get((ncols + 1) * lr..(ncols + 1) * lr + ncols)
-                .unwrap();
-            if let Some(row_lower) = land.get((ncols + 1) * hr..(ncols + 1) * hr + ncols) {
-                count += row_upper
+fn find_horizontal_mirror_1(land: &Land) -> Option<usize> {
+    (1..land.nrows()).find(|&r| {
+        reflections(r, land.nrows())
+            .map(|(lr, hr)| {
+                land.row(lr)
                     .iter()
-                    .zip(row_lower.iter())
-                    .map(|(l, r)| usize::from(l != r))
-                    .sum::<usize>();
-            }
-
-            count
-        });
-        if count == 1 {
-            break Some(r);
-        }
-
-        r += 1;
-        if land.get((ncols + 1) * r).is_none() {
-            break None;
-        }
-    }
+                    .zip(land.row(hr))
+                    .filter(|(l, h)| l != h)
+                    .count()
+            })
+            .sum::<usize>()
+            == 1
+    })
 }
 
-#[allow(clippy::maybe_infinite_iter)]
-fn find_vertical_mirror_1(Land { land, ncols }: &Land) -> Option<usize> {
-    let mut c = 1;
-    loop {
-        let count = (0..c)
-            .rev()
-            .zip(c..*ncols)
-            .flat_map(|(lc, hc)| {
-                (0..).scan((), move |_, r| {
-                    land.get((ncols + 1) * r + lc)
-                        .and_then(|l| land.get((ncols + 1) * r + hc).map(|h| usize::from(l != h)))
-                })
+fn find_vertical_mirror_1(land: &Land) -> Option<usize> {
+    (1..land.ncols()).find(|&c| {
+        reflections(c, land.ncols())
+            .map(|(lc, hc)| {
+                land.column(lc)
+                    .zip(land.column(hc))
+                    .filter(|(l, h)| l != h)
+                    .count()
             })
-            .sum::<usize>();
-        if count == 1 {
-            break Some(c);
-        }
-
-        c += 1;
-        if c >= *ncols {
-            break None;
-        }
-    }
+            .sum::<usize>()
+            == 1
+    })
 }
 
-fn solve(
-    input: &str,
+fn parse(input: &str) -> Result<Vec<Land<'_>>, ParseError> {
+    let parser = Parser::new(input);
+
+    input
+        .split("\n\n")
+        .map(|land| parse_land(parser, land))
+        .collect()
+}
+
+/// Sum the mirror summaries, or return the first land without a mirror.
+fn solve<'a, 'b>(
+    lands: &'b [Land<'a>],
     find_horizontal_mirror: impl Fn(&Land) -> Option<usize> + Sync,
     find_vertical_mirror: impl Fn(&Land) -> Option<usize> + Sync,
-) -> usize {
+) -> Result<usize, &'b Land<'a>> {
     #[cfg(not(feature = "rayon"))]
-    let input = input.split("\n\n");
+    let lands = lands.iter();
 
     #[cfg(feature = "rayon")]
-    let input = input.split("\n\n").par_bridge();
+    let lands = lands.par_iter();
 
-    input
+    lands
         .map(|land| {
-            let land = Land::parse(land).expect("invalid input");
-
-            find_horizontal_mirror(&land).map_or_else(
-                || {
-                    find_vertical_mirror(&land).unwrap_or_else(|| {
-                        panic!(
-                            "cannot find neither vertical or horizontal mirror: \n{}",
-                            from_utf8(land.land).unwrap()
-                        )
-                    })
-                },
-                |columns| columns * 100,
+            find_horizontal_mirror(land).map_or_else(
+                || find_vertical_mirror(land).ok_or(land),
+                |columns| Ok(columns * 100),
             )
         })
         .sum()
 }
 
+fn no_mirror(land: &Land) -> ! {
+    panic!("cannot find neither vertical or horizontal mirror: \n{land}")
+}
+
+fn no_mirror_error(input: &str, land: &Land) -> ParseError {
+    Parser::new(input).error_bytes(land.row(0), ErrorKind::Missing("mirror"))
+}
+
 /// Solve part 1
 ///
 /// # Panics
 /// Panic if invalid input
 pub fn solve_1(input: &str) -> usize {
-    solve(input, find_horizontal_mirror, find_vertical_mirror)
+    Day13::solve_1(input)
 }
 
 /// Solve part 2
@@ -172,7 +116,67 @@ pub fn solve_1(input: &str) -> usize {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> usize {
-    solve(input, find_horizontal_mirror_1, find_vertical_mirror_1)
+    Day13::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day13::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day13::try_solve_2(input)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Point of Incidence";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 405),
+        aoc::example!("example1", Part::Two, 400),
+    ];
+
+    type Input<'a> = Vec<Land<'a>>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(lands: &Self::Input<'_>) -> Self::Output1 {
+        solve(lands, find_horizontal_mirror, find_vertical_mirror)
+            .unwrap_or_else(|land| no_mirror(land))
+    }
+
+    fn part_2(lands: &Self::Input<'_>) -> Self::Output2 {
+        solve(lands, find_horizontal_mirror_1, find_vertical_mirror_1)
+            .unwrap_or_else(|land| no_mirror(land))
+    }
+
+    fn try_part_1(lands: &Self::Input<'_>, input: &str) -> Result<Self::Output1, ParseError> {
+        solve(lands, find_horizontal_mirror, find_vertical_mirror)
+            .map_err(|land| no_mirror_error(input, land))
+    }
+
+    fn try_part_2(lands: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        solve(lands, find_horizontal_mirror_1, find_vertical_mirror_1)
+            .map_err(|land| no_mirror_error(input, land))
+    }
 }
 
 pub fn part_1() -> usize {
@@ -200,28 +204,40 @@ mod tests {
     fn test_horizontal_mirror_ok() {
         let (_, land) = EXAMPLE_1.split_once("\n\n").unwrap();
 
-        assert_eq!(find_horizontal_mirror(&Land::parse(land).unwrap()), Some(4));
+        assert_eq!(
+            find_horizontal_mirror(&parse_land(Parser::new(land), land).unwrap()),
+            Some(4)
+        );
     }
 
     #[test]
     fn test_horizontal_mirror_ko() {
         let (land, _) = EXAMPLE_1.split_once("\n\n").unwrap();
 
-        assert_eq!(find_horizontal_mirror(&Land::parse(land).unwrap()), None);
+        assert_eq!(
+            find_horizontal_mirror(&parse_land(Parser::new(land), land).unwrap()),
+            None
+        );
     }
 
     #[test]
     fn test_vertical_mirror_ko() {
         let (_, land) = EXAMPLE_1.split_once("\n\n").unwrap();
 
-        assert_eq!(find_vertical_mirror(&Land::parse(land).unwrap()), None);
+        assert_eq!(
+            find_vertical_mirror(&parse_land(Parser::new(land), land).unwrap()),
+            None
+        );
     }
 
     #[test]
     fn test_vertical_mirror_ok() {
         let (land, _) = EXAMPLE_1.split_once("\n\n").unwrap();
 
-        assert_eq!(find_vertical_mirror(&Land::parse(land).unwrap()), Some(5));
+        assert_eq!(
+            find_vertical_mirror(&parse_land(Parser::new(land), land).unwrap()),
+            Some(5)
+        );
     }
 
     #[test]
@@ -234,7 +250,7 @@ mod tests {
         let (land, _) = EXAMPLE_1.split_once("\n\n").unwrap();
 
         assert_eq!(
-            find_horizontal_mirror_1(&Land::parse(land).unwrap()),
+            find_horizontal_mirror_1(&parse_land(Parser::new(land), land).unwrap()),
             Some(3)
         );
     }
@@ -243,6 +259,23 @@ mod tests {
     fn test_vertical_mirror_1_ko() {
         let (_, land) = EXAMPLE_1.split_once("\n\n").unwrap();
 
-        assert_eq!(find_vertical_mirror_1(&Land::parse(land).unwrap()), None);
+        assert_eq!(
+            find_vertical_mirror_1(&parse_land(Parser::new(land), land).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("#.#\n#.#\n\n#.\n.#.").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.kind, ErrorKind::InvalidRowLength);
+
+        let error = try_solve_1("#.#\n#.#\n\n#..\n.#.").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (4, 1, "#..")
+        );
+        assert_eq!(error.kind, ErrorKind::Missing("mirror"));
     }
 }
@@ -9,15 +9,18 @@ lazy_static! {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
+    // read the input first, for the usage of the parts alone
+    lazy_static::initialize(&INPUT);
+    aoc::memory::report("part 1", part_1);
+    aoc::memory::report("part 2", part_2);
+
     c.bench_function("part 1", |b| b.iter(part_1));
     c.bench_function("part 2", |b| b.iter(part_2));
 }
 
 fn bench_simple(c: &mut Criterion) {
     {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).expect("invalid input");
-
-        let tiles = tiles.to_vec();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).expect("invalid input"));
 
         c.bench_function("simple cycle example 1", |b| {
             b.iter(|| black_box(simple::cycle(tiles.clone(), ncols, nrows)))
@@ -28,9 +31,7 @@ fn bench_simple(c: &mut Criterion) {
     }
 
     {
-        let (tiles, ncols, nrows) = parse(&INPUT).expect("invalid input");
-
-        let tiles = tiles.to_vec();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&INPUT).expect("invalid input"));
 
         c.bench_function("simple cycle input", |b| {
             b.iter(|| black_box(simple::cycle(tiles.clone(), ncols, nrows)))
@@ -43,9 +44,7 @@ fn bench_simple(c: &mut Criterion) {
 
 #[cfg(feature = "simd")]
 fn bench_simd(c: &mut Criterion) {
-    let (tiles, ncols, nrows) = parse(&EXAMPLE_1).expect("invalid input");
-
-    let tiles = tiles.to_vec();
+    let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).expect("invalid input"));
 
     c.bench_function("simd1 cycle example 1", |b| {
         b.iter(|| black_box(simd::simd1::cycle::<16>(tiles.clone(), ncols, nrows)))
@@ -58,9 +57,7 @@ fn bench_simd(c: &mut Criterion) {
 #[cfg(feature = "simd")]
 fn bench_simd2(c: &mut Criterion) {
     {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).expect("invalid input");
-
-        let tiles = tiles.to_vec();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).expect("invalid input"));
 
         c.bench_function("simd2 cycle example 1", |b| {
             b.iter(|| black_box(simd::simd2::cycle::<8>(tiles.clone(), ncols, nrows)))
@@ -71,9 +68,7 @@ fn bench_simd2(c: &mut Criterion) {
     }
 
     {
-        let (tiles, ncols, nrows) = parse(&INPUT).expect("invalid input");
-
-        let tiles = tiles.to_vec();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&INPUT).expect("invalid input"));
 
         c.bench_function("simd2 cycle input", |b| {
              b.iter(|| black_box(simd::simd2::cycle::<64>(tiles.clone(), ncols, nrows)))
@@ -4,6 +4,11 @@
 
 use lazy_static::lazy_static;
 
+use aoc::grid::Grid;
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{Example, Overlay, ParseError, Part, Solution};
+
 use std::collections::HashMap;
 
 pub mod simple;
@@ -13,55 +18,47 @@ use simple::{cycle, load};
 pub mod simd;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 /// Parse input.
 ///
 /// # Errors
 /// Error if invalid input.
-pub fn parse(input: &str) -> Result<(&[u8], usize, usize), &'static str> {
-    let tiles = input.as_bytes();
-    let ncols = tiles
-        .iter()
-        .position(|&t| t == b'\n')
-        .ok_or("invalid input")?;
-    let (tiles, nrows) = {
-        let mut tiles = tiles;
-        loop {
-            let t = tiles[tiles.len() - 1];
-
-            if t.is_ascii_whitespace() {
-                tiles = &tiles[0..tiles.len() - 1];
-            } else {
-                break;
-            }
-        }
+pub fn parse(input: &str) -> Result<Grid<&[u8]>, ParseError> {
+    Grid::parse(Parser::new(input), input, |tile| {
+        matches!(tile, b'O' | b'#' | b'.')
+    })
+}
 
-        (tiles, (tiles.len() + 1) / (ncols + 1))
-    };
+/// Tiles, number of columns and number of rows, laid out as [`simple`]
+/// and `simd` want them: every row followed by a newline.
+pub fn cycle_tiles(grid: &Grid<&[u8]>) -> (Vec<u8>, usize, usize) {
+    let tiles = grid
+        .rows()
+        .flat_map(|row| row.iter().chain(b"\n"))
+        .copied()
+        .collect();
 
-    Ok((tiles, ncols, nrows))
+    (tiles, grid.ncols(), grid.nrows())
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> usize {
-    let (tiles, ncols, nrows) = parse(input).expect("invalid input");
-
-    (0..ncols)
-        .zip(vec![nrows; ncols].iter_mut())
-        .map(|(c, state)| {
-            (0..nrows)
-                .map(|r| match tiles.get((ncols + 1) * r + c) {
-                    Some(b'O') => {
+fn north_load(tiles: &Grid<&[u8]>) -> usize {
+    let nrows = tiles.nrows();
+    let mut states = vec![nrows; tiles.ncols()];
+    tiles
+        .rows()
+        .enumerate()
+        .map(|(r, row)| {
+            row.iter()
+                .zip(&mut states)
+                .map(|(tile, state)| match tile {
+                    b'O' => {
                         let weight = *state;
                         *state -= 1;
                         weight
                     }
-                    Some(b'#') => {
+                    b'#' => {
                         *state = nrows - (r + 1);
                         0
                     }
@@ -72,25 +69,90 @@ pub fn solve_1(input: &str) -> usize {
         .sum()
 }
 
-/// Solve part 2
-///
-/// # Panics
-/// Panic if input is invalid
-pub fn solve_2(input: &str) -> usize {
-    let (tiles, ncols, nrows) = parse(input).expect("invalid input");
+/// Rounded rocks after tilting the platform north.
+fn rolled_north(tiles: &Grid<&[u8]>) -> Vec<(usize, usize)> {
+    let mut free = vec![0; tiles.ncols()];
+    let mut rocks = vec![];
+    for (y, row) in tiles.rows().enumerate() {
+        for (x, (tile, free)) in row.iter().zip(&mut free).enumerate() {
+            match tile {
+                b'O' => {
+                    rocks.push((x, *free));
+                    *free += 1;
+                }
+                b'#' => *free = y + 1,
+                _ => {}
+            }
+        }
+    }
+    rocks
+}
 
+fn north_load_after_cycles(grid: &Grid<&[u8]>) -> usize {
+    let (tiles, ncols, nrows) = after_cycles(grid);
+    load(&tiles, ncols, nrows)
+}
+
+/// One spin cycle, with the `simd1` or `simd2` cycle the build selects when
+/// the map fits its lanes, else with the [`simple`] one.
+fn spin(tiles: Vec<u8>, ncols: usize, nrows: usize) -> Vec<u8> {
+    #[cfg(feature = "simd1")]
+    {
+        let size = ncols.max(nrows);
+        if size < 16 {
+            return simd::simd1::cycle::<16>(tiles, ncols, nrows);
+        }
+        if size < 32 {
+            return simd::simd1::cycle::<32>(tiles, ncols, nrows);
+        }
+        if size < 64 {
+            return simd::simd1::cycle::<64>(tiles, ncols, nrows);
+        }
+    }
+
+    #[cfg(feature = "simd2")]
+    {
+        let fits = |lanes| ncols.min(nrows) > lanes && ncols.max(nrows) < 2 * lanes;
+        if fits(8) {
+            return simd::simd2::cycle::<8>(tiles, ncols, nrows);
+        }
+        if fits(16) {
+            return simd::simd2::cycle::<16>(tiles, ncols, nrows);
+        }
+        if fits(32) {
+            return simd::simd2::cycle::<32>(tiles, ncols, nrows);
+        }
+        if fits(64) {
+            return simd::simd2::cycle::<64>(tiles, ncols, nrows);
+        }
+    }
+
+    cycle(tiles, ncols, nrows)
+}
+
+/// Index of the tiles after `cycles` spin cycles, the tiles after the
+/// spin cycle at index `end` being the ones at index `start`, the tiles
+/// after the first spin cycle being at index 0.
+fn cycle_index(start: usize, end: usize, cycles: usize) -> usize {
+    start + (cycles - 1 - start) % (end - start)
+}
+
+/// Tiles, laid out as [`cycle_tiles`], after the spin cycles.
+fn after_cycles(grid: &Grid<&[u8]>) -> (Vec<u8>, usize, usize) {
+    let (mut tiles, ncols, nrows) = cycle_tiles(grid);
     let mut history: HashMap<Vec<u8>, usize> = HashMap::with_capacity(1_024);
-    let mut tiles = tiles.to_owned();
+    let _span = aoc::span!("cycle_search");
     for i in 0.. {
-        tiles = cycle(tiles, ncols, nrows);
+        tiles = spin(tiles, ncols, nrows);
         if let Some(old_i) = history.get(&tiles) {
-            let t = old_i - 1 + (1_000_000_000 - old_i) % (i - old_i);
+            aoc::event!(start = old_i, length = i - old_i, "cycle found");
+            let t = cycle_index(*old_i, i, 1_000_000_000);
 
             return history
-                .iter()
-                .find_map(|(tiles, &i)| {
+                .into_iter()
+                .find_map(|(tiles, i)| {
                     if i == t {
-                        Some(load(tiles, ncols, nrows))
+                        Some((tiles, ncols, nrows))
                     } else {
                         None
                     }
@@ -104,6 +166,96 @@ pub fn solve_2(input: &str) -> usize {
     unreachable!()
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Parabolic Reflector Dish";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "simd1")]
+        "simd1",
+        #[cfg(feature = "simd2")]
+        "simd2",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 136),
+        aoc::example!("example1", Part::Two, 64),
+    ];
+
+    type Input<'a> = Grid<&'a [u8]>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(tiles: &Self::Input<'_>) -> Self::Output1 {
+        north_load(tiles)
+    }
+
+    fn part_2(tiles: &Self::Input<'_>) -> Self::Output2 {
+        north_load_after_cycles(tiles)
+    }
+
+    fn try_part_overlay(
+        part: Part,
+        tiles: &Self::Input<'_>,
+        _: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let rocks = match part {
+            Part::One => rolled_north(tiles),
+            Part::Two => {
+                let (tiles, ncols, _) = after_cycles(tiles);
+                tiles
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &tile)| tile == b'O')
+                    .map(|(i, _)| (i % (ncols + 1), i / (ncols + 1)))
+                    .collect()
+            }
+        };
+        let load = rocks.iter().map(|&(_, y)| tiles.nrows() - y).sum::<usize>();
+        Ok((
+            load.to_string(),
+            Some(Overlay::default().cells(Color::ORANGE, rocks)),
+        ))
+    }
+}
+
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> usize {
+    Day14::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if input is invalid
+pub fn solve_2(input: &str) -> usize {
+    Day14::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day14::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day14::try_solve_2(input)
+}
+
 pub fn part_1() -> usize {
     solve_1(&INPUT)
 }
@@ -114,6 +266,8 @@ pub fn part_2() -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     lazy_static! {
@@ -129,4 +283,51 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 64);
     }
+
+    #[test]
+    fn trailing_blank_line() {
+        let input = format!("{}\n\n", *EXAMPLE_1);
+        assert_eq!(try_solve_1(&input), Ok(136));
+        assert_eq!(try_solve_2(&input), Ok(64));
+    }
+
+    #[test]
+    fn cycle_index_in_period() {
+        // the tiles at 1 and 2 repeat from 3, 11 cycles end at index 10
+        assert_eq!(cycle_index(1, 3, 11), 2);
+        assert_eq!(cycle_index(1, 3, 12), 1);
+        assert_eq!(cycle_index(2, 5, 11), 4);
+        // the tiles after the first spin cycle repeat
+        assert_eq!(cycle_index(0, 1, 1_000_000_000), 0);
+        assert_eq!(cycle_index(0, 4, 1_000_000_000), 3);
+    }
+
+    #[test]
+    fn first_tiles_repeat() {
+        // every spin cycle leaves the rock where the first one does
+        assert_eq!(try_solve_2("O.\n..\n"), Ok(1));
+        assert_eq!(try_solve_2(".O\n#.\n"), Ok(2));
+    }
+
+    #[test]
+    fn overlay() {
+        for (part, load) in [(Part::One, 136), (Part::Two, 64)] {
+            let (timed, overlay) =
+                Day14::try_solve_overlay(part, &EXAMPLE_1, &mut Duration::default).unwrap();
+            let rocks = &overlay.unwrap().cells[0].positions;
+            assert_eq!(rocks.len(), 18);
+            assert_eq!(rocks.iter().map(|(_, y)| 10 - y).sum::<usize>(), load);
+            assert_eq!(timed.answer, load.to_string());
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("O.#\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidTile);
+
+        let error = try_solve_2("O.#\n..\n").unwrap_err();
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidRowLength);
+    }
 }
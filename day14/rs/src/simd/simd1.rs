@@ -155,8 +155,8 @@ where
 mod test {
     use lazy_static::lazy_static;
 
-    use crate::parse;
     use crate::simple;
+    use crate::{cycle_tiles, parse};
 
     use super::*;
 
@@ -166,7 +166,7 @@ mod test {
 
     #[test]
     fn test_same_results_for_load() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         assert_eq!(
             load::<16>(&tiles, ncols, nrows),
@@ -176,7 +176,7 @@ mod test {
 
     #[test]
     fn test_same_results_for_cycle() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let simd_r = cycle::<16>(tiles.to_vec(), ncols, nrows);
         let simple_r = simple::cycle(tiles.to_vec(), ncols, nrows);
@@ -192,7 +192,7 @@ mod test {
     #[test]
     #[should_panic(expected = "invalid LANES, must be > (nrows, ncols)")]
     fn test_load_with_invalid_lanes() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let _ = load::<8>(&tiles, ncols, nrows);
     }
@@ -200,7 +200,7 @@ mod test {
     #[test]
     #[should_panic(expected = "invalid LANES, must be > (nrows, ncols)")]
     fn test_cycle_with_invalid_lanes() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let _ = cycle::<8>(tiles.to_vec(), ncols, nrows);
     }
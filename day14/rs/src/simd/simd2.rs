@@ -168,7 +168,7 @@ where
 mod test {
     use lazy_static::lazy_static;
 
-    use crate::parse;
+    use crate::{cycle_tiles, parse};
     use super::*;
     use crate::simple;
 
@@ -178,7 +178,7 @@ mod test {
 
     #[test]
     fn test_same_results_for_load() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         assert_eq!(
             load::<8>(&tiles, ncols, nrows),
@@ -188,7 +188,7 @@ mod test {
 
     #[test]
     fn test_same_results_for_cycle() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let simd_r = cycle::<8>(tiles.to_vec(), ncols, nrows);
         let simple_r = simple::cycle(tiles.to_vec(), ncols, nrows);
@@ -204,7 +204,7 @@ mod test {
     #[test]
     #[should_panic(expected = "invalid LANES")]
     fn test_load_with_invalid_lanes() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let _ = load::<16>(&tiles, ncols, nrows);
     }
@@ -212,7 +212,7 @@ mod test {
     #[test]
     #[should_panic(expected = "invalid LANES, must be > (nrows, ncols)")]
     fn test_cycle_with_invalid_lanes() {
-        let (tiles, ncols, nrows) = parse(&EXAMPLE_1).unwrap();
+        let (tiles, ncols, nrows) = cycle_tiles(&parse(&EXAMPLE_1).unwrap());
 
         let _ = cycle::<8>(tiles.to_vec(), ncols, nrows);
     }
@@ -3,10 +3,13 @@
 
 use lazy_static::lazy_static;
 
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 use std::array;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 fn hash<'a>(part: impl IntoIterator<Item = &'a u8>) -> u32 {
@@ -15,35 +18,83 @@ fn hash<'a>(part: impl IntoIterator<Item = &'a u8>) -> u32 {
         .fold(0, |current, &c| (current + u32::from(c)) * 17 % 256)
 }
 
-pub fn solve_1(input: &str) -> u32 {
-    input.as_bytes().split(|&c| c == b',').map(hash).sum()
+/// Lens operation of a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Remove,
+    Insert(usize),
 }
 
-/// Solve part 2
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_2(input: &str) -> usize {
+/// Step of the initialization sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<'a> {
+    text: &'a [u8],
+    label: &'a [u8],
+    operation: Operation,
+}
+
+impl<'a> Step<'a> {
+    fn parse(parser: Parser, step: &'a str) -> Result<Self, ParseError> {
+        let Some(i) = step.find(['-', '=']) else {
+            return Err(parser.error(step, ErrorKind::Expected("- or =")));
+        };
+        let (label, operation) = step.split_at(i);
+        if label.is_empty() {
+            return Err(parser.error(step, ErrorKind::Missing("label")));
+        }
+
+        let operation = match operation.split_at(1) {
+            ("-", "") => Operation::Remove,
+            ("=", focal_length) => Operation::Insert(parser.number(focal_length)?),
+            (_, rest) => return Err(parser.error(rest, ErrorKind::Expected("end of step"))),
+        };
+
+        Ok(Self {
+            text: step.as_bytes(),
+            label: label.as_bytes(),
+            operation,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Step<'_>>, ParseError> {
+    let parser = Parser::new(input);
+
     input
-        .as_bytes()
-        .split(|&c| c == b',')
+        .trim_end()
+        .split(',')
+        .map(|step| Step::parse(parser, step))
+        .collect()
+}
+
+fn hash_sum(steps: &[Step]) -> u32 {
+    steps.iter().map(|step| hash(step.text)).sum()
+}
+
+fn focusing_power(steps: &[Step]) -> usize {
+    steps
+        .iter()
         .fold(
             &mut array::from_fn::<Vec<(&[u8], usize)>, 256, _>(|_| Vec::new()),
-            |boxes, lens| {
-                let mut parts = lens.split_inclusive(|&c| c == b'-' || c == b'=');
-                let label = parts.next().expect("label not found");
-                let (label, action) = { (&label[0..label.len() - 1], label[label.len() - 1]) };
-
+            |boxes,
+             &Step {
+                 label, operation, ..
+             }| {
                 let box_index = hash(label) as usize;
                 let b = &mut boxes[box_index];
-                if action == b'-' {
-                    if let Some(index) = b.iter().position(|(l, _)| l == &label) {
-                        b.remove(index);
+                match operation {
+                    Operation::Remove => {
+                        if let Some(index) = b.iter().position(|(l, _)| l == &label) {
+                            b.remove(index);
+                        }
+                    }
+                    Operation::Insert(focal_length) => {
+                        if let Some((_, v)) = b.iter_mut().find(|(l, _)| l == &label) {
+                            *v = focal_length;
+                        } else {
+                            b.push((label, focal_length));
+                        }
                     }
-                } else if let Some((_, v)) = b.iter_mut().find(|(l, _)| l == &label).as_mut() {
-                    *v = usize::from(parts.next().unwrap()[0] - b'0');
-                } else {
-                    b.push((label, usize::from(parts.next().unwrap()[0] - b'0')));
                 }
 
                 boxes
@@ -60,6 +111,66 @@ pub fn solve_2(input: &str) -> usize {
         .sum()
 }
 
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> u32 {
+    Day15::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_2(input: &str) -> usize {
+    Day15::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day15::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day15::try_solve_2(input)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Lens Library";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 1320),
+        aoc::example!("example1", Part::Two, 145),
+    ];
+
+    type Input<'a> = Vec<Step<'a>>;
+    type Output1 = u32;
+    type Output2 = usize;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(steps: &Self::Input<'_>) -> Self::Output1 {
+        hash_sum(steps)
+    }
+
+    fn part_2(steps: &Self::Input<'_>) -> Self::Output2 {
+        focusing_power(steps)
+    }
+}
+
 pub fn part_1() -> u32 {
     solve_1(&INPUT)
 }
@@ -85,4 +196,17 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 145);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_2("rn=1,cm-,qp=x").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 13, "x")
+        );
+        assert_eq!(error.kind, ErrorKind::InvalidNumber);
+
+        let error = try_solve_1("rn=1,cm").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Expected("- or ="));
+    }
 }
@@ -3,11 +3,16 @@
 
 use lazy_static::lazy_static;
 
+use aoc::grid::{Grid, NEIGHBORS_4};
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{Example, Overlay, ParseError, Part, Solution};
+
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -18,12 +23,6 @@ enum Direction {
     Left,
 }
 
-struct Grid<'a> {
-    data: &'a [u8],
-    nrows: usize,
-    ncols: usize,
-}
-
 struct Delta;
 
 impl From<Direction> for u8 {
@@ -52,36 +51,26 @@ impl From<u8> for Direction {
 impl Delta {
     #[inline]
     fn from(direction: Direction) -> (isize, isize) {
-        [(0, -1), (1, 0), (0, 1), (-1, 0)][u8::from(direction) as usize]
+        NEIGHBORS_4[u8::from(direction) as usize]
     }
 }
 
-fn parse(input: &str) -> Result<Grid, &'static str> {
-    let data = {
-        let mut data = input.as_bytes();
-        while !data.is_empty() && data[data.len() - 1].is_ascii_whitespace() {
-            data = &data[0..data.len() - 1];
-        }
-        data
-    };
-
-    if data.is_empty() {
-        Err("grid is empty")
-    } else {
-        let Some(ncols) = data.iter().position(|&c| c == b'\n') else {
-            return Err("invalid grid");
-        };
-        let nrows = (data.len() + 1) / (ncols + 1);
+fn parse(input: &str) -> Result<Grid<&[u8]>, ParseError> {
+    Grid::parse(Parser::new(input), input, |tile| b"./\\-|".contains(&tile))
+}
 
-        Ok(Grid { data, nrows, ncols })
-    }
+fn energize(grid: &Grid<&[u8]>, beam: ((usize, usize), Direction)) -> usize {
+    beam_state(grid, beam)
+        .iter()
+        .map(|&v| usize::from(v != 0))
+        .sum()
 }
 
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-fn energize(Grid { data, nrows, ncols }: &Grid, beam: ((usize, usize), Direction)) -> usize {
-    let (nrows, ncols) = (*nrows, *ncols);
+/// Directions of the beams through every tile, a bit for each.
+fn beam_state(grid: &Grid<&[u8]>, beam: ((usize, usize), Direction)) -> Vec<u8> {
+    let (nrows, ncols) = (grid.nrows(), grid.ncols());
 
-    let state = &mut vec![0_u8; nrows * ncols];
+    let mut state = vec![0_u8; nrows * ncols];
 
     let mut beams = Vec::with_capacity(1_024);
     beams.push(beam);
@@ -96,7 +85,7 @@ fn energize(Grid { data, nrows, ncols }: &Grid, beam: ((usize, usize), Direction
 
             *s |= 1 << u8::from(direction);
 
-            match (data[x + y * (ncols + 1)], direction) {
+            match (grid[(x, y)], direction) {
                 // empty
                 (b'.', direction) => next.push(direction),
 
@@ -124,37 +113,19 @@ fn energize(Grid { data, nrows, ncols }: &Grid, beam: ((usize, usize), Direction
 
             let mut directions = next.drain(..);
             let next_step = if let Some(new_direction) = directions.next() {
-                let (dx, dy) = Delta::from(new_direction);
-                let (new_x, new_y) = (x as isize + dx, y as isize + dy);
-
-                if (0..ncols as isize).contains(&new_x) && (0..nrows as isize).contains(&new_y) {
-                    let next_step = Some(((new_x as usize, new_y as usize), new_direction));
-
-                    if let Some(new_direction) = directions.next() {
-                        let (dx, dy) = Delta::from(new_direction);
-                        let (new_x, new_y) = (x as isize + dx, y as isize + dy);
-
-                        if (0..ncols as isize).contains(&new_x)
-                            && (0..nrows as isize).contains(&new_y)
-                            && state[new_x as usize + new_y as usize * ncols]
-                                & (1 << u8::from(new_direction))
-                                == 0
-                        {
-                            beams.push(((new_x as usize, new_y as usize), new_direction));
+                if let Some(position) = grid.step((x, y), Delta::from(new_direction)) {
+                    if let Some(direction) = directions.next() {
+                        if let Some((x, y)) = grid.step((x, y), Delta::from(direction)) {
+                            if state[x + y * ncols] & (1 << u8::from(direction)) == 0 {
+                                beams.push(((x, y), direction));
+                            }
                         }
                     }
 
-                    next_step
+                    Some((position, new_direction))
                 } else if let Some(new_direction) = directions.next() {
-                    let (dx, dy) = Delta::from(new_direction);
-                    let (new_x, new_y) = (x as isize + dx, y as isize + dy);
-
-                    if (0..ncols as isize).contains(&new_x) && (0..nrows as isize).contains(&new_y)
-                    {
-                        Some(((new_x as usize, new_y as usize), new_direction))
-                    } else {
-                        None
-                    }
+                    grid.step((x, y), Delta::from(new_direction))
+                        .map(|position| (position, new_direction))
                 } else {
                     None
                 }
@@ -170,7 +141,95 @@ fn energize(Grid { data, nrows, ncols }: &Grid, beam: ((usize, usize), Direction
         }
     }
 
-    state.iter().map(|&v| usize::from(v != 0)).sum()
+    state
+}
+
+/// Beams entering from every edge tile.
+fn perimeter(grid: &Grid<&[u8]>) -> impl Iterator<Item = ((usize, usize), Direction)> + Send {
+    let (nrows, ncols) = (grid.nrows(), grid.ncols());
+
+    (0..ncols)
+        .map(|x| ((x, 0), Direction::Down))
+        .chain((0..ncols).map(move |x| ((x, (nrows - 1)), Direction::Up)))
+        .chain((0..nrows).map(|y| ((0, y), Direction::Right)))
+        .chain((0..nrows).map(move |y| ((ncols - 1, y), Direction::Left)))
+}
+
+fn most_energized(grid: &Grid<&[u8]>) -> usize {
+    most_energizing(grid).0
+}
+
+/// Beam from the edges energising the most tiles, with how many.
+fn most_energizing(grid: &Grid<&[u8]>) -> (usize, ((usize, usize), Direction)) {
+    let perimeter = perimeter(grid);
+
+    #[cfg(feature = "rayon")]
+    let perimeter = perimeter.par_bridge();
+
+    perimeter
+        .map(|beam| (energize(grid, beam), beam))
+        .max_by_key(|&(energized, _)| energized)
+        .unwrap()
+}
+
+/// Tiles energised by `beam`, and the tile it enters by.
+fn energized(grid: &Grid<&[u8]>, beam: ((usize, usize), Direction)) -> Overlay {
+    let ncols = grid.ncols();
+    let tiles = beam_state(grid, beam)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &v)| v != 0)
+        .map(|(i, _)| (i % ncols, i / ncols))
+        .collect::<Vec<_>>();
+
+    Overlay::default()
+        .cells(Color::YELLOW, tiles)
+        .cells(Color::RED, [beam.0])
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "The Floor Will Be Lava";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 46),
+        aoc::example!("example1", Part::Two, 51),
+    ];
+
+    type Input<'a> = Grid<&'a [u8]>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(grid: &Self::Input<'_>) -> Self::Output1 {
+        energize(grid, ((0, 0), Direction::Right))
+    }
+
+    fn part_2(grid: &Self::Input<'_>) -> Self::Output2 {
+        most_energized(grid)
+    }
+
+    fn try_part_overlay(
+        part: Part,
+        grid: &Self::Input<'_>,
+        _: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let beam = match part {
+            Part::One => ((0, 0), Direction::Right),
+            Part::Two => most_energizing(grid).1,
+        };
+        let overlay = energized(grid, beam);
+        Ok((overlay.cells[0].positions.len().to_string(), Some(overlay)))
+    }
 }
 
 /// Solve part 1
@@ -178,9 +237,7 @@ fn energize(Grid { data, nrows, ncols }: &Grid, beam: ((usize, usize), Direction
 /// # Panics
 /// Panic if invalid input
 pub fn solve_1(input: &str) -> usize {
-    let grid = parse(input).expect("invalid input");
-
-    energize(&grid, ((0, 0), Direction::Right))
+    Day16::solve_1(input)
 }
 
 /// Solve part 2
@@ -188,20 +245,23 @@ pub fn solve_1(input: &str) -> usize {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> usize {
-    let grid = parse(input).expect("invalid input");
-
-    let (nrows, ncols) = (grid.nrows, grid.ncols);
-
-    let perimeter = (0..ncols)
-        .map(|x| ((x, 0), Direction::Down))
-        .chain((0..ncols).map(|x| ((x, (nrows - 1)), Direction::Up)))
-        .chain((0..nrows).map(|y| ((0, y), Direction::Right)))
-        .chain((0..nrows).map(|y| ((ncols - 1, y), Direction::Left)));
+    Day16::solve_2(input)
+}
 
-    #[cfg(feature = "rayon")]
-    let perimeter = perimeter.par_bridge();
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day16::try_solve_1(input)
+}
 
-    perimeter.map(|beam| energize(&grid, beam)).max().unwrap()
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day16::try_solve_2(input)
 }
 
 pub fn part_1() -> usize {
@@ -214,6 +274,8 @@ pub fn part_2() -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     lazy_static! {
@@ -229,4 +291,24 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 51);
     }
+
+    #[test]
+    fn overlay() {
+        for (part, energized) in [(Part::One, 46), (Part::Two, 51)] {
+            let (timed, overlay) =
+                Day16::try_solve_overlay(part, &EXAMPLE_1, &mut Duration::default).unwrap();
+            assert_eq!(overlay.unwrap().cells[0].positions.len(), energized);
+            assert_eq!(timed.answer, energized.to_string());
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1(".|.\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidTile);
+
+        let error = try_solve_2("").unwrap_err();
+        assert_eq!(error.kind, aoc::ErrorKind::UnexpectedEnd);
+    }
 }
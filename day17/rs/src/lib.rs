@@ -3,233 +3,219 @@
 
 use lazy_static::lazy_static;
 
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-};
+use aoc::graph::astar;
+use aoc::grid::{Grid, NEIGHBORS_4};
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, Overlay, ParseError, Part, Solution};
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
-#[derive(Debug, Eq)]
-struct Node {
+/// Crucible moving on the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Crucible {
     position: (usize, usize),
-    cost: u32,
-    direction_strength: usize,
+    /// Index in [`NEIGHBORS_4`] of the last move.
     direction: usize,
+    /// Blocks moved in `direction`, 0 before the first move.
+    straight: usize,
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Crucible {
+    /// Moves turning or going straight, with their heat loss: after a
+    /// turn the crucible moves `min` blocks, never more than `max`
+    /// blocks in the same direction.
+    fn moves<'a>(
+        &self,
+        map: &'a Map,
+        min: usize,
+        max: usize,
+    ) -> impl Iterator<Item = (Crucible, u32)> + 'a {
+        let crucible = *self;
+        NEIGHBORS_4
+            .iter()
+            .enumerate()
+            .filter_map(move |(direction, &delta)| {
+                let steps = if crucible.straight == 0 {
+                    min
+                } else if direction == crucible.direction {
+                    if crucible.straight == max {
+                        return None;
+                    }
+                    1
+                } else if direction == (crucible.direction + 2) % 4 {
+                    return None;
+                } else {
+                    min
+                };
+
+                let (position, heat_loss) =
+                    (0..steps).try_fold((crucible.position, 0), |(position, heat_loss), _| {
+                        let position = map.tiles.step(position, delta)?;
+                        Some((position, heat_loss + map.get(position)?))
+                    })?;
+                let straight = if crucible.straight > 0 && direction == crucible.direction {
+                    crucible.straight + 1
+                } else {
+                    steps
+                };
+
+                Some((
+                    Crucible {
+                        position,
+                        direction,
+                        straight,
+                    },
+                    heat_loss,
+                ))
+            })
     }
 }
 
-impl Ord for Node {
-    fn cmp(&self, other: &Node) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
+pub struct Map<'a> {
+    tiles: Grid<&'a [u8]>,
 }
 
-impl PartialEq for Node {
-    fn eq(&self, other: &Node) -> bool {
-        self.cost == other.cost
+impl<'a> Map<'a> {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            tiles: Grid::parse(Parser::new(input), input, |tile| {
+                matches!(tile, b'1'..=b'9')
+            })?,
+        })
     }
-}
 
-impl Node {
-    fn new(
-        position: (usize, usize),
-        cost: u32,
-        direction_strength: usize,
-        direction: usize,
-    ) -> Self {
-        Self {
-            position,
-            cost,
-            direction_strength,
-            direction,
-        }
+    fn get(&self, position: (usize, usize)) -> Option<u32> {
+        self.tiles.get(position).map(|tile| u32::from(tile - b'0'))
     }
+}
 
-    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-    fn neighbors<'a, 'b: 'a, 'c: 'a>(
-        &'a self,
-        map: &'c Map<'b>,
-    ) -> impl Iterator<Item = (usize, (usize, usize), u32, usize)> + 'a {
-        [
-            (3, (-1, 0)),
-            (0, (0, -1)),
-            (1, (1, 0)),
-            (2, (0, 1)),
-            (3, (-1, 0)),
-            (0, (0, 1)),
-        ][self.direction..=self.direction + 2]
-            .iter()
-            .filter_map(move |(direction, (dx, dy))| {
-                if *direction == self.direction && self.direction_strength == 3 {
-                    None
-                } else {
-                    let (x, y) = (self.position.0 as isize + dx, self.position.1 as isize + dy);
-                    map.get((x as usize, y as usize))
-                        .map(|cost| (*direction, (x as usize, y as usize), cost, 1))
-                }
-            })
-    }
+/// Least heat loss from the top left to the bottom right block, if the
+/// crucible can reach it.
+fn least_heat_loss(map: &Map, min: usize, max: usize) -> Option<u32> {
+    least_heat_loss_path(map, min, max).map(|(heat_loss, _)| heat_loss)
+}
 
-    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-    fn neighbors_ultracrucible<'a, 'b: 'a, 'c: 'a>(
-        &'a self,
-        map: &'c Map<'b>,
-    ) -> impl Iterator<Item = (usize, (usize, usize), u32, usize)> + 'a {
-        [
-            (3, (-1, 0)),
-            (0, (0, -1)),
-            (1, (1, 0)),
-            (2, (0, 1)),
-            (3, (-1, 0)),
-            (0, (0, 1)),
-        ][self.direction..=self.direction + 2]
-            .iter()
-            .filter_map(move |(direction, (dx, dy))| {
-                if *direction == self.direction {
-                    if self.direction_strength == 10 {
-                        None
-                    } else {
-                        assert!(
-                            self.direction_strength >= 4,
-                            "current value: {}",
-                            self.direction_strength
-                        );
-
-                        let steps = 1;
-
-                        (1..=steps)
-                            .try_fold((0, (0, 0)), |(acc, _), step| {
-                                let (x, y) = (
-                                    self.position.0 as isize + dx * step,
-                                    self.position.1 as isize + dy * step,
-                                );
-                                map.get((x as usize, y as usize))
-                                    .map(|cost| (acc + cost, (x as usize, y as usize)))
-                            })
-                            .map(|(cost, position)| (*direction, position, cost, steps as usize))
-                    }
-                } else {
-                    assert!(
-                        self.direction_strength >= 4,
-                        "current value: {}",
-                        self.direction_strength
-                    );
-
-                    let steps = 4;
-
-                    (1..=steps)
-                        .try_fold((0, (0, 0)), |(acc, _), step| {
-                            let (x, y) = (
-                                self.position.0 as isize + dx * step,
-                                self.position.1 as isize + dy * step,
-                            );
-                            map.get((x as usize, y as usize))
-                                .map(|cost| (acc + cost, (x as usize, y as usize)))
-                        })
-                        .map(|(cost, position)| (*direction, position, cost, steps as usize))
-                }
-            })
-    }
+/// Least heat loss from the top left to the bottom right block, with the
+/// moves of the crucible, if it can reach it.
+#[allow(clippy::cast_possible_truncation)]
+fn least_heat_loss_path(map: &Map, min: usize, max: usize) -> Option<(u32, Vec<Crucible>)> {
+    let end = (map.tiles.ncols() - 1, map.tiles.nrows() - 1);
+    let start = Crucible {
+        position: (0, 0),
+        direction: 0,
+        straight: 0,
+    };
+
+    astar(
+        &|crucible: &Crucible| crucible.moves(map, min, max),
+        start,
+        |crucible| (end.0 - crucible.position.0 + end.1 - crucible.position.1) as u32,
+        |crucible| crucible.position == end,
+    )
 }
 
-struct Map<'a> {
-    data: &'a [u8],
-    ncols: usize,
-    nrows: usize,
+fn unreachable_end(input: &str) -> ParseError {
+    Parser::new(input).error_end(ErrorKind::Invalid(
+        "map, cannot reach the bottom right block",
+    ))
 }
 
-impl<'a> Map<'a> {
-    fn parse(input: &'a str) -> Result<Self, &'static str> {
-        let data = {
-            let mut data = input.as_bytes();
-            while !data.is_empty() && data[data.len() - 1].is_ascii_whitespace() {
-                data = &data[0..data.len() - 1];
-            }
-            data
-        };
-
-        let Some(ncols) = data.iter().position(u8::is_ascii_whitespace) else {
-            return Err("invalid input");
-        };
-
-        let nrows = (data.len() + 1) / (ncols + 1);
-
-        Ok(Self { data, ncols, nrows })
+/// Blocks entered by the crucible moving along `path`, the first one
+/// included: it moves more blocks at once after a turn.
+fn blocks(map: &Map, path: &[Crucible]) -> Vec<(usize, usize)> {
+    let mut blocks = vec![path[0].position];
+    for crucible in &path[1..] {
+        while let Some(&block) = blocks.last().filter(|&&block| block != crucible.position) {
+            let block = map
+                .tiles
+                .step(block, NEIGHBORS_4[crucible.direction])
+                .expect("path out of the map");
+            blocks.push(block);
+        }
     }
 
-    fn get(&self, (x, y): (usize, usize)) -> Option<u32> {
-        if (0..self.ncols).contains(&x) && (0..self.nrows).contains(&y) {
-            Some(u32::from(self.data[x + (self.ncols + 1) * y] - b'0'))
-        } else {
-            None
-        }
+    blocks
+}
+
+/// Minimum and maximum blocks moved in a direction by the crucible of
+/// `part`.
+fn moves(part: Part) -> (usize, usize) {
+    match part {
+        Part::One => (1, 3),
+        Part::Two => (4, 10),
     }
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> u32 {
-    let map = Map::parse(input).expect("invalid input");
+fn crucible(map: &Map) -> Option<u32> {
+    let (min, max) = moves(Part::One);
+    least_heat_loss(map, min, max)
+}
 
-    let mut visited = HashMap::with_capacity(map.ncols * map.nrows);
-    visited.insert((0, 0), 0);
+fn ultra_crucible(map: &Map) -> Option<u32> {
+    let (min, max) = moves(Part::Two);
+    least_heat_loss(map, min, max)
+}
 
-    let mut dist = HashMap::with_capacity(map.ncols * map.nrows);
+pub struct Day17;
 
-    let mut current = BinaryHeap::new();
-    current.push(Node::new((0, 1), map.get((0, 1)).unwrap(), 1, 2));
-    current.push(Node::new((1, 0), map.get((1, 0)).unwrap(), 1, 1));
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Clumsy Crucible";
 
-    while let Some(node) = current.pop() {
-        visited
-            .entry(node.position)
-            .and_modify(|v| *v = node.cost.min(*v))
-            .or_insert(node.cost);
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 102),
+        aoc::example!("example1", Part::Two, 94),
+        aoc::example!("example2", Part::Two, 71),
+    ];
 
-        if visited.contains_key(&(map.nrows - 1, map.ncols - 1)) {
-            break;
-        }
+    type Input<'a> = Map<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
 
-        if let Some(&cost) = dist.get(&(node.direction, node.position, node.direction_strength)) {
-            if cost < node.cost {
-                continue;
-            }
-        }
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Map::parse(input)
+    }
 
-        for (target_direction, target_position, cost, steps) in Node::neighbors(&node, &map) {
-            let target_cost = node.cost + cost;
-            let target_direction_strength = if target_direction == node.direction {
-                node.direction_strength + steps
-            } else {
-                steps
-            };
-
-            let target_key = (target_direction, target_position, target_direction_strength);
-            let current_cost = dist.get(&target_key).unwrap_or(&u32::MAX);
-            if target_cost < *current_cost {
-                let target = Node::new(
-                    target_position,
-                    target_cost,
-                    target_direction_strength,
-                    target_direction,
-                );
-                current.push(target);
-                dist.insert(target_key, target_cost);
-            }
-        }
+    fn part_1(map: &Self::Input<'_>) -> Self::Output1 {
+        crucible(map).expect("cannot reach the bottom right block")
+    }
+
+    fn part_2(map: &Self::Input<'_>) -> Self::Output2 {
+        ultra_crucible(map).expect("cannot reach the bottom right block")
     }
 
-    visited[&(map.ncols - 1, map.nrows - 1)]
+    fn try_part_1(map: &Self::Input<'_>, input: &str) -> Result<Self::Output1, ParseError> {
+        crucible(map).ok_or_else(|| unreachable_end(input))
+    }
+
+    fn try_part_2(map: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        ultra_crucible(map).ok_or_else(|| unreachable_end(input))
+    }
+
+    fn try_part_overlay(
+        part: Part,
+        map: &Self::Input<'_>,
+        input: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let (min, max) = moves(part);
+        let (heat_loss, path) =
+            least_heat_loss_path(map, min, max).ok_or_else(|| unreachable_end(input))?;
+        Ok((
+            heat_loss.to_string(),
+            Some(Overlay::default().path(Color::RED, blocks(map, &path))),
+        ))
+    }
+}
+
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> u32 {
+    Day17::solve_1(input)
 }
 
 /// Solve part 2
@@ -237,63 +223,23 @@ pub fn solve_1(input: &str) -> u32 {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> u32 {
-    let map = Map::parse(input).expect("invalid input");
-
-    let mut visited = HashMap::with_capacity(map.ncols * map.nrows);
-    visited.insert((0, 0), 0);
-
-    let mut dist = HashMap::with_capacity(map.ncols * map.nrows);
-
-    let mut nodes = BinaryHeap::new();
-
-    let zero_four_cost = (1..=4).filter_map(|y| map.get((0, y))).sum();
-    let four_zero_cost = (1..=4).filter_map(|x| map.get((x, 0))).sum();
-
-    nodes.push(Node::new((0, 4), zero_four_cost, 4, 2));
-    nodes.push(Node::new((4, 0), four_zero_cost, 4, 1));
-
-    while let Some(node) = nodes.pop() {
-        visited
-            .entry(node.position)
-            .and_modify(|v| *v = node.cost.min(*v))
-            .or_insert(node.cost);
-
-        if visited.contains_key(&(map.nrows - 1, map.ncols - 1)) {
-            break;
-        }
-
-        if let Some(&cost) = dist.get(&(node.direction, node.position, node.direction_strength)) {
-            if cost < node.cost {
-                continue;
-            }
-        }
+    Day17::solve_2(input)
+}
 
-        for (target_direction, target_position, cost, steps) in node.neighbors_ultracrucible(&map) {
-            let target_cost = node.cost + cost;
-            let target_direction_strength = if target_direction == node.direction {
-                node.direction_strength + steps
-            } else {
-                steps
-            };
-
-            assert!((4..=10).contains(&target_direction_strength));
-
-            let target_key = (target_direction, target_position, target_direction_strength);
-            let current_cost = dist.get(&target_key).unwrap_or(&u32::MAX);
-            if target_cost < *current_cost {
-                let target = Node::new(
-                    target_position,
-                    target_cost,
-                    target_direction_strength,
-                    target_direction,
-                );
-                nodes.push(target);
-                dist.insert(target_key, target_cost);
-            }
-        }
-    }
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u32, ParseError> {
+    Day17::try_solve_1(input)
+}
 
-    visited[&(map.ncols - 1, map.nrows - 1)]
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u32, ParseError> {
+    Day17::try_solve_2(input)
 }
 
 pub fn part_1() -> u32 {
@@ -327,4 +273,49 @@ mod tests {
     fn same_results_2_2() {
         assert_eq!(solve_2(&EXAMPLE_2), 71);
     }
+
+    #[test]
+    fn overlay() {
+        for (part, example, heat_loss) in [
+            (Part::One, *EXAMPLE_1, 102),
+            (Part::Two, *EXAMPLE_1, 94),
+            (Part::Two, *EXAMPLE_2, 71),
+        ] {
+            let map = Map::parse(example).unwrap();
+            let (answer, overlay) = Day17::try_part_overlay(part, &map, example).unwrap();
+            assert_eq!(answer, heat_loss.to_string());
+            let blocks = &overlay.unwrap().paths[0].positions;
+            assert_eq!(blocks[0], (0, 0));
+            assert_eq!(
+                blocks[1..]
+                    .iter()
+                    .map(|&block| map.get(block).unwrap())
+                    .sum::<u32>(),
+                heat_loss
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("241\n3.5\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "."));
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidTile);
+
+        let error = try_solve_2("241\n35\n").unwrap_err();
+        assert_eq!(error.kind, aoc::ErrorKind::InvalidRowLength);
+    }
+
+    #[test]
+    fn unreachable_end() {
+        // an ultra crucible moves 4 blocks after a turn
+        assert_eq!(try_solve_1("12\n34\n"), Ok(6));
+        let error = try_solve_2("12\n34").unwrap_err();
+        assert_eq!(
+            error.kind,
+            aoc::ErrorKind::Invalid("map, cannot reach the bottom right block")
+        );
+        let map = Map::parse("12\n34").unwrap();
+        assert!(Day17::try_part_overlay(Part::Two, &map, "12\n34").is_err());
+    }
 }
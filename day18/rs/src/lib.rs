@@ -3,30 +3,33 @@
 
 use lazy_static::lazy_static;
 
-use std::{ops::Mul, str::FromStr};
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
+use std::io::BufRead;
+use std::ops::Mul;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Direction {
+pub enum Direction {
     Up,
     Right,
     Down,
     Left,
 }
 
-impl FromStr for Direction {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
+impl Direction {
+    fn parse(parser: Parser, direction: &str) -> Result<Self, ParseError> {
+        match direction {
             "U" => Ok(Direction::Up),
             "R" => Ok(Direction::Right),
             "D" => Ok(Direction::Down),
             "L" => Ok(Direction::Left),
-            _ => Err("invalid direction"),
+            _ => Err(parser.error(direction, ErrorKind::Expected("U, R, D or L"))),
         }
     }
 }
@@ -46,76 +49,78 @@ impl Mul<i64> for Direction {
     }
 }
 
-/// Parse mode 1
-///
-/// # Panics
-/// Panic if invalid input
-fn parse_1(line: &str) -> (Direction, i64) {
+/// Dig instruction: direction and length.
+pub type Step = (Direction, i64);
+
+/// Parse a line as the instruction of part 1 and the one hidden in
+/// the color for part 2.
+fn parse_line(parser: Parser, line: &str) -> Result<(Step, Step), ParseError> {
     let mut parts = line.split_whitespace();
+    let (Some(direction), Some(length), Some(color), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(parser.error(line, ErrorKind::Expected("direction, length and color")));
+    };
 
-    let direction = parts
-        .next()
-        .expect("cannot find direction")
-        .parse::<Direction>()
-        .expect("invalid direction");
-    let length = parts
-        .next()
-        .expect("cannot find length")
-        .parse::<i64>()
-        .expect("cannot parse length");
+    let step_1 = (Direction::parse(parser, direction)?, parser.number(length)?);
 
-    (direction, length)
-}
+    let invalid_color = || parser.error(color, ErrorKind::Expected("color (#rrggbb)"));
+    let hex = color
+        .strip_prefix("(#")
+        .and_then(|hex| hex.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6)
+        .ok_or_else(invalid_color)?;
 
-/// Parse mode 2
-///
-/// # Panics
-/// Panic if invalid input
-fn parse_2(line: &str) -> (Direction, i64) {
-    let part = line
-        .as_bytes()
-        .split(|&c| c == b' ')
-        .nth(2)
-        .expect("invalid input");
-
-    let length = i64::from_str_radix(
-        std::str::from_utf8(part.get(2..2 + 5).expect("invalid length part"))
-            .expect("invalid length part"),
-        16,
-    )
-    .expect("invalid length");
-    let direction = match part.get(2 + 5) {
-        Some(b'0') => Direction::Right,
-        Some(b'1') => Direction::Down,
-        Some(b'2') => Direction::Left,
-        Some(b'3') => Direction::Up,
-        invalid => panic!("invalid direction: {invalid:?}"),
+    let length = i64::from_str_radix(&hex[..5], 16).map_err(|_| invalid_color())?;
+    let direction = match &hex[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        _ => return Err(invalid_color()),
     };
 
-    (direction, length)
+    Ok((step_1, (direction, length)))
 }
 
-/// Generic solve
-///
-/// # Panics
-/// Panic if invalid input
-fn solve<F: FnMut(&str) -> (Direction, i64)>(input: &str, parse: F) -> i64 {
-    let (_, area, perimeter) = input.lines().map(parse).fold(
-        ((0, 0), 0, 0),
-        |(position, area, perimeter), (direction, length)| {
-            let (dx, dy) = direction * length;
+fn parse(input: &str) -> Result<Vec<(Step, Step)>, ParseError> {
+    let parser = Parser::new(input);
 
-            let next = (position.0 + dx, position.1 + dy);
+    input.lines().map(|line| parse_line(parser, line)).collect()
+}
 
-            (
-                next,
-                area + position.0 * next.1 - next.0 * position.1,
-                perimeter + (position.0 - next.0).abs() + (position.1 - next.1).abs(),
-            )
-        },
-    );
+/// Lagoon being dug: position, twice the signed area and perimeter
+/// of the trench.
+#[derive(Debug, Default, Clone, Copy)]
+struct Lagoon {
+    position: (i64, i64),
+    area: i64,
+    perimeter: i64,
+}
 
-    (area + perimeter) / 2 + 1
+impl Lagoon {
+    fn dig(self, (direction, length): Step) -> Self {
+        let (dx, dy) = direction * length;
+
+        let position = self.position;
+        let next = (position.0 + dx, position.1 + dy);
+
+        Self {
+            position: next,
+            area: self.area + position.0 * next.1 - next.0 * position.1,
+            perimeter: self.perimeter + (position.0 - next.0).abs() + (position.1 - next.1).abs(),
+        }
+    }
+
+    /// Area of the trench and of the lagoon inside, by Pick's theorem.
+    fn area(self) -> i64 {
+        (self.area + self.perimeter) / 2 + 1
+    }
+}
+
+/// Lagoon area dug by `steps`.
+fn area(steps: impl Iterator<Item = Step>) -> i64 {
+    steps.fold(Lagoon::default(), Lagoon::dig).area()
 }
 
 /// Solve part 1
@@ -123,7 +128,7 @@ fn solve<F: FnMut(&str) -> (Direction, i64)>(input: &str, parse: F) -> i64 {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_1(input: &str) -> i64 {
-    solve(input, parse_1)
+    Day18::solve_1(input)
 }
 
 /// Solve part 2
@@ -131,7 +136,76 @@ pub fn solve_1(input: &str) -> i64 {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> i64 {
-    solve(input, parse_2)
+    Day18::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<i64, ParseError> {
+    Day18::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<i64, ParseError> {
+    Day18::try_solve_2(input)
+}
+
+fn solve_reader<R: BufRead>(reader: R, step: fn((Step, Step)) -> Step) -> Result<i64, ReadError> {
+    let mut lagoon = Lagoon::default();
+    input::for_each_line(reader, |line| {
+        lagoon = lagoon.dig(step(parse_line(Parser::new(line), line)?));
+        Ok(())
+    })?;
+    Ok(lagoon.area())
+}
+
+/// Solve part 1 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<i64, ReadError> {
+    solve_reader(reader, |(step, _)| step)
+}
+
+/// Solve part 2 reading the input a line at a time
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<i64, ReadError> {
+    solve_reader(reader, |(_, step)| step)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Lavaduct Lagoon";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 62),
+        aoc::example!("example1", Part::Two, 952408144115),
+    ];
+
+    type Input<'a> = Vec<(Step, Step)>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(steps: &Self::Input<'_>) -> Self::Output1 {
+        area(steps.iter().map(|&(step, _)| step))
+    }
+
+    fn part_2(steps: &Self::Input<'_>) -> Self::Output2 {
+        area(steps.iter().map(|&(_, step)| step))
+    }
 }
 
 pub fn part_1() -> i64 {
@@ -159,4 +233,33 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 952408144115);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("R 6 (#70c710)\nX 5 (#0dc571)").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, "X"));
+        assert_eq!(error.kind, ErrorKind::Expected("U, R, D or L"));
+
+        let error = try_solve_2("R 6 (#70c714)").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.kind, ErrorKind::Expected("color (#rrggbb)"));
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_2_reader("R 6 (#70c710)\nR 6 (#70c714)".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 5));
+    }
 }
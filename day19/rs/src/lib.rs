@@ -3,18 +3,20 @@
 
 use lazy_static::lazy_static;
 
-use std::{
-    collections::{HashMap, HashSet},
-    ops,
-    str::FromStr,
-};
+use aoc::interval::{Cuboid, Interval, IntervalSet};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Solution};
+
+use std::{collections::HashMap, ops, str::FromStr};
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
+/// Category of the ratings of a part.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum PartCategory {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartCategory {
     X,
     M,
     A,
@@ -35,22 +37,10 @@ impl FromStr for PartCategory {
     }
 }
 
-impl TryFrom<char> for PartCategory {
-    type Error = &'static str;
-
-    fn try_from(input: char) -> Result<Self, Self::Error> {
-        match input {
-            'x' => Ok(Self::X),
-            'm' => Ok(Self::M),
-            'a' => Ok(Self::A),
-            's' => Ok(Self::S),
-            _ => Err("invalid part"),
-        }
-    }
-}
-
+/// Ratings of a part, or their ranges.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-struct Part<T>([T; 4]);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part<T>(pub [T; 4]);
 
 impl<T> Part<T> {
     fn new(x: T, m: T, a: T, s: T) -> Self {
@@ -64,113 +54,9 @@ impl Part<u64> {
     }
 }
 
-impl Part<ops::RangeInclusive<u64>> {
-    fn value(&self) -> u64 {
-        self.0.iter().map(|r| r.end() - r.start() + 1).product()
-    }
-
-    fn is_empty(&self) -> bool {
-        self.0.iter().any(ops::RangeInclusive::is_empty)
-    }
-
-    fn intersect(&self, other: &Self) -> bool {
-        [
-            PartCategory::X,
-            PartCategory::M,
-            PartCategory::A,
-            PartCategory::S,
-        ]
-        .iter()
-        .all(|&category| {
-            let a = &self[category];
-            let b = &other[category];
-
-            a.contains(b.start())
-                || a.contains(b.end())
-                || b.contains(a.start())
-                || b.contains(a.end())
-        })
-    }
-
-    #[allow(clippy::range_minus_one)]
-    fn fragments(&self, other: &Self) -> HashSet<Part<ops::RangeInclusive<u64>>> {
-        let mut cuboids = HashSet::new();
-        cuboids.insert(self.clone());
-
-        for category in [
-            PartCategory::X,
-            PartCategory::M,
-            PartCategory::A,
-            PartCategory::S,
-        ] {
-            let mut new_cuboids = HashSet::new();
-
-            let start = *other[category].start();
-            let end = *other[category].end();
-
-            for cuboid in cuboids.drain() {
-                let range = &cuboid[category];
-
-                if range.contains(&start) {
-                    let r = *range.start()..=start - 1;
-                    if !r.is_empty() {
-                        let mut cuboid = cuboid.clone();
-                        cuboid[category] = r;
-                        new_cuboids.insert(cuboid);
-                    }
-
-                    if range.contains(&end) {
-                        let r = end + 1..=*range.end();
-                        if !r.is_empty() {
-                            let mut cuboid = cuboid.clone();
-                            cuboid[category] = r;
-                            new_cuboids.insert(cuboid);
-                        }
-
-                        let r = start..=end;
-                        if !r.is_empty() {
-                            let mut cuboid = cuboid.clone();
-                            cuboid[category] = r;
-                            new_cuboids.insert(cuboid);
-                        }
-                    } else {
-                        let r = start..=*range.end();
-                        if !r.is_empty() {
-                            let mut cuboid = cuboid.clone();
-                            cuboid[category] = r;
-                            new_cuboids.insert(cuboid);
-                        }
-                    }
-                } else if range.contains(&end) {
-                    let r = end + 1..=*range.end();
-                    if !r.is_empty() {
-                        let mut cuboid = cuboid.clone();
-                        cuboid[category] = r;
-                        new_cuboids.insert(cuboid);
-                    }
-
-                    let r = *range.start()..=end;
-                    if !r.is_empty() {
-                        let mut cuboid = cuboid.clone();
-                        cuboid[category] = r;
-                        new_cuboids.insert(cuboid);
-                    }
-                } else {
-                    new_cuboids.insert(cuboid);
-                }
-            }
-
-            cuboids = new_cuboids;
-        }
-
-        cuboids
-    }
-
-    fn difference(&self, other: &Self) -> HashSet<Part<ops::RangeInclusive<u64>>> {
-        self.fragments(other)
-            .difference(&other.fragments(self))
-            .cloned()
-            .collect::<HashSet<_>>()
+impl Part<Interval<u64>> {
+    fn cuboid(&self) -> Cuboid<u64, 4> {
+        Cuboid::new(self.0)
     }
 }
 
@@ -198,42 +84,76 @@ impl<T> ops::IndexMut<PartCategory> for Part<T> {
     }
 }
 
-impl FromStr for Part<u64> {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (_, part) = input.split_once('{').ok_or("invalid part, cannot find {")?;
-        let (part, _) = part.split_once('}').ok_or("invalid part, cannot find }")?;
+impl Part<u64> {
+    fn parse(parser: Parser, input: &str) -> Result<Self, ParseError> {
+        let part = parser.strip_prefix(input, "{")?;
+        let part = part
+            .strip_suffix('}')
+            .ok_or_else(|| parser.error(input, ErrorKind::Expected("}")))?;
 
         let mut values = [0; 4];
-        for (i, part) in part.split(',').enumerate() {
-            let (_, value) = part.split_once('=').ok_or("invalid part part")?;
-            values[i] = value.parse().map_err(|_| "invalid value")?;
+        let mut ratings = part.split(',');
+        for value in &mut values {
+            let rating = ratings
+                .next()
+                .ok_or_else(|| parser.error(input, ErrorKind::Expected("4 ratings")))?;
+            let (_, rating) = parser.split_once(rating, "=")?;
+            *value = parser.number(rating)?;
+        }
+        if let Some(rating) = ratings.next() {
+            return Err(parser.error(rating, ErrorKind::Expected("4 ratings")));
         }
 
         Ok(Self(values))
     }
 }
 
-struct Workflows<'a>(HashMap<&'a str, Vec<Rule<'a>>>);
+/// Rules of the workflows, by workflow name.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Workflows<'a>(
+    #[cfg_attr(feature = "serde", serde(borrow))] pub HashMap<&'a str, Vec<Rule<'a>>>,
+);
 
 impl<'a> Workflows<'a> {
-    fn parse(input: &'a str) -> Result<Self, &'static str> {
-        Ok(Workflows(
-            input
-                .lines()
-                .map(|line| {
-                    let (workflow_id, remainder) =
-                        line.split_once('{').ok_or("cannot find workflow id")?;
-                    let (rules, _) = remainder.split_once('}').ok_or("cannot find rules")?;
-                    let rules = rules
-                        .split(',')
-                        .map(Rule::parse)
-                        .collect::<Result<Vec<_>, _>>()?;
-                    Ok((workflow_id, rules))
-                })
-                .collect::<Result<HashMap<&str, Vec<Rule>>, _>>()?,
-        ))
+    fn parse(parser: Parser, input: &'a str) -> Result<Self, ParseError> {
+        let mut targets = vec![];
+        let workflows = input
+            .lines()
+            .map(|line| {
+                let (workflow_id, remainder) = parser.split_once(line, "{")?;
+                let rules = remainder
+                    .strip_suffix('}')
+                    .ok_or_else(|| parser.error(remainder, ErrorKind::Expected("}")))?
+                    .split(',')
+                    .map(|rule| Ok((rule, Rule::parse(parser, rule)?)))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if let Some(&(rule, Rule::PartLessThan(..) | Rule::PartGreaterThan(..))) =
+                    rules.last()
+                {
+                    return Err(
+                        parser.error(rule, ErrorKind::Expected("a final rule without condition"))
+                    );
+                }
+                let rules = rules.into_iter().map(|(_, rule)| rule).collect::<Vec<_>>();
+
+                targets.extend(rules.iter().filter_map(Rule::target));
+
+                Ok((workflow_id, rules))
+            })
+            .collect::<Result<HashMap<&str, Vec<Rule>>, _>>()?;
+
+        if !workflows.contains_key("in") {
+            return Err(parser.error(&input[..0], ErrorKind::Missing("workflow in")));
+        }
+        if let Some(target) = targets
+            .into_iter()
+            .find(|target| !workflows.contains_key(target))
+        {
+            return Err(parser.error(target, ErrorKind::Undefined("workflow")));
+        }
+
+        Ok(Workflows(workflows))
     }
 
     #[allow(clippy::never_loop)]
@@ -260,31 +180,8 @@ impl<'a> Workflows<'a> {
         Err("cannot find workflow")
     }
 
-    fn add_cuboid(
-        cuboids: &mut HashSet<Part<ops::RangeInclusive<u64>>>,
-        cuboid: Part<ops::RangeInclusive<u64>>,
-    ) {
-        let mut r = HashSet::new();
-
-        {
-            let drain = cuboids.drain();
-            for current in drain {
-                if current.intersect(&cuboid) {
-                    for cuboid in current.difference(&cuboid) {
-                        r.insert(cuboid);
-                    }
-                } else {
-                    r.insert(current);
-                }
-            }
-            r.insert(cuboid);
-        }
-
-        *cuboids = r;
-    }
-
-    fn combinations(&self, part: Part<ops::RangeInclusive<u64>>) -> u64 {
-        let mut accepted = HashSet::new();
+    fn combinations(&self, part: Part<Interval<u64>>) -> u64 {
+        let mut accepted = IntervalSet::new();
 
         let mut queue = vec![("in", part)];
         while let Some((workflow, mut part)) = queue.pop() {
@@ -292,7 +189,7 @@ impl<'a> Workflows<'a> {
                 for rule in rules {
                     match rule.apply_range(&part) {
                         RuleApplyResult::Accept => {
-                            Self::add_cuboid(&mut accepted, part);
+                            accepted.insert(part.cuboid());
                             break;
                         }
                         RuleApplyResult::Reject => {
@@ -306,19 +203,19 @@ impl<'a> Workflows<'a> {
                         RuleApplyResult::Split(action, ok_part, ko_part) => {
                             match action {
                                 Action::Accept => {
-                                    Self::add_cuboid(&mut accepted, ok_part);
+                                    accepted.insert(ok_part.cuboid());
                                 }
                                 Action::Reject => {
                                     // Self::add_cuboid(&mut rejected, ok_part);
                                 }
                                 Action::JumpToWorkflow(workflow) => {
-                                    if !ok_part.is_empty() {
+                                    if !ok_part.cuboid().is_empty() {
                                         queue.push((workflow, ok_part));
                                     }
                                 }
                             }
 
-                            if ko_part.is_empty() {
+                            if ko_part.cuboid().is_empty() {
                                 break;
                             }
 
@@ -329,54 +226,69 @@ impl<'a> Workflows<'a> {
             }
         }
 
-        accepted
-            .iter()
-            .map(Part::<ops::RangeInclusive<u64>>::value)
-            .sum::<u64>()
+        accepted.volume()
     }
 }
 
+/// What a rule does with the parts it applies to.
 #[derive(Debug, Copy, Clone)]
-enum Action<'a> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action<'a> {
     Accept,
     Reject,
     JumpToWorkflow(&'a str),
 }
 
-enum Rule<'a> {
-    Immediate(Action<'a>),
-    PartLessThan(PartCategory, u64, Action<'a>),
-    PartGreaterThan(PartCategory, u64, Action<'a>),
+/// Rule of a workflow, applying its action to every part or to the
+/// parts with a rating less or greater than a value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rule<'a> {
+    Immediate(#[cfg_attr(feature = "serde", serde(borrow))] Action<'a>),
+    PartLessThan(
+        PartCategory,
+        u64,
+        #[cfg_attr(feature = "serde", serde(borrow))] Action<'a>,
+    ),
+    PartGreaterThan(
+        PartCategory,
+        u64,
+        #[cfg_attr(feature = "serde", serde(borrow))] Action<'a>,
+    ),
 }
 
 impl<'a> Rule<'a> {
-    fn parse(input: &'a str) -> Result<Self, &'static str> {
-        match input {
-            "A" => Ok(Rule::Immediate(Action::Accept)),
-            "R" => Ok(Rule::Immediate(Action::Reject)),
-            _ => {
-                if let Some((pre, action)) = input.split_once(':') {
-                    let mut chars = pre.chars();
-                    let part_category = chars
-                        .next()
-                        .ok_or("cannot find part category")?
-                        .try_into()?;
-                    let op = chars.next().ok_or("cannot find operation")?;
-                    let value = chars.as_str().parse().map_err(|_| "invalid value")?;
-                    let action = match action {
-                        "A" => Action::Accept,
-                        "R" => Action::Reject,
-                        workflow => Action::JumpToWorkflow(workflow),
-                    };
-                    match op {
-                        '>' => Ok(Rule::PartGreaterThan(part_category, value, action)),
-                        '<' => Ok(Rule::PartLessThan(part_category, value, action)),
-                        _ => Err("invalid operation"),
-                    }
-                } else {
-                    Ok(Rule::Immediate(Action::JumpToWorkflow(input)))
-                }
-            }
+    fn parse(parser: Parser, input: &'a str) -> Result<Self, ParseError> {
+        let action = |action| match action {
+            "A" => Action::Accept,
+            "R" => Action::Reject,
+            workflow => Action::JumpToWorkflow(workflow),
+        };
+
+        let Some((condition, target)) = input.split_once(':') else {
+            return Ok(Rule::Immediate(action(input)));
+        };
+
+        let invalid = || parser.error(condition, ErrorKind::Expected("condition"));
+        let part_category = condition
+            .get(..1)
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+        let value = parser.number(condition.get(2..).ok_or_else(invalid)?)?;
+        match condition.get(1..2) {
+            Some(">") => Ok(Rule::PartGreaterThan(part_category, value, action(target))),
+            Some("<") => Ok(Rule::PartLessThan(part_category, value, action(target))),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Workflow the rule can jump to.
+    fn target(&self) -> Option<&'a str> {
+        match self {
+            Self::Immediate(Action::JumpToWorkflow(workflow))
+            | Self::PartLessThan(_, _, Action::JumpToWorkflow(workflow))
+            | Self::PartGreaterThan(_, _, Action::JumpToWorkflow(workflow)) => Some(workflow),
+            _ => None,
         }
     }
 
@@ -400,8 +312,7 @@ impl<'a> Rule<'a> {
         }
     }
 
-    #[allow(clippy::range_minus_one)]
-    fn apply_range(&self, part: &Part<ops::RangeInclusive<u64>>) -> RuleApplyResult {
+    fn apply_range(&self, part: &Part<Interval<u64>>) -> RuleApplyResult {
         match self {
             Self::Immediate(Action::Accept) => RuleApplyResult::Accept,
             Self::Immediate(Action::Reject) => RuleApplyResult::Reject,
@@ -409,29 +320,23 @@ impl<'a> Rule<'a> {
                 RuleApplyResult::JumpToWorkflow(workflow)
             }
             Self::PartLessThan(part_category, value, action) => {
-                let range = &part[*part_category];
+                let (range_ok, range_ko) = part[*part_category].split_at(*value);
 
-                let range_ok = *range.start()..=*value - 1;
-                let range_ko = *value..=*range.end();
-
-                let mut part_ok = part.clone();
+                let mut part_ok = *part;
                 part_ok[*part_category] = range_ok;
 
-                let mut part_ko = part.clone();
+                let mut part_ko = *part;
                 part_ko[*part_category] = range_ko;
 
                 RuleApplyResult::Split(*action, part_ok, part_ko)
             }
             Self::PartGreaterThan(part_category, value, action) => {
-                let range = &part[*part_category];
-
-                let range_ko = *range.start()..=*value;
-                let range_ok = *value + 1..=*range.end();
+                let (range_ko, range_ok) = part[*part_category].split_at(*value + 1);
 
-                let mut part_ok = part.clone();
+                let mut part_ok = *part;
                 part_ok[*part_category] = range_ok;
 
-                let mut part_ko = part.clone();
+                let mut part_ko = *part;
                 part_ko[*part_category] = range_ko;
 
                 RuleApplyResult::Split(*action, part_ok, part_ko)
@@ -449,44 +354,94 @@ enum RuleApplyResult<'a> {
     Accept,
     Reject,
     JumpToWorkflow(&'a str),
-    Split(
-        Action<'a>,
-        Part<ops::RangeInclusive<u64>>,
-        Part<ops::RangeInclusive<u64>>,
-    ),
+    Split(Action<'a>, Part<Interval<u64>>, Part<Interval<u64>>),
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> u64 {
-    let (rules, parts) = input.split_once("\n\n").expect("invalid input");
+fn parse(input: &str) -> Result<(Workflows<'_>, Vec<Part<u64>>), ParseError> {
+    let parser = Parser::new(input);
 
-    let workflows = Workflows::parse(rules).expect("invalid worfklow");
+    let (workflows, parts) = input
+        .split_once("\n\n")
+        .ok_or_else(|| parser.error_end(ErrorKind::Missing("parts")))?;
 
-    parts
+    let workflows = Workflows::parse(parser, workflows)?;
+    let parts = parts
         .lines()
-        .map(|line| {
-            let part = line.parse().expect("invalid part");
-            match workflows.check(&part).expect("invalid rule") {
-                WorkflowsApplyResult::Accept => part.value(),
-                WorkflowsApplyResult::Reject => 0,
-            }
+        .map(|line| Part::parse(parser, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok((workflows, parts))
+}
+
+fn accepted_ratings(workflows: &Workflows, parts: &[Part<u64>]) -> u64 {
+    parts
+        .iter()
+        .map(|part| match workflows.check(part).expect("invalid rule") {
+            WorkflowsApplyResult::Accept => part.value(),
+            WorkflowsApplyResult::Reject => 0,
         })
         .sum()
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Aplenty";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", aoc::Part::One, 19114),
+        aoc::example!("example1", aoc::Part::Two, 167409079868000),
+    ];
+
+    type Input<'a> = (Workflows<'a>, Vec<Part<u64>>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1((workflows, parts): &Self::Input<'_>) -> Self::Output1 {
+        accepted_ratings(workflows, parts)
+    }
+
+    fn part_2((workflows, _): &Self::Input<'_>) -> Self::Output2 {
+        let ratings = Interval::new(1, 4001);
+        workflows.combinations(Part::new(ratings, ratings, ratings, ratings))
+    }
+}
+
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> u64 {
+    Day19::solve_1(input)
+}
+
 /// Solve part 2
 ///
 /// # Panics
 /// Panic if invalid input
 pub fn solve_2(input: &str) -> u64 {
-    let (rules, _) = input.split_once("\n\n").expect("invalid input");
+    Day19::solve_2(input)
+}
 
-    let workflows = Workflows::parse(rules).expect("invalid rules");
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day19::try_solve_1(input)
+}
 
-    workflows.combinations(Part::new(1..=4000, 1..=4000, 1..=4000, 1..=4000))
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day19::try_solve_2(input)
 }
 
 pub fn part_1() -> u64 {
@@ -515,11 +470,34 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 167409079868000);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("in{x>10:A,R}\n\n{x=1,m=2,a=3,s=b}").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 16, "b")
+        );
+        assert_eq!(error.kind, ErrorKind::InvalidNumber);
+
+        let error = try_solve_2("in{x>10:A,px}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 11, "px")
+        );
+        assert_eq!(error.kind, ErrorKind::Undefined("workflow"));
+
+        let error = try_solve_2("in{x>10:A,m<5:R}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Expected("a final rule without condition")
+        );
+    }
+
     #[test]
     fn cuboid_difference() {
-        let cuboid1 = Part::new(1..=3, 1..=3, 1..=3, 1..=3);
-        let cuboid2 = Part::new(2..=2, 2..=2, 2..=2, 2..=2);
+        let cuboid1 = IntervalSet::from(Part([Interval::new(1, 4); 4]).cuboid());
+        let cuboid2 = IntervalSet::from(Part([Interval::new(2, 3); 4]).cuboid());
 
-        assert_eq!(cuboid1.difference(&cuboid2).len(), 3 * 3 * 3 * 3 - 1);
+        assert_eq!(cuboid1.difference(&cuboid2).volume(), 3 * 3 * 3 * 3 - 1);
     }
 }
@@ -5,28 +5,37 @@ use std::collections::{HashMap, VecDeque};
 
 use lazy_static::lazy_static;
 
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
 use num::integer::lcm;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[derive(Debug, Clone)]
-enum Module {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Module {
     Broadcast,
     Conjunction(Vec<bool>),
     FlipFlop(bool),
 }
 
+/// Modules by name, with their state, inputs and outputs.
+pub type Modules<'a> = HashMap<&'a str, (Module, Vec<&'a str>, Vec<&'a str>)>;
+
 /// Parse input
 ///
-/// # Panics
-/// Panic if invalid input
-fn parse(input: &str) -> HashMap<&str, (Module, Vec<&str>, Vec<&str>)> {
+/// # Errors
+/// Return the first invalid module definition
+fn parse(input: &str) -> Result<Modules<'_>, ParseError> {
+    let parser = Parser::new(input);
+
     let mut modules = input
         .lines()
         .map(|line| {
-            let (module, outputs) = line.split_once(" -> ").expect("invalid module definition");
+            let (module, outputs) = parser.split_once(line, " -> ")?;
 
             let outputs = outputs.split(", ").collect::<Vec<_>>();
 
@@ -35,12 +44,15 @@ fn parse(input: &str) -> HashMap<&str, (Module, Vec<&str>, Vec<&str>)> {
                 Some('%') => ('%', module_chars.as_str()),
                 Some('&') => ('&', module_chars.as_str()),
                 Some(_) => (' ', module),
-                None => panic!("invalid module"),
+                None => return Err(parser.error(line, ErrorKind::Missing("module name"))),
             };
+            if module.is_empty() {
+                return Err(parser.error(line, ErrorKind::Missing("module name")));
+            }
 
-            (module, (module_type, vec![], outputs))
+            Ok((module, (module_type, vec![], outputs)))
         })
-        .collect::<HashMap<_, _>>();
+        .collect::<Result<HashMap<_, _>, _>>()?;
 
     let mo = modules
         .iter()
@@ -54,7 +66,7 @@ fn parse(input: &str) -> HashMap<&str, (Module, Vec<&str>, Vec<&str>)> {
         }
     }
 
-    modules
+    Ok(modules
         .drain()
         .map(|(module, (module_type, inputs, outputs))| {
             let module_state = match module_type {
@@ -66,18 +78,26 @@ fn parse(input: &str) -> HashMap<&str, (Module, Vec<&str>, Vec<&str>)> {
 
             (module, (module_state, inputs, outputs))
         })
-        .collect::<HashMap<_, _>>()
+        .collect())
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> u64 {
-    let mut modules = parse(input);
+/// Module sending its pulses to `rx`.
+fn rx_in_module<'a>(modules: &Modules<'a>) -> Option<&'a str> {
+    modules.iter().find_map(|(&module, (_, _, outputs))| {
+        if outputs.contains(&"rx") {
+            Some(module)
+        } else {
+            None
+        }
+    })
+}
+
+fn pulses(modules: &Modules) -> u64 {
+    let mut modules = modules.clone();
 
     let mut high = 0;
     let mut low = 0;
+    let _span = aoc::span!("button_presses", presses = 1000);
     for _ in 0..1000 {
         let mut pulses = VecDeque::new();
 
@@ -117,27 +137,19 @@ pub fn solve_1(input: &str) -> u64 {
             }
         }
     }
+    aoc::event!(high, low, "pulses sent");
 
     high * low
 }
 
-/// Solve part 2
+/// Button presses to send a low pulse to `rx`.
 ///
 /// # Panics
-/// Panic if invalid input
-pub fn solve_2(input: &str) -> u64 {
-    let mut modules = parse(input);
+/// Panic if no module sends pulses to `rx`
+fn rx_low_pulse(modules: &Modules) -> u64 {
+    let mut modules = modules.clone();
 
-    let rx_in_module = modules
-        .iter()
-        .find_map(|(&module, (_, _, outputs))| {
-            if outputs.contains(&"rx") {
-                Some(module)
-            } else {
-                None
-            }
-        })
-        .expect("cannot find rx in module");
+    let rx_in_module = rx_in_module(&modules).expect("cannot find rx in module");
 
     let rx_modules = modules
         .iter()
@@ -152,6 +164,7 @@ pub fn solve_2(input: &str) -> u64 {
 
     let mut rx_modules_data = HashMap::with_capacity(4);
 
+    let _span = aoc::span!("button_presses", cycles = rx_modules.len());
     for i in 1.. {
         let mut pulses = VecDeque::new();
 
@@ -159,6 +172,7 @@ pub fn solve_2(input: &str) -> u64 {
 
         while let Some((from, level, module)) = pulses.pop_front() {
             if !level && rx_modules.contains(&module) {
+                aoc::event!(module, presses = i, "cycle found");
                 rx_modules_data.insert(module, i);
                 if rx_modules_data.len() == rx_modules.len() {
                     return rx_modules_data
@@ -198,6 +212,74 @@ pub fn solve_2(input: &str) -> u64 {
     unreachable!()
 }
 
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> u64 {
+    Day20::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_2(input: &str) -> u64 {
+    Day20::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day20::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day20::try_solve_2(input)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Pulse Propagation";
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 32000000),
+        aoc::example!("example2", Part::One, 11687500),
+    ];
+
+    type Input<'a> = Modules<'a>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(modules: &Self::Input<'_>) -> Self::Output1 {
+        pulses(modules)
+    }
+
+    fn part_2(modules: &Self::Input<'_>) -> Self::Output2 {
+        rx_low_pulse(modules)
+    }
+
+    fn try_part_2(modules: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        if rx_in_module(modules).is_none() {
+            return Err(Parser::new(input).error_end(ErrorKind::Missing("module with output rx")));
+        }
+
+        Ok(rx_low_pulse(modules))
+    }
+}
+
 pub fn part_1() -> u64 {
     solve_1(&INPUT)
 }
@@ -229,4 +311,17 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&INPUT), 217317393039529);
     }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("broadcaster -> a\n%a => b").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 1, "%a => b")
+        );
+        assert_eq!(error.kind, ErrorKind::Expected(" -> "));
+
+        let error = try_solve_2(&EXAMPLE_1).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Missing("module with output rx"));
+    }
 }
@@ -1,87 +1,79 @@
 #![deny(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 
-use std::collections::VecDeque;
-
 use lazy_static::lazy_static;
 
+use aoc::graph::bfs_distances;
+use aoc::grid::Grid;
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Overlay, ParseError, Part, Solution};
+
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
-}
-
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-fn fill(garden: &[u8], ncols: isize, nrows: isize, start: (isize, isize), steps: usize) -> u64 {
-    let mut distances = vec![None; nrows as usize * ncols as usize];
-
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-
-    while let Some(((x, y), distance)) = queue.pop_front() {
-        match &mut distances[x as usize + y as usize * ncols as usize] {
-            cell @ None => {
-                *cell = Some(distance);
-                for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
-                    let (x, y) = (x + dx, y + dy);
-                    if (0..ncols).contains(&x)
-                        && (0..nrows).contains(&y)
-                        && garden[x as usize + y as usize * (ncols as usize + 1)] != b'#'
-                        && distances[x as usize + y as usize * ncols as usize].is_none()
-                    {
-                        queue.push_back(((x, y), distance + 1));
-                    }
-                }
-            }
-            Some(dist) if *dist <= distance => {}
-            _ => unreachable!(),
-        }
-    }
+    pub static ref INPUT: &'static str = aoc::input!();
+}
 
-    distances
-        .iter()
-        .filter(|&v| {
-            if let Some(distance) = v {
-                distance <= &steps && steps % 2 == distance % 2
-            } else {
-                false
-            }
-        })
+/// Steps from `start` to every garden plot, row by row, `None` if
+/// unreachable.
+fn distances(garden: &Grid<&[u8]>, start: (usize, usize)) -> Vec<Option<usize>> {
+    let plots = |&position: &(usize, usize)| {
+        garden
+            .neighbors_4(position)
+            .filter(|&position| garden[position] != b'#')
+            .map(|position| (position, 1))
+    };
+
+    let ncols = garden.ncols();
+    bfs_distances(&plots, start, ncols * garden.nrows(), |&(x, y)| {
+        y * ncols + x
+    })
+}
+
+fn reached(distance: Option<usize>, steps: usize) -> bool {
+    distance.is_some_and(|distance| distance <= steps && steps % 2 == distance % 2)
+}
+
+fn fill(garden: &Grid<&[u8]>, start: (usize, usize), steps: usize) -> u64 {
+    distances(garden, start)
+        .into_iter()
+        .filter(|&distance| reached(distance, steps))
         .count() as u64
 }
 
-fn sqr(value: isize) -> isize {
+/// Garden plots reachable from `start` in exactly `steps` steps.
+fn plots(garden: &Grid<&[u8]>, start: (usize, usize), steps: usize) -> Vec<(usize, usize)> {
+    let ncols = garden.ncols();
+    distances(garden, start)
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, distance)| reached(distance, steps))
+        .map(|(index, _)| (index % ncols, index / ncols))
+        .collect()
+}
+
+fn sqr(value: u64) -> u64 {
     value * value
 }
 
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-fn parse(input: &str) -> (&[u8], isize, isize, (isize, isize)) {
-    let garden = {
-        let mut garden = input.as_bytes();
-        while let Some(tile) = garden.last() {
-            if tile.is_ascii_whitespace() {
-                garden = &garden[0..garden.len() - 1];
-            } else {
-                break;
-            }
-        }
-        garden
-    };
+pub struct Garden<'a> {
+    tiles: Grid<&'a [u8]>,
+    start: (usize, usize),
+}
 
-    let ncols = garden
-        .iter()
-        .position(|&c| c == b'\n')
-        .expect("invalid garden") as isize;
-    let nrows = (garden.len() as isize + 1) / (ncols + 1);
-
-    let Some(s) = garden.iter().position(|&c| c == b'S').map(|s| {
-        (
-            (s as isize).rem_euclid(ncols + 1),
-            (s as isize).div_euclid(ncols + 1),
-        )
-    }) else {
-        panic!("cannot find S");
-    };
+fn parse(input: &str) -> Result<Garden<'_>, ParseError> {
+    let parser = Parser::new(input);
+
+    let tiles = Grid::parse(parser, input, |tile| matches!(tile, b'.' | b'#' | b'S'))?;
+    let start = tiles
+        .find(b'S')
+        .ok_or_else(|| parser.error_end(ErrorKind::Missing("start S")))?;
+
+    Ok(Garden { tiles, start })
+}
 
-    (garden, ncols, nrows, s)
+/// Garden plots reachable in exactly `steps` steps.
+fn reachable(Garden { tiles, start }: &Garden, steps: usize) -> u64 {
+    fill(tiles, *start, steps)
 }
 
 /// Solve part 1
@@ -89,116 +81,80 @@ fn parse(input: &str) -> (&[u8], isize, isize, (isize, isize)) {
 /// # Panics
 /// Panic if invalid input
 pub fn solve_1(input: &str) -> u64 {
-    solve_1_steps(input, 64)
+    Day21::solve_1(input)
 }
 
-/// Solve part 1 with steps
+/// Solve part 2
 ///
 /// # Panics
 /// Panic if invalid input
-fn solve_1_steps(input: &str, steps: usize) -> u64 {
-    let (garden, ncols, nrows, s) = parse(input);
+pub fn solve_2(input: &str) -> u64 {
+    Day21::solve_2(input)
+}
 
-    fill(garden, ncols, nrows, s, steps)
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<u64, ParseError> {
+    Day21::try_solve_1(input)
 }
 
-/// Solve part 2
+/// Solve part 2, without panicking on invalid input
 ///
-/// # Panics
-/// Panic if invalid input
-#[allow(
-    clippy::unreadable_literal,
-    clippy::cast_sign_loss,
-    clippy::cast_possible_wrap
-)]
-pub fn solve_2(input: &str) -> u64 {
-    let garden = {
-        let mut garden = input.as_bytes();
-        while let Some(tile) = garden.last() {
-            if tile.is_ascii_whitespace() {
-                garden = &garden[0..garden.len() - 1];
-            } else {
-                break;
-            }
-        }
-        garden
-    };
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<u64, ParseError> {
+    Day21::try_solve_2(input)
+}
 
-    let ncols = garden
-        .iter()
-        .position(|&c| c == b'\n')
-        .expect("invalid garden") as isize;
-    let nrows = (garden.len() as isize + 1) / (ncols + 1);
-
-    let grid_width = 26501365 / ncols - 1;
-    let odd = sqr(grid_width / 2 * 2 + 1) as u64;
-    let even = sqr((grid_width + 1) / 2 * 2) as u64;
-
-    let odd_points = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols / 2, nrows / 2),
-        2 * ncols as usize + 1,
-    );
-    let even_points = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols / 2, nrows / 2),
-        2 * ncols as usize,
-    );
-
-    let corner_top = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols / 2, nrows - 1),
-        nrows as usize - 1,
-    );
-    let corner_right = fill(garden, ncols, nrows, (0, nrows / 2), ncols as usize - 1);
-    let corner_bottom = fill(garden, ncols, nrows, (ncols / 2, 0), nrows as usize - 1);
-    let corner_left = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols - 1, nrows / 2),
-        ncols as usize - 1,
-    );
-
-    let small_top_right = fill(garden, ncols, nrows, (0, nrows - 1), nrows as usize / 2 - 1);
-    let small_top_left = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols - 1, nrows - 1),
-        nrows as usize / 2 - 1,
-    );
-    let small_bottom_right = fill(garden, ncols, nrows, (0, 0), nrows as usize / 2 - 1);
-    let small_bottom_left = fill(garden, ncols, nrows, (ncols - 1, 0), nrows as usize / 2 - 1);
-
-    let large_top_right = fill(
-        garden,
-        ncols,
-        nrows,
-        (0, nrows - 1),
-        nrows as usize * 3 / 2 - 1,
-    );
-    let large_top_left = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols - 1, nrows - 1),
-        nrows as usize * 3 / 2 - 1,
-    );
-    let large_bottom_right = fill(garden, ncols, nrows, (0, 0), nrows as usize * 3 / 2 - 1);
-    let large_bottom_left = fill(
-        garden,
-        ncols,
-        nrows,
-        (ncols - 1, 0),
-        nrows as usize * 3 / 2 - 1,
-    );
+/// Check the shape [`infinite_reachable`] relies on: a square garden with
+/// an odd side of 3 tiles or more and S in its centre.
+fn check_infinite(Garden { tiles, start }: &Garden, input: &str) -> Result<(), ParseError> {
+    let parser = Parser::new(input);
+    let side = tiles.ncols();
+    if tiles.nrows() != side || side % 2 == 0 || side < 3 {
+        return Err(parser.error_end(ErrorKind::Invalid(
+            "garden, not square with an odd side of 3 or more",
+        )));
+    }
+    if *start != (side / 2, side / 2) {
+        let (x, y) = *start;
+        return Err(parser.error_bytes(
+            &tiles.row(y)[x..=x],
+            ErrorKind::Invalid("start S, not in the centre of the garden"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Garden plots reachable in 26501365 steps on the infinite garden.
+#[allow(clippy::unreadable_literal)]
+fn infinite_reachable(Garden { tiles: garden, .. }: &Garden) -> u64 {
+    let (ncols, nrows) = (garden.ncols(), garden.nrows());
+
+    let grid_width = 26501365 / ncols as u64 - 1;
+    let odd = sqr(grid_width / 2 * 2 + 1);
+    let even = sqr(grid_width.div_ceil(2) * 2);
+
+    let odd_points = fill(garden, (ncols / 2, nrows / 2), 2 * ncols + 1);
+    let even_points = fill(garden, (ncols / 2, nrows / 2), 2 * ncols);
+
+    let corner_top = fill(garden, (ncols / 2, nrows - 1), nrows - 1);
+    let corner_right = fill(garden, (0, nrows / 2), ncols - 1);
+    let corner_bottom = fill(garden, (ncols / 2, 0), nrows - 1);
+    let corner_left = fill(garden, (ncols - 1, nrows / 2), ncols - 1);
+
+    let small_top_right = fill(garden, (0, nrows - 1), nrows / 2 - 1);
+    let small_top_left = fill(garden, (ncols - 1, nrows - 1), nrows / 2 - 1);
+    let small_bottom_right = fill(garden, (0, 0), nrows / 2 - 1);
+    let small_bottom_left = fill(garden, (ncols - 1, 0), nrows / 2 - 1);
+
+    let large_top_right = fill(garden, (0, nrows - 1), nrows * 3 / 2 - 1);
+    let large_top_left = fill(garden, (ncols - 1, nrows - 1), nrows * 3 / 2 - 1);
+    let large_bottom_right = fill(garden, (0, 0), nrows * 3 / 2 - 1);
+    let large_bottom_left = fill(garden, (ncols - 1, 0), nrows * 3 / 2 - 1);
 
     odd_points * odd
         + even_points * even
@@ -206,10 +162,60 @@ pub fn solve_2(input: &str) -> u64 {
         + corner_right
         + corner_bottom
         + corner_left
-        + (grid_width as u64 + 1)
+        + (grid_width + 1)
             * (small_top_right + small_top_left + small_bottom_right + small_bottom_left)
-        + grid_width as u64
-            * (large_top_right + large_top_left + large_bottom_right + large_bottom_left)
+        + grid_width * (large_top_right + large_top_left + large_bottom_right + large_bottom_left)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Step Counter";
+
+    type Input<'a> = Garden<'a>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(garden: &Self::Input<'_>) -> Self::Output1 {
+        reachable(garden, 64)
+    }
+
+    fn part_2(garden: &Self::Input<'_>) -> Self::Output2 {
+        infinite_reachable(garden)
+    }
+
+    fn try_part_2(garden: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        check_infinite(garden, input)?;
+        Ok(infinite_reachable(garden))
+    }
+
+    fn try_part_overlay(
+        part: Part,
+        garden: &Self::Input<'_>,
+        input: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        let Garden { tiles, start } = garden;
+        Ok(match part {
+            Part::One => {
+                let plots = plots(tiles, *start, 64);
+                (
+                    plots.len().to_string(),
+                    Some(
+                        Overlay::default()
+                            .cells(Color::GREEN, plots)
+                            .cells(Color::RED, [*start]),
+                    ),
+                )
+            }
+            // the garden repeats too many times to draw
+            Part::Two => (Self::try_part_2(garden, input)?.to_string(), None),
+        })
+    }
 }
 
 pub fn part_1() -> u64 {
@@ -222,17 +228,63 @@ pub fn part_2() -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     lazy_static! {
         static ref EXAMPLE_1: &'static str = include_str!("../../example1");
     }
 
+    fn solve_1_steps(input: &str, steps: usize) -> u64 {
+        reachable(&Day21::parse(input), steps)
+    }
+
     #[test]
     fn same_results_1() {
         assert_eq!(solve_1_steps(&EXAMPLE_1, 6), 16);
     }
 
+    #[test]
+    fn overlay() {
+        let (timed, overlay) =
+            Day21::try_solve_overlay(Part::One, &EXAMPLE_1, &mut Duration::default).unwrap();
+        let overlay = overlay.unwrap();
+        assert_eq!(overlay.cells[0].positions.len() as u64, solve_1(&EXAMPLE_1));
+        assert_eq!(overlay.cells[1].positions, [(5, 5)]);
+        assert_eq!(timed.answer, solve_1(&EXAMPLE_1).to_string());
+
+        let (timed, overlay) =
+            Day21::try_solve_overlay(Part::Two, &INPUT, &mut Duration::default).unwrap();
+        assert_eq!((timed.answer, overlay), (solve_2(&INPUT).to_string(), None));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("...\n.S.\n.x.").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 2, "x"));
+        assert_eq!(error.kind, ErrorKind::InvalidTile);
+
+        let error = try_solve_2("...\n...\n...").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Missing("start S"));
+
+        let error = try_solve_2("S").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("garden, not square with an odd side of 3 or more")
+        );
+        assert!(try_solve_2("....\n.S..\n....\n....").is_err());
+        assert!(try_solve_2("...\n.S.\n").is_err());
+
+        let error = try_solve_2("...\nS..\n...").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, "S"));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("start S, not in the centre of the garden")
+        );
+        assert!(try_solve_2("...\n.S.\n...").is_ok());
+    }
+
     // #[test]
     // fn same_results_2() {
     //     assert_eq!(solve_2_steps(&EXAMPLE_1, 6), 16);
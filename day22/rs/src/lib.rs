@@ -1,10 +1,15 @@
 #![deny(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 
-use std::str::FromStr;
-
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, ParseError, Part, Solution};
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
@@ -15,41 +20,34 @@ mod spinlock;
 use spinlock::SpinLock;
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
+/// Brick, from one end to the other, as `x`, `y` and `z` coordinates.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-struct Brick([usize; 3], [usize; 3]);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brick(pub [usize; 3], pub [usize; 3]);
 
-impl FromStr for Brick {
-    type Err = &'static str;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let (start, end) = line.split_once('~').ok_or("invalid brick definition")?;
+impl Brick {
+    fn parse(parser: Parser, line: &str) -> Result<Self, ParseError> {
+        let (start, end) = parser.split_once(line, "~")?;
 
         let parse = |l: &str| {
             let mut i = l.split(',');
 
-            let x = i
-                .next()
-                .ok_or("x undefined")?
-                .parse()
-                .map_err(|_| "invalid x")?;
-            let y = i
-                .next()
-                .ok_or("y undefined")?
-                .parse()
-                .map_err(|_| "invalid y")?;
-            let z = i
-                .next()
-                .ok_or("z undefined")?
-                .parse()
-                .map_err(|_| "invalid z")?;
-
-            if i.next().is_none() {
-                Ok([x, y, z])
+            let mut coordinate = || {
+                i.next()
+                    .ok_or_else(|| parser.error(l, ErrorKind::Expected("3 coordinates")))
+                    .and_then(|c| parser.number(c))
+            };
+            let position = [coordinate()?, coordinate()?, coordinate()?];
+
+            if let Some(c) = i.next() {
+                Err(parser.error(c, ErrorKind::Expected("3 coordinates")))
+            } else if position[2] == 0 {
+                Err(parser.error(l, ErrorKind::Invalid("brick, z below 1")))
             } else {
-                Err("too many coordinates")
+                Ok(position)
             }
         };
 
@@ -74,41 +72,59 @@ macro_rules! intersect {
     };
 }
 
-impl Brick {
+/// Intersections of the bricks, memoized with the `spinlock` feature.
+///
+/// The memo lives as long as a solve, shared by its threads, so that
+/// inputs solved at the same time do not share it nor make it grow.
+struct Intersections {
+    #[cfg(feature = "spinlock")]
+    memoize: SpinLock<HashMap<[usize; 8], bool>>,
+}
+
+impl Intersections {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "spinlock")]
+            memoize: SpinLock::new(HashMap::with_capacity(1_024)),
+        }
+    }
+
     #[cfg(not(feature = "spinlock"))]
-    fn intersect(&self, start: &[usize], end: &[usize]) -> bool {
-        intersect!(self.0, self.1, start, end)
+    #[allow(clippy::unused_self)]
+    fn intersect(&self, brick: &Brick, start: &[usize], end: &[usize]) -> bool {
+        brick.intersect(start, end)
     }
 
     #[cfg(feature = "spinlock")]
-    fn intersect(&self, start: &[usize], end: &[usize]) -> bool {
-        lazy_static! {
-            static ref MEMOIZE: SpinLock<std::collections::HashMap<[usize; 8], bool>> =
-                SpinLock::new(std::collections::HashMap::with_capacity(1_024));
-        }
-
+    fn intersect(&self, brick: &Brick, start: &[usize], end: &[usize]) -> bool {
         let key1 = [
-            self.0[0], self.0[1], self.1[0], self.1[1], start[0], start[1], end[0], end[1],
+            brick.0[0], brick.0[1], brick.1[0], brick.1[1], start[0], start[1], end[0], end[1],
         ];
-        if let Some(value) = MEMOIZE.lock().get(&key1) {
+        if let Some(value) = self.memoize.lock().get(&key1) {
             return *value;
         }
 
         let key2 = [
-            start[0], start[1], end[0], end[1], self.0[0], self.0[1], self.1[0], self.1[1],
+            start[0], start[1], end[0], end[1], brick.0[0], brick.0[1], brick.1[0], brick.1[1],
         ];
-        if let Some(value) = MEMOIZE.lock().get(&key2) {
+        if let Some(value) = self.memoize.lock().get(&key2) {
             return *value;
         }
 
-        let value = intersect!(self.0, self.1, start, end);
+        let value = brick.intersect(start, end);
 
-        let map = &mut MEMOIZE.lock();
+        let map = &mut self.memoize.lock();
         map.insert(key1, value);
         map.insert(key2, value);
 
         value
     }
+}
+
+impl Brick {
+    fn intersect(&self, start: &[usize], end: &[usize]) -> bool {
+        intersect!(self.0, self.1, start, end)
+    }
 
     fn fall(&mut self, dz: usize) -> bool {
         self.0[2] -= dz;
@@ -116,28 +132,38 @@ impl Brick {
         dz > 0
     }
 
-    fn removable(&self, bricks: &[Self]) -> bool {
+    fn removable(&self, bricks: &[Self], intersections: &Intersections) -> bool {
         bricks
             .iter()
             .filter(|candidate| {
-                candidate.0[2] == self.1[2] + 1 && candidate.intersect(&self.0[..2], &self.1[..2])
+                candidate.0[2] == self.1[2] + 1
+                    && intersections.intersect(candidate, &self.0[..2], &self.1[..2])
             })
             .all(|candidate| {
                 bricks.iter().filter(|base| base != &self).any(|base| {
                     base.1[2] + 1 == candidate.0[2]
-                        && candidate.intersect(&base.0[..2], &base.1[..2])
+                        && intersections.intersect(candidate, &base.0[..2], &base.1[..2])
                 })
             })
     }
 }
 
-fn fall(mut bricks: Vec<Brick>) -> (Vec<Brick>, usize) {
+/// Let `bricks` fall, returning them and how many fell, reporting the
+/// progress as `phase` if any.
+fn fall(
+    mut bricks: Vec<Brick>,
+    intersections: &Intersections,
+    phase: Option<&str>,
+) -> (Vec<Brick>, usize) {
+    let total = bricks.len();
     let mut count = 0;
     let mut fallens: Vec<Brick> = Vec::new();
     while let Some(i) = bricks.iter().position(|brick| {
         bricks
             .iter()
-            .filter(|other| other != &brick && other.intersect(&brick.0[..2], &brick.1[..2]))
+            .filter(|other| {
+                other != &brick && intersections.intersect(other, &brick.0[..2], &brick.1[..2])
+            })
             .all(|other| other.0[2] > brick.1[2])
     }) {
         let mut brick = bricks.remove(i);
@@ -145,7 +171,8 @@ fn fall(mut bricks: Vec<Brick>) -> (Vec<Brick>, usize) {
         let falled = if let Some(base) = fallens
             .iter()
             .filter(|fallen| {
-                fallen.intersect(&brick.0[..2], &brick.1[..2]) && fallen.1[2] < brick.0[2]
+                intersections.intersect(fallen, &brick.0[..2], &brick.1[..2])
+                    && fallen.1[2] < brick.0[2]
             })
             .max_by_key(|a| a.1[2])
         {
@@ -156,33 +183,29 @@ fn fall(mut bricks: Vec<Brick>) -> (Vec<Brick>, usize) {
 
         fallens.push(brick);
         count += usize::from(falled);
+        if let Some(phase) = phase {
+            aoc::progress::report(phase, fallens.len(), total);
+        }
     }
 
     (fallens, count)
 }
 
-/// Parse input
-///
-/// # Panics
-/// Panic if invalid input
-fn parse(input: &str) -> Vec<Brick> {
+fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    let parser = Parser::new(input);
+
     let mut bricks = input
         .lines()
-        .map(Brick::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .expect("invalid input");
+        .map(|line| Brick::parse(parser, line))
+        .collect::<Result<Vec<_>, _>>()?;
 
     bricks.sort_unstable_by_key(|b| b.0[2]);
 
-    bricks
+    Ok(bricks)
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_1(input: &str) -> usize {
-    let (bricks, _) = fall(parse(input));
+fn disintegrable(bricks: &[Brick]) -> usize {
+    let intersections = Intersections::new();
 
     #[cfg(feature = "rayon")]
     let i = bricks.par_iter();
@@ -190,15 +213,12 @@ pub fn solve_1(input: &str) -> usize {
     #[cfg(not(feature = "rayon"))]
     let i = bricks.iter();
 
-    i.filter(|brick| brick.removable(&bricks)).count()
+    i.filter(|brick| brick.removable(bricks, &intersections))
+        .count()
 }
 
-/// Solve part 2
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_2(input: &str) -> usize {
-    let (bricks, _) = fall(parse(input));
+fn chain_reaction(bricks: &[Brick]) -> usize {
+    let intersections = Intersections::new();
 
     #[cfg(feature = "rayon")]
     let i = bricks.par_iter();
@@ -206,23 +226,312 @@ pub fn solve_2(input: &str) -> usize {
     #[cfg(not(feature = "rayon"))]
     let i = bricks.iter();
 
-    i.filter_map(|brick| {
-        if brick.removable(&bricks) {
-            None
-        } else {
-            Some(
-                fall(
-                    bricks
-                        .iter()
-                        .copied()
-                        .filter(|b| b != brick)
-                        .collect::<Vec<_>>(),
+    i.enumerate()
+        .filter_map(|(done, brick)| {
+            aoc::progress::report("chain reaction", done, bricks.len());
+            if brick.removable(bricks, &intersections) {
+                None
+            } else {
+                Some(
+                    fall(
+                        bricks
+                            .iter()
+                            .copied()
+                            .filter(|b| b != brick)
+                            .collect::<Vec<_>>(),
+                        &intersections,
+                        None,
+                    )
+                    .1,
                 )
-                .1,
-            )
+            }
+        })
+        .sum()
+}
+
+/// Brick settled, with its top seen from above.
+struct Seen {
+    /// Cells of the height map where its top is seen.
+    cells: usize,
+    /// Whether it is the only support of another brick.
+    sole_support: bool,
+}
+
+/// Brick in the tree of the bricks that make it fall when removed, its
+/// dominators in the graph of the supports from the ground.
+struct Dominated {
+    /// Nearest dominator kept, the ground at the root.
+    parent: usize,
+    /// Dominators up to the ground, itself included.
+    depth: usize,
+    children: Vec<usize>,
+}
+
+/// Bricks settled one at a time in the order of their bottom, on the
+/// height map of their tops seen from above.
+///
+/// Only the bricks with their top seen can support the next ones, the
+/// others are forgotten once covered: the memory is bounded by the area
+/// of the height map, not by the number of bricks. The dominator tree
+/// keeps the bricks seen and their common dominators, the dominators of
+/// a single brick kept only as its depth.
+struct Settling {
+    /// Top and brick seen at every `x`, `y`.
+    heights: HashMap<(usize, usize), (usize, usize)>,
+    seen: HashMap<usize, Seen>,
+    dominators: HashMap<usize, Dominated>,
+    bricks: usize,
+    /// Bottom of the last brick settled.
+    bottom: usize,
+    sole_supports: usize,
+    /// Bricks falling when each brick is removed, summed.
+    falling: usize,
+}
+
+impl Settling {
+    /// The ground, at the root of the dominator tree.
+    const GROUND: usize = 0;
+
+    fn new() -> Self {
+        let ground = Dominated {
+            parent: Self::GROUND,
+            depth: 0,
+            children: vec![],
+        };
+        Self {
+            heights: HashMap::new(),
+            seen: HashMap::new(),
+            dominators: HashMap::from([(Self::GROUND, ground)]),
+            bricks: 0,
+            bottom: 0,
+            sole_supports: 0,
+            falling: 0,
         }
-    })
-    .sum()
+    }
+
+    /// Nearest common dominator of `a` and `b`.
+    fn common_dominator(&self, mut a: usize, mut b: usize) -> usize {
+        while a != b {
+            if self.dominators[&a].depth >= self.dominators[&b].depth {
+                a = self.dominators[&a].parent;
+            } else {
+                b = self.dominators[&b].parent;
+            }
+        }
+        a
+    }
+
+    /// Let `brick` fall on the bricks settled, returns `false` if it is
+    /// below the last one.
+    fn settle(&mut self, Brick(start, end): Brick) -> bool {
+        if start[2] < self.bottom {
+            return false;
+        }
+        self.bottom = start[2];
+        self.bricks += 1;
+        let id = self.bricks;
+
+        let (xs, ys) = (
+            start[0].min(end[0])..=start[0].max(end[0]),
+            start[1].min(end[1])..=start[1].max(end[1]),
+        );
+        let cells = xs
+            .flat_map(|x| ys.clone().map(move |y| (x, y)))
+            .collect::<Vec<_>>();
+
+        let below = cells
+            .iter()
+            .filter_map(|cell| self.heights.get(cell))
+            .map(|&(top, _)| top)
+            .max()
+            .unwrap_or(0);
+        let mut supports = cells
+            .iter()
+            .filter_map(|cell| self.heights.get(cell))
+            .filter(|&&(top, _)| top == below)
+            .map(|&(_, brick)| brick)
+            .collect::<Vec<_>>();
+        supports.sort_unstable();
+        supports.dedup();
+
+        if let [support] = supports[..] {
+            let seen = self.seen.get_mut(&support).expect("support not seen");
+            if !seen.sole_support {
+                seen.sole_support = true;
+                self.sole_supports += 1;
+            }
+        }
+
+        let parent = supports
+            .iter()
+            .copied()
+            .reduce(|a, b| self.common_dominator(a, b))
+            .unwrap_or(Self::GROUND);
+        let depth = self.dominators[&parent].depth + 1;
+        self.falling += depth - 1;
+        self.dominators.get_mut(&parent).unwrap().children.push(id);
+        self.dominators.insert(
+            id,
+            Dominated {
+                parent,
+                depth,
+                children: vec![],
+            },
+        );
+
+        let top = below + 1 + start[2].abs_diff(end[2]);
+        self.seen.insert(
+            id,
+            Seen {
+                cells: cells.len(),
+                sole_support: false,
+            },
+        );
+        for cell in cells {
+            if let Some((_, covered)) = self.heights.insert(cell, (top, id)) {
+                let seen = self.seen.get_mut(&covered).unwrap();
+                seen.cells -= 1;
+                if seen.cells == 0 {
+                    self.seen.remove(&covered);
+                    self.forget(covered);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Remove `brick` from the dominator tree if it is no longer seen nor
+    /// a common dominator of the bricks seen, and its parents after it.
+    fn forget(&mut self, mut brick: usize) {
+        while brick != Self::GROUND && !self.seen.contains_key(&brick) {
+            let dominated = &self.dominators[&brick];
+            let parent = dominated.parent;
+            match dominated.children[..] {
+                [] => {
+                    self.dominators.remove(&brick);
+                    let children = &mut self.dominators.get_mut(&parent).unwrap().children;
+                    children.retain(|&child| child != brick);
+                    brick = parent;
+                }
+                [child] => {
+                    self.dominators.remove(&brick);
+                    self.dominators.get_mut(&child).unwrap().parent = parent;
+                    for sibling in &mut self.dominators.get_mut(&parent).unwrap().children {
+                        if *sibling == brick {
+                            *sibling = child;
+                        }
+                    }
+                    return;
+                }
+                _ => return,
+            }
+        }
+    }
+}
+
+/// Bricks of the input read a line at a time, settled as read: the input
+/// must be sorted by the bottom of the bricks.
+fn settle_reader<R: BufRead>(reader: R) -> Result<Settling, ReadError> {
+    let mut settling = Settling::new();
+    input::for_each_line(reader, |line| {
+        let parser = Parser::new(line);
+        let brick = Brick::parse(parser, line)?;
+        if settling.settle(brick) {
+            Ok(())
+        } else {
+            Err(parser.error(line, ErrorKind::Invalid("brick, below the previous one")))
+        }
+    })?;
+
+    Ok(settling)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Sand Slabs";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "rayon")]
+        "rayon",
+        #[cfg(feature = "spinlock")]
+        "spinlock",
+    ];
+
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 5),
+        aoc::example!("example1", Part::Two, 7),
+    ];
+
+    type Input<'a> = Vec<Brick>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (bricks, _) = fall(parse(input)?, &Intersections::new(), Some("fall"));
+
+        Ok(bricks)
+    }
+
+    fn part_1(bricks: &Self::Input<'_>) -> Self::Output1 {
+        disintegrable(bricks)
+    }
+
+    fn part_2(bricks: &Self::Input<'_>) -> Self::Output2 {
+        chain_reaction(bricks)
+    }
+}
+
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> usize {
+    Day22::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_2(input: &str) -> usize {
+    Day22::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day22::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day22::try_solve_2(input)
+}
+
+/// Solve part 1 reading the input a line at a time, sorted by the
+/// bottom of the bricks, keeping only the bricks seen from above
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<usize, ReadError> {
+    let settling = settle_reader(reader)?;
+    Ok(settling.bricks - settling.sole_supports)
+}
+
+/// Solve part 2 reading the input a line at a time, sorted by the
+/// bottom of the bricks, keeping only the bricks seen from above
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<usize, ReadError> {
+    Ok(settle_reader(reader)?.falling)
 }
 
 pub fn part_1() -> usize {
@@ -251,6 +560,94 @@ mod tests {
         assert_eq!(solve_2(&EXAMPLE_1), 7);
     }
 
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("1,0,1~1,2,1\n0,0,2~2,0").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 7, "2,0")
+        );
+        assert_eq!(error.kind, ErrorKind::Expected("3 coordinates"));
+
+        let error = try_solve_2("1,0,0~1,2,0").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Invalid("brick, z below 1"));
+    }
+
+    fn sorted(input: &str) -> String {
+        let mut lines = input.lines().collect::<Vec<_>>();
+        lines.sort_by_key(|line| Brick::parse(Parser::new(line), line).unwrap().0[2]);
+        lines.join("\n")
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT].map(sorted) {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(&input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(&input));
+        }
+    }
+
+    #[test]
+    fn reader_forgets_covered() {
+        // a tower of 1000 bricks, each on the one below
+        let input = (1..=1000)
+            .map(|z| format!("0,0,{z}~1,1,{z}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let settling = settle_reader(input.as_bytes()).unwrap();
+        assert_eq!((settling.bricks, settling.sole_supports), (1000, 999));
+        assert_eq!(settling.falling, 999 * 1000 / 2);
+        assert_eq!(settling.seen.len(), 1);
+        assert_eq!(settling.dominators.len(), 2);
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) = solve_1_reader("1,0,1~1,2,1\n0,0,2~2,0".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 7));
+
+        let Err(ReadError::Parse(error)) = solve_2_reader("1,0,2~1,2,2\n0,0,1~2,0,1".as_bytes())
+        else {
+            panic!("unsorted input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("brick, below the previous one")
+        );
+    }
+
+    #[cfg(feature = "spinlock")]
+    #[test]
+    fn memo_per_solve() {
+        let a = Brick([0, 0, 1], [1, 0, 1]);
+        let b = Brick([0, 0, 2], [0, 2, 2]);
+
+        let intersections = Intersections::new();
+        assert!(intersections.intersect(&a, &b.0[..2], &b.1[..2]));
+        assert!(intersections.intersect(&b, &a.0[..2], &a.1[..2]));
+        assert_eq!(intersections.memoize.lock().len(), 2);
+
+        assert!(Intersections::new().memoize.lock().is_empty());
+    }
+
+    #[cfg(feature = "spinlock")]
+    #[test]
+    fn same_results_parallel() {
+        let inputs = [*EXAMPLE_1, *INPUT, *EXAMPLE_1, *INPUT];
+        let expected = inputs.map(solve_1);
+
+        let solved = std::thread::scope(|scope| {
+            inputs
+                .map(|input| scope.spawn(move || solve_1(input)))
+                .map(|handle| handle.join().unwrap())
+        });
+        assert_eq!(solved, expected);
+    }
+
     #[test]
     fn test_intersect_1() {
         let a = Brick([0, 0, 0], [1, 0, 0]);
@@ -1,9 +1,7 @@
 use day22ui::Part2;
 
-use gloo_worker::Registrable;
-
 fn main() {
     console_error_panic_hook::set_once();
 
-    rsui::Solve::<Part2>::registrar().register();
+    rsui::Solve::<Part2>::register();
 }
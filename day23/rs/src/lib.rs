@@ -3,209 +3,235 @@
 
 use lazy_static::lazy_static;
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use aoc::bitset::BitSet;
+use aoc::graph::{bfs, longest_path};
+use aoc::grid::{Grid, NEIGHBORS_4};
+use aoc::overlay::Color;
+use aoc::parse::Parser;
+use aoc::{ErrorKind, Example, Overlay, ParseError, Part, Solution};
+
+use std::collections::HashMap;
 
 #[cfg(feature = "fast")]
 mod transform;
 #[cfg(feature = "fast")]
-use transform::{transform, Set};
+use transform::{longest_walk, transform};
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
-struct Map<'a> {
-    data: &'a [u8],
-    nrows: usize,
-    ncols: usize,
+pub struct Map<'a> {
+    tiles: Grid<&'a [u8]>,
 }
 
 impl<'a> Map<'a> {
-    fn parse(input: &'a str) -> Result<Self, &'static str> {
-        let mut data = input.as_bytes();
-        while let Some(tile) = data.last() {
-            if tile.is_ascii_whitespace() {
-                data = &data[..data.len() - 1];
-            } else {
-                break;
-            }
-        }
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let parser = Parser::new(input);
 
-        let ncols = data
-            .iter()
-            .position(u8::is_ascii_whitespace)
-            .ok_or("invalid input map, cannot find columns")?;
-        let nrows = (data.len() + 1) / (ncols + 1);
+        let tiles = Grid::parse(parser, input, |tile| b"#.^>v<".contains(&tile))?;
+        let (first, last) = (tiles.row(0), tiles.row(tiles.nrows() - 1));
+        if first.get(1) != Some(&b'.') || tiles.nrows() < 2 {
+            return Err(parser.error_bytes(first, ErrorKind::Missing("start path in column 2")));
+        }
+        if last[last.len() - 2] != b'.' {
+            return Err(parser.error_bytes(
+                last,
+                ErrorKind::Missing("end path in the last but one column"),
+            ));
+        }
 
-        Ok(Self { data, nrows, ncols })
+        Ok(Self { tiles })
     }
 }
 
-/// Solve part 1
-///
-/// # Panics
-/// Panic if invalid input
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-pub fn solve_1(input: &str) -> usize {
-    let Map { data, nrows, ncols } = Map::parse(input).expect("invalid input");
-
-    let mut longest_path_len = 0;
-    let mut paths = vec![((1, 0), HashSet::new())];
-
-    while let Some(((mut x, mut y), mut path)) = paths.pop() {
-        loop {
-            path.insert((x, y));
-
-            if (x, y) == (ncols - 2, nrows - 1) {
-                longest_path_len = longest_path_len.max(path.len());
-                break;
-            }
+fn longest_hike(map: &Map) -> Option<usize> {
+    longest_hike_path(map).map(|(len, _)| len)
+}
 
-            let directions: &[(isize, isize)] = match data[x + y * (ncols + 1)] {
-                b'^' => &[(0, -1)],
-                b'>' => &[(1, 0)],
-                b'v' => &[(0, 1)],
-                b'<' => &[(-1, 0)],
-                b'.' => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
-                _ => unreachable!(),
-            };
-            let mut next = directions.iter().filter_map(|(dx, dy)| {
-                let (x, y) = (x as isize + dx, y as isize + dy);
-                if (0..ncols as isize).contains(&x)
-                    && (0..nrows as isize).contains(&y)
-                    && data[x as usize + y as usize * (ncols + 1)] != b'#'
-                    && !path.contains(&(x as usize, y as usize))
-                {
-                    Some((x as usize, y as usize))
-                } else {
-                    None
-                }
-            });
-            if let Some((new_x, new_y)) = next.next() {
-                for p in next {
-                    paths.push((p, path.clone()));
-                }
-                (x, y) = (new_x, new_y);
-            } else {
-                break;
-            }
-        }
-    }
+/// Longest hike down the slopes, with the tiles walked, the start
+/// included, if the end can be reached.
+fn longest_hike_path(Map { tiles }: &Map) -> Option<(usize, Vec<(usize, usize)>)> {
+    let trails = |&position: &(usize, usize)| {
+        let directions: &[(isize, isize)] = match tiles[position] {
+            b'^' => &[(0, -1)],
+            b'>' => &[(1, 0)],
+            b'v' => &[(0, 1)],
+            b'<' => &[(-1, 0)],
+            b'.' => &NEIGHBORS_4,
+            _ => unreachable!(),
+        };
+        directions
+            .iter()
+            .filter_map(move |&delta| tiles.step(position, delta))
+            .filter(|&position| tiles[position] != b'#')
+            .map(|position| (position, 1))
+    };
 
-    longest_path_len - 1
+    let end = (tiles.ncols() - 2, tiles.nrows() - 1);
+    longest_path(&trails, (1, 0), |&position| position == end)
 }
 
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
 fn edges(
-    data: &[u8],
-    nrows: usize,
-    ncols: usize,
-    nodes: &HashSet<(usize, usize)>,
+    tiles: &Grid<&[u8]>,
+    crossings: &BitSet,
     start: (usize, usize),
-) -> HashSet<((usize, usize), usize)> {
-    let mut edges = HashSet::new();
-
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    queue.push_back((start, 0));
-    visited.insert(start);
-
-    while let Some((node, len)) = queue.pop_front() {
-        if node != start && nodes.contains(&node) {
-            edges.insert((node, len));
-        } else {
-            let (x, y) = (node.0 as isize, node.1 as isize);
-            for (x, y) in [(x, y + 1), (x, y - 1), (x + 1, y), (x - 1, y)] {
-                if (0..ncols as isize).contains(&x)
-                    && (0..nrows as isize).contains(&y)
-                    && data[x as usize + y as usize * (ncols + 1)] != b'#'
-                {
-                    let node = (x as usize, y as usize);
-                    if !visited.contains(&node) {
-                        visited.insert(node);
-                        queue.push_back((node, len + 1));
+) -> Vec<((usize, usize), usize)> {
+    let is_crossing =
+        |(x, y): (usize, usize)| (x, y) != start && crossings.contains(y * tiles.ncols() + x);
+
+    // walk the trails up to the nearest crossings
+    let trails = |&position: &(usize, usize)| {
+        let crossing = is_crossing(position);
+        tiles
+            .neighbors_4(position)
+            .filter(move |&position| !crossing && tiles[position] != b'#')
+            .map(|position| (position, 1))
+    };
+
+    bfs(&trails, start)
+        .iter()
+        .filter(|&(&node, _)| is_crossing(node))
+        .map(|(&node, len)| (node, len))
+        .collect()
+}
+
+fn longest_dry_hike(Map { tiles }: &Map) -> Option<usize> {
+    let (ncols, nrows) = (tiles.ncols(), tiles.nrows());
+    let (start, end) = ((1, 0), (ncols - 2, nrows - 1));
+
+    // compress the trails to the edges between crossings
+    let edges = {
+        let _span = aoc::span!("compress");
+        let crossings = (1..ncols - 1)
+            .flat_map(|x| {
+                (1..nrows - 1).filter_map(move |y| {
+                    if tiles[(x, y)] != b'#'
+                        && tiles
+                            .neighbors_4((x, y))
+                            .filter(|&position| tiles[position] != b'#')
+                            .count()
+                            > 2
+                    {
+                        Some((x, y))
+                    } else {
+                        None
                     }
-                }
-            }
-        }
+                })
+            })
+            .chain([start, end])
+            .map(|(x, y)| y * ncols + x)
+            .collect::<BitSet>();
+
+        crossings
+            .iter()
+            .map(|index| (index % ncols, index / ncols))
+            .map(|node| (node, edges(tiles, &crossings, node)))
+            .collect::<HashMap<_, _>>()
+    };
+    aoc::event!(crossings = edges.len(), "trails compressed");
+
+    #[cfg(feature = "fast")]
+    if let Some((edges, start, end)) = transform(&edges, start, end) {
+        return longest_walk(&edges, start, end);
     }
 
-    edges
+    let crossings = |node: &(usize, usize)| edges[node].iter().copied();
+    longest_path(&crossings, start, |&node| node == end).map(|(len, _)| len)
 }
 
-/// Solve part 2
-///
-/// # Panics
-/// Panic if invalid input
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-pub fn solve_2(input: &str) -> usize {
-    let Map { data, nrows, ncols } = Map::parse(input).expect("invalid input");
-
-    let nodes = (1..ncols - 1)
-        .flat_map(|x| {
-            (1..nrows - 1).filter_map(move |y| {
-                if data[x + y * (ncols + 1)] != b'#'
-                    && [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                        .iter()
-                        .filter(|(dx, dy)| {
-                            let (x, y) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
-                            data[x + y * (ncols + 1)] != b'#'
-                        })
-                        .count()
-                        > 2
-                {
-                    Some((x, y))
-                } else {
-                    None
-                }
-            })
-        })
-        .chain([(1, 0), (ncols - 2, nrows - 1)])
-        .collect::<HashSet<_>>();
+fn unreachable_end(input: &str) -> ParseError {
+    Parser::new(input).error_end(ErrorKind::Invalid("end path, cannot be reached"))
+}
 
-    let edges = nodes
-        .iter()
-        .map(|&node| (node, edges(data, nrows, ncols, &nodes, node)))
-        .collect::<HashMap<_, _>>();
+pub struct Day23;
 
-    #[cfg(feature = "fast")]
-    let ((edges, start, end), start_path) = (transform(nrows, ncols, nodes, edges), 0_u64);
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "A Long Walk";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "fast")]
+        "fast",
+    ];
 
-    #[cfg(not(feature = "fast"))]
-    let (start, end, start_path) = ((1, 0), (ncols - 2, nrows - 1), HashSet::new());
+    const EXAMPLES: &'static [Example] = &[
+        aoc::example!("example1", Part::One, 94),
+        aoc::example!("example1", Part::Two, 154),
+    ];
 
-    let mut longest_path_len = 0;
+    type Input<'a> = Map<'a>;
+    type Output1 = usize;
+    type Output2 = usize;
 
-    let mut paths = vec![(start, start_path, 0)];
-    while let Some((mut node, mut path, mut len)) = paths.pop() {
-        loop {
-            path.insert(node);
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Map::parse(input)
+    }
 
-            if node == end {
-                longest_path_len = longest_path_len.max(len);
-                break;
-            }
+    fn part_1(map: &Self::Input<'_>) -> Self::Output1 {
+        longest_hike(map).expect("cannot reach the end")
+    }
 
-            let mut next = edges[&node].iter().filter(|(node, _)| !path.contains(node));
+    fn part_2(map: &Self::Input<'_>) -> Self::Output2 {
+        longest_dry_hike(map).expect("cannot reach the end")
+    }
 
-            if let Some((new_node, weight)) = next.next() {
-                for (new_node, new_weight) in next {
-                    #[cfg(feature = "fast")]
-                    paths.push((*new_node, path, len + new_weight));
+    fn try_part_1(map: &Self::Input<'_>, input: &str) -> Result<Self::Output1, ParseError> {
+        longest_hike(map).ok_or_else(|| unreachable_end(input))
+    }
 
-                    #[cfg(not(feature = "fast"))]
-                    paths.push((*new_node, path.clone(), len + new_weight));
-                }
+    fn try_part_2(map: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        longest_dry_hike(map).ok_or_else(|| unreachable_end(input))
+    }
 
-                node = *new_node;
-                len += weight;
-            } else {
-                break;
+    fn try_part_overlay(
+        part: Part,
+        map: &Self::Input<'_>,
+        input: &str,
+    ) -> Result<(String, Option<Overlay>), ParseError> {
+        match part {
+            Part::One => {
+                let (len, hike) = longest_hike_path(map).ok_or_else(|| unreachable_end(input))?;
+                Ok((
+                    len.to_string(),
+                    Some(Overlay::default().path(Color::RED, hike)),
+                ))
             }
+            // the hike is only known from crossing to crossing
+            Part::Two => Ok((Self::try_part_2(map, input)?.to_string(), None)),
         }
     }
+}
 
-    longest_path_len
+/// Solve part 1
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_1(input: &str) -> usize {
+    Day23::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_2(input: &str) -> usize {
+    Day23::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day23::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<usize, ParseError> {
+    Day23::try_solve_2(input)
 }
 
 pub fn part_1() -> usize {
@@ -218,6 +244,8 @@ pub fn part_2() -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     lazy_static! {
@@ -233,4 +261,45 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(&EXAMPLE_1), 154);
     }
+
+    #[test]
+    fn overlay() {
+        let (timed, overlay) =
+            Day23::try_solve_overlay(Part::One, &EXAMPLE_1, &mut Duration::default).unwrap();
+        assert_eq!(timed.answer, "94");
+        let hike = &overlay.unwrap().paths[0].positions;
+        assert_eq!(hike.len(), 94 + 1);
+        assert_eq!((hike[0], hike[94]), ((1, 0), (21, 22)));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("#.##\n#..#\n#x.#").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 2, "x"));
+        assert_eq!(error.kind, ErrorKind::InvalidTile);
+
+        let error = try_solve_2("#.##\n#..#\n#.##").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Missing("end path in the last but one column")
+        );
+
+        for error in [
+            try_solve_1("#.###\n#####\n###.#").unwrap_err(),
+            try_solve_2("#.###\n#####\n###.#").unwrap_err(),
+        ] {
+            assert_eq!((error.line, error.column), (3, 6));
+            assert_eq!(
+                error.kind,
+                ErrorKind::Invalid("end path, cannot be reached")
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_blank_line() {
+        assert_eq!(try_solve_1("#.###\n#...#\n###.#\n\n"), Ok(4));
+        assert_eq!(try_solve_2("#.###\n#...#\n###.#\n\n"), Ok(4));
+    }
 }
@@ -1,58 +1,74 @@
-use std::collections::{HashMap, HashSet};
-use std::ops;
+use std::collections::HashMap;
 
-pub type EdgesPoint<T> = HashMap<T, HashSet<(T, usize)>>;
-pub type Edges<T> = HashMap<T, Vec<(T, usize)>>;
+use aoc::bitset::InlineBitSet;
 
-pub trait Set<T> {
-    fn insert(&mut self, element: T);
-    fn contains(&self, element: &T) -> bool;
-}
+pub type EdgesPoint<T> = HashMap<T, Vec<(T, usize)>>;
+pub type Edges = Vec<Vec<(usize, usize)>>;
 
-impl<T> Set<T> for T
-where
-    T: ops::Shl<Output = T>,
-    T: ops::BitOrAssign,
-    T: ops::BitAnd<T, Output = T>,
-    T: From<u16>,
-    T: PartialEq<T>,
-    T: Copy,
-{
-    fn insert(&mut self, element: T) {
-        *self |= T::from(1) << element;
-    }
+/// Crossings walked, numbered as by [`transform`].
+type Walked = InlineBitSet<1>;
 
-    fn contains(&self, element: &T) -> bool {
-        *self & (T::from(1) << *element) != T::from(0)
+/// Edges between the crossings numbered from 0, with the numbers of
+/// `start` and `end`, if there are few enough crossings to walk them
+/// with the set of the crossings walked in a [`Walked`].
+pub fn transform(
+    edges: &EdgesPoint<(usize, usize)>,
+    start: (usize, usize),
+    end: (usize, usize),
+) -> Option<(Edges, usize, usize)> {
+    if edges.len() > Walked::CAPACITY {
+        return None;
     }
-}
 
-pub fn transform(
-    nrows: usize,
-    ncols: usize,
-    nodes: HashSet<(usize, usize)>,
-    edges: EdgesPoint<(usize, usize)>,
-) -> (Edges<u64>, u64, u64) {
-    let lookup = nodes
-        .into_iter()
+    let lookup = edges
+        .keys()
         .enumerate()
-        .map(|(i, node)| (node, i as u64))
+        .map(|(i, &node)| (node, i))
         .collect::<HashMap<_, _>>();
 
-    (
-        edges
-            .into_iter()
-            .map(|(node, neighbors)| {
-                (
-                    lookup[&node],
-                    neighbors
-                        .into_iter()
-                        .map(|(node, len)| (lookup[&node], len))
-                        .collect::<Vec<_>>(),
-                )
-            })
-            .collect::<HashMap<_, _>>(),
-        lookup[&(1, 0)],
-        lookup[&(ncols - 2, nrows - 1)],
-    )
+    let mut numbered = vec![vec![]; edges.len()];
+    for (node, neighbors) in edges {
+        numbered[lookup[node]] = neighbors
+            .iter()
+            .map(|(node, len)| (lookup[node], *len))
+            .collect();
+    }
+
+    Some((numbered, lookup[&start], lookup[&end]))
+}
+
+/// Longest walk from `start` to `end` never walking a crossing twice,
+/// if `end` can be reached.
+pub fn longest_walk(edges: &Edges, start: usize, end: usize) -> Option<usize> {
+    let _span = aoc::span!("longest_walk");
+    let mut longest_path_len = None;
+
+    let mut paths = vec![(start, Walked::new(), 0)];
+    while let Some((mut node, mut path, mut len)) = paths.pop() {
+        loop {
+            path.insert(node);
+
+            if node == end {
+                longest_path_len = longest_path_len.max(Some(len));
+                break;
+            }
+
+            let mut next = edges[node]
+                .iter()
+                .filter(|&&(node, _)| !path.contains(node));
+
+            if let Some((new_node, weight)) = next.next() {
+                for (new_node, new_weight) in next {
+                    paths.push((*new_node, path, len + new_weight));
+                }
+
+                node = *new_node;
+                len += weight;
+            } else {
+                break;
+            }
+        }
+    }
+
+    longest_path_len
 }
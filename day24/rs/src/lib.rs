@@ -1,22 +1,26 @@
 #![deny(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 
-use std::str::FromStr;
+use std::io::BufRead;
 
 use lazy_static::lazy_static;
 
+use aoc::input::{self, ReadError};
+use aoc::parse::Parser;
+use aoc::{ErrorKind, ParseError, Solution};
+
 #[cfg(feature = "z3")]
 mod z3;
 #[cfg(feature = "z3")]
-pub use z3::{solve_2, part_2};
+use z3::{rock, Output2};
 
 #[cfg(not(feature = "z3"))]
 mod simple;
 #[cfg(not(feature = "z3"))]
-pub use simple::{solve_2, part_2};
+use simple::{rock, Output2};
 
 lazy_static! {
-    pub static ref INPUT: &'static str = include_str!("../../input");
+    pub static ref INPUT: &'static str = aoc::input!();
 }
 
 #[derive(Debug, PartialEq)]
@@ -28,32 +32,6 @@ struct Hail2 {
     q: f64,
 }
 
-impl FromStr for Hail2 {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (position, velocity) = input.split_once(" @ ").ok_or("invalid hail")?;
-
-        let parse = |part: &str| {
-            let mut part = part.split(", ");
-            Ok([
-                part.next()
-                    .ok_or("cannot find x")?
-                    .trim_start()
-                    .parse::<f64>()
-                    .map_err(|_| "invalid x")?,
-                part.next()
-                    .ok_or("cannot find y")?
-                    .trim_start()
-                    .parse::<f64>()
-                    .map_err(|_| "invalid y")?,
-            ])
-        };
-
-        Ok(Self::new(parse(position)?, parse(velocity)?))
-    }
-}
-
 impl Hail2 {
     fn new(position: [f64; 2], velocity: [f64; 2]) -> Self {
         let dx = velocity[0];
@@ -81,59 +59,95 @@ impl Hail2 {
     }
 }
 
+/// Hailstone, with its position and velocity.
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Hail3 {
-    position: [f64; 3],
-    velocity: [f64; 3],
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hail3 {
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
 }
 
-impl FromStr for Hail3 {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (position, velocity) = input.split_once(" @ ").ok_or("invalid hail")?;
+impl Hail3 {
+    fn parse(parser: Parser, input: &str) -> Result<Self, ParseError> {
+        let (position, velocity) = parser.split_once(input, " @ ")?;
 
         let parse = |part: &str| {
-            let mut part = part.split(", ");
-            Ok([
-                part.next()
-                    .ok_or("cannot find x")?
-                    .trim_start()
-                    .parse::<f64>()
-                    .map_err(|_| "invalid x")?,
-                part.next()
-                    .ok_or("cannot find y")?
-                    .trim_start()
-                    .parse::<f64>()
-                    .map_err(|_| "invalid y")?,
+            let mut part = part.split(',');
+            let mut coordinate = || {
                 part.next()
-                    .ok_or("cannot find z")?
-                    .trim_start()
-                    .parse::<f64>()
-                    .map_err(|_| "invalid z")?,
-            ])
+                    .ok_or_else(|| parser.error(input, ErrorKind::Expected("3 coordinates")))
+                    .and_then(|c| parser.number::<f64>(c.trim()))
+            };
+            let coordinates = [coordinate()?, coordinate()?, coordinate()?];
+
+            match part.next() {
+                Some(c) => Err(parser.error(c, ErrorKind::Expected("3 coordinates"))),
+                None => Ok(coordinates),
+            }
         };
 
         Ok(Self::new(parse(position)?, parse(velocity)?))
     }
-}
 
-impl Hail3 {
     fn new(position: [f64; 3], velocity: [f64; 3]) -> Self {
         Self { position, velocity }
     }
 }
 
-/// Solve part 1 with range
-///
-/// # Panics
-/// Panic if invalid input
-fn solve_1_with_range(input: &str, start: f64, end: f64) -> usize {
+fn parse(input: &str) -> Result<Vec<Hail3>, ParseError> {
+    let parser = Parser::new(input);
+
     let hails = input
         .lines()
-        .map(Hail2::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .expect("invalid input");
+        .map(|line| Hail3::parse(parser, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    if hails.is_empty() {
+        return Err(parser.error_end(ErrorKind::UnexpectedEnd));
+    }
+
+    Ok(hails)
+}
+
+/// Hailstones of the input read a line at a time, keeping only the
+/// first `keep` of them.
+fn parse_reader<R: BufRead>(reader: R, keep: usize) -> Result<Vec<Hail3>, ReadError> {
+    let mut hails = Vec::new();
+    let (mut lines, mut last) = (0, String::new());
+    input::for_each_line(reader, |line| {
+        let hail = Hail3::parse(Parser::new(line), line)?;
+        if hails.len() < keep {
+            hails.push(hail);
+        }
+        lines += 1;
+        last.replace_range(.., line);
+        Ok(())
+    })?;
+
+    // at the end of the last line, as for the input trimmed
+    let error_end = |kind| {
+        let mut error = Parser::new(&last).error_end(kind);
+        error.line += lines.max(1) - 1;
+        error
+    };
+    if hails.is_empty() {
+        return Err(error_end(ErrorKind::UnexpectedEnd).into());
+    }
+    if hails.len() < keep.min(3) {
+        return Err(error_end(ErrorKind::Missing("3 hailstones")).into());
+    }
+
+    Ok(hails)
+}
+
+/// Intersections of the paths of `hails` inside the test area
+/// from `start` to `end`, ignoring the z axis.
+fn intersections(hails: &[Hail3], start: f64, end: f64) -> usize {
+    let hails = hails
+        .iter()
+        .map(|&Hail3 { position, velocity }| {
+            Hail2::new([position[0], position[1]], [velocity[0], velocity[1]])
+        })
+        .collect::<Vec<_>>();
 
     let mut count = 0;
     for (i, a) in hails.iter().enumerate().take(hails.len() - 1) {
@@ -180,15 +194,96 @@ fn solve_1_with_range(input: &str, start: f64, end: f64) -> usize {
 ///
 /// # Panics
 /// Panic if invalid input
-#[allow(clippy::unreadable_literal)]
 pub fn solve_1(input: &str) -> usize {
-    solve_1_with_range(input, 200000000000000., 400000000000000.)
+    Day24::solve_1(input)
+}
+
+/// Solve part 2
+///
+/// # Panics
+/// Panic if invalid input
+pub fn solve_2(input: &str) -> Output2 {
+    Day24::solve_2(input)
+}
+
+/// Solve part 1, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_1(input: &str) -> Result<usize, ParseError> {
+    Day24::try_solve_1(input)
+}
+
+/// Solve part 2, without panicking on invalid input
+///
+/// # Errors
+/// Return the first error found in the input
+pub fn try_solve_2(input: &str) -> Result<Output2, ParseError> {
+    Day24::try_solve_2(input)
+}
+
+/// Solve part 1 reading the input a line at a time, the hailstones
+/// are all kept
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_1_reader<R: BufRead>(reader: R) -> Result<usize, ReadError> {
+    Ok(Day24::part_1(&parse_reader(reader, usize::MAX)?))
+}
+
+/// Solve part 2 reading the input a line at a time, keeping only the
+/// first 3 hailstones
+///
+/// # Errors
+/// Return the first error reading or found in the input
+pub fn solve_2_reader<R: BufRead>(reader: R) -> Result<Output2, ReadError> {
+    Ok(Day24::part_2(&parse_reader(reader, 3)?))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Never Tell Me The Odds";
+    const FEATURES: &'static [&'static str] = &[
+        #[cfg(feature = "z3")]
+        "z3",
+    ];
+
+    type Input<'a> = Vec<Hail3>;
+    type Output1 = usize;
+    type Output2 = Output2;
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    #[allow(clippy::unreadable_literal)]
+    fn part_1(hails: &Self::Input<'_>) -> Self::Output1 {
+        intersections(hails, 200000000000000., 400000000000000.)
+    }
+
+    fn part_2(hails: &Self::Input<'_>) -> Self::Output2 {
+        rock(hails)
+    }
+
+    fn try_part_2(hails: &Self::Input<'_>, input: &str) -> Result<Self::Output2, ParseError> {
+        if hails.len() < 3 {
+            return Err(Parser::new(input).error_end(ErrorKind::Missing("3 hailstones")));
+        }
+
+        Ok(rock(hails))
+    }
 }
 
 pub fn part_1() -> usize {
     solve_1(&INPUT)
 }
 
+pub fn part_2() -> Output2 {
+    solve_2(&INPUT)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,6 +294,48 @@ mod tests {
 
     #[test]
     fn same_results_1() {
-        assert_eq!(solve_1_with_range(&EXAMPLE_1, 7., 27.), 2);
+        assert_eq!(intersections(&Day24::parse(&EXAMPLE_1), 7., 27.), 2);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = try_solve_1("19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.kind, ErrorKind::Expected("3 coordinates"));
+
+        let error = try_solve_1("19, 13, 30 @ -2, 1, x").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 21, "x")
+        );
+        assert_eq!(error.kind, ErrorKind::InvalidNumber);
+
+        let error = try_solve_2("19, 13, 30 @ -2, 1, -2").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Missing("3 hailstones"));
+    }
+
+    #[test]
+    fn same_results_reader() {
+        for input in [*EXAMPLE_1, *INPUT] {
+            assert_eq!(solve_1_reader(input.as_bytes()).unwrap(), solve_1(input));
+            assert_eq!(solve_2_reader(input.as_bytes()).unwrap(), solve_2(input));
+        }
+    }
+
+    #[test]
+    fn invalid_input_reader() {
+        let Err(ReadError::Parse(error)) =
+            solve_2_reader("19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1".as_bytes())
+        else {
+            panic!("invalid input solved");
+        };
+        assert_eq!((error.line, error.column), (2, 1));
+
+        for input in ["", "19, 13, 30 @ -2, 1, -2\n"] {
+            let Err(ReadError::Parse(error)) = solve_2_reader(input.as_bytes()) else {
+                panic!("invalid input solved");
+            };
+            assert_eq!(error, try_solve_2(input).unwrap_err());
+        }
     }
 }
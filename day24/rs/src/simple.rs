@@ -1,10 +1,9 @@
-use super::{Hail3, INPUT};
+use super::Hail3;
 
-/// Solve part 2
-///
-/// # Panics
-/// Panic if invalid input
-pub fn solve_2(input: &str) -> String {
+pub(crate) type Output2 = String;
+
+/// Python script solving part 2 with z3 on the first 3 `hails`
+pub(crate) fn rock(hails: &[Hail3]) -> String {
     [
         "\n# run this on python3 with z3\n",
         "from z3 import *\n",
@@ -17,12 +16,11 @@ pub fn solve_2(input: &str) -> String {
     .into_iter()
     .map(str::to_string)
     .chain(
-        input
-            .lines()
-            .map(|line| line.parse::<Hail3>().expect("invalid input"))
+        hails
+            .iter()
             .enumerate()
             .take(3)
-            .flat_map(|(i, Hail3 { position, velocity })| {
+            .flat_map(|(i, &Hail3 { position, velocity })| {
                 position
                     .iter()
                     .zip(velocity)
@@ -34,7 +32,3 @@ pub fn solve_2(input: &str) -> String {
     .chain(["   answer == p0 + p1 + p2\n", ")\n"].map(str::to_string))
     .collect::<String>()
 }
-
-pub fn part_2() -> String {
-    solve_2(&INPUT)
-}
@@ -1,13 +1,15 @@
-use super::{Hail3, INPUT};
+use super::Hail3;
 
 use z3::{Config, Context, Solver, SatResult};
 use z3::ast::{Int, Ast};
 
-/// Solve part 2
+pub(crate) type Output2 = i64;
+
+/// Sum of the coordinates of the rock position hitting the first 3 `hails`
 ///
 /// # Panics
-/// Panic if invalid input
-pub fn solve_2(input: &str) -> i64 {
+/// Panic if z3 cannot find a solution
+pub(crate) fn rock(hails: &[Hail3]) -> i64 {
     let cfg = Config::default();
     let ctx = Context::new(&cfg);
 
@@ -17,12 +19,11 @@ pub fn solve_2(input: &str) -> i64 {
     
     let solver = Solver::new(&ctx);
 
-    input
-        .lines()
-        .map(|line| line.parse::<Hail3>().expect("invalid input"))
+    hails
+        .iter()
         .take(3)
         .zip([&t0, &t1, &t2])
-        .for_each(|(Hail3 { position, velocity }, t)| {
+        .for_each(|(&Hail3 { position, velocity }, t)| {
             position
                 .iter()
                 .zip(velocity)
@@ -46,13 +47,9 @@ pub fn solve_2(input: &str) -> i64 {
     p0 + p1 + p2
 }
 
-pub fn part_2() -> i64 {
-    solve_2(&INPUT)
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::solve_2;
 
     use lazy_static::lazy_static;
     
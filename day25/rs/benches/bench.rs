@@ -11,6 +11,8 @@ lazy_static! {
 }
 
 pub fn criterion_benchmark(c: &mut Criterion) {
+    aoc::memory::report("test part 1", || solve_1(&EXAMPLE_1));
+
     c.bench_function("test part 1", |b| b.iter(|| black_box(solve_1(&EXAMPLE_1))));
 }
 
@@ -1,21 +1,5 @@
 #![deny(clippy::pedantic)]
 
-use std::fmt::Debug;
+pub type Part1 = rsui::Part1<rs::Day25>;
 
-use rsui::Function;
-
-pub struct Part1;
-
-impl Function for Part1 {
-    fn f(input: &str) -> impl Debug {
-        rs::solve_1(input)
-    }
-}
-
-pub struct Part2;
-
-impl Function for Part2 {
-    fn f(input: &str) -> impl Debug {
-        rs::solve_2(input)
-    }
-}
+pub type Part2 = rsui::Part2<rs::Day25>;